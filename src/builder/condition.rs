@@ -0,0 +1,396 @@
+use std::cmp::Ordering;
+
+use crate::{common::errors::Result, olr_err};
+use crate::common::OLRErrorCode::*;
+
+use super::values::ColumnValue;
+
+/// Row filter from "condition" field of table filter. Grammar:
+///   expr    := and { OR and }
+///   and     := not { AND not }
+///   not     := NOT not | '(' expr ')' | operand predicate
+///   predicate := IS [NOT] NULL | [NOT] IN '(' operand { ',' operand } ')' | [NOT] LIKE 'pattern' | cmp operand
+///   cmp     := = | != | <> | < | <= | > | >=
+///   operand := column | 'string' | number | NULL
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+    Not(Box<Condition>),
+    Compare(Operand, Ordering, bool, Operand), // `bool` is negation, so "!=" is (Equal, true) and "<=" is (Greater, true)
+    IsNull(Operand, bool),
+    In(Operand, Vec<Operand>, bool),
+    Like(Operand, String, bool),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operand {
+    Column(String),
+    Literal(ColumnValue),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    Text(String),
+    Number(String),
+    Symbol(&'static str),
+}
+
+impl Condition {
+    pub fn parse(text : &str) -> Result<Self> {
+        let tokens = Self::tokenize(text)?;
+        let mut parser = ConditionParser { tokens, position : 0 };
+        let result = parser.parse_or()?;
+
+        if let Some(token) = parser.peek() {
+            return olr_err!(NotValidField, "Unexpected token {:?} in condition: {}", token, text);
+        }
+
+        Ok(result)
+    }
+
+    /// Evaluates condition using SQL three-valued logic, unknown result does not match
+    pub fn matches(&self, row : &dyn Fn(&str) -> ColumnValue) -> bool {
+        self.evaluate(row).unwrap_or(false)
+    }
+
+    fn evaluate(&self, row : &dyn Fn(&str) -> ColumnValue) -> Option<bool> {
+        match self {
+            Condition::And(left, right) => match (left.evaluate(row), right.evaluate(row)) {
+                (Some(false), _) | (_, Some(false)) => Some(false),
+                (Some(true), Some(true)) => Some(true),
+                _ => None,
+            },
+            Condition::Or(left, right) => match (left.evaluate(row), right.evaluate(row)) {
+                (Some(true), _) | (_, Some(true)) => Some(true),
+                (Some(false), Some(false)) => Some(false),
+                _ => None,
+            },
+            Condition::Not(inner) => inner.evaluate(row).map(|x| !x),
+            Condition::Compare(left, ordering, negate, right) => {
+                left.value(row).compare(&right.value(row)).map(|x| (x == *ordering) != *negate)
+            },
+            Condition::IsNull(operand, negate) => Some(operand.value(row).is_null() != *negate),
+            Condition::In(operand, list, negate) => {
+                let value = operand.value(row);
+                if value.is_null() {
+                    return None;
+                }
+
+                let mut result = Some(false);
+                for item in list {
+                    match value.compare(&item.value(row)) {
+                        Some(Ordering::Equal) => return Some(!*negate),
+                        None => result = None,
+                        _ => (),
+                    }
+                }
+                result.map(|x| x != *negate)
+            },
+            Condition::Like(operand, pattern, negate) => {
+                let pattern : Vec<char> = pattern.chars().collect();
+                operand.value(row).as_text().map(|x| Self::like(&x.chars().collect::<Vec<char>>(), &pattern) != *negate)
+            },
+        }
+    }
+
+    /// Matches with two positions and backtracking to the last '%', which takes one more char on mismatch
+    fn like(text : &[char], pattern : &[char]) -> bool {
+        let (mut t, mut p) = (0, 0);
+        let mut last_percent : Option<(usize, usize)> = None;
+
+        while t < text.len() {
+            match pattern.get(p) {
+                Some('%') => {
+                    last_percent = Some((p, t));
+                    p += 1;
+                },
+                Some(&c) if c == '_' || c == text[t] => {
+                    t += 1;
+                    p += 1;
+                },
+                _ => match last_percent {
+                    Some((percent_p, percent_t)) => {
+                        last_percent = Some((percent_p, percent_t + 1));
+                        p = percent_p + 1;
+                        t = percent_t + 1;
+                    },
+                    None => return false,
+                },
+            }
+        }
+
+        pattern[p ..].iter().all(|x| *x == '%')
+    }
+
+    fn tokenize(text : &str) -> Result<Vec<Token>> {
+        let chars : Vec<char> = text.chars().collect();
+        let mut result = Vec::new();
+        let mut i = 0;
+
+        while i < chars.len() {
+            let c = chars[i];
+
+            if c.is_whitespace() {
+                i += 1;
+            } else if c == '\'' || c == '"' {
+                let mut value = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        None => return olr_err!(NotValidField, "Not closed quote in condition: {}", text),
+                        Some(&x) if x == c && chars.get(i + 1) == Some(&c) => { value.push(c); i += 2; },
+                        Some(&x) if x == c => { i += 1; break; },
+                        Some(&x) => { value.push(x); i += 1; },
+                    }
+                }
+                result.push(if c == '\'' { Token::Text(value) } else { Token::Quoted(value) });
+            } else if c.is_ascii_digit() || (c == '-' && chars.get(i + 1).is_some_and(|x| x.is_ascii_digit())) {
+                let start = i;
+                let mut has_point = false;
+                i += 1;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    if chars[i] == '.' && std::mem::replace(&mut has_point, true) {
+                        return olr_err!(NotValidField, "Not valid number {} in condition: {}", chars[start ..= i].iter().collect::<String>(), text);
+                    }
+                    i += 1;
+                }
+                result.push(Token::Number(chars[start .. i].iter().collect()));
+            } else if c.is_alphabetic() || c == '_' {
+                let start = i;
+                while i < chars.len() && (chars[i].is_alphanumeric() || ['_', '$', '#'].contains(&chars[i])) {
+                    i += 1;
+                }
+                result.push(Token::Word(chars[start .. i].iter().collect::<String>().to_uppercase()));
+            } else {
+                let symbol = ["<=", ">=", "<>", "!=", "=", "<", ">", "(", ")", ","]
+                    .into_iter()
+                    .find(|x| chars[i ..].starts_with(&x.chars().collect::<Vec<char>>()));
+
+                match symbol {
+                    Some(x) => { result.push(Token::Symbol(x)); i += x.len(); },
+                    None => return olr_err!(NotValidField, "Unexpected symbol '{}' in condition: {}", c, text),
+                }
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+impl Operand {
+    fn value(&self, row : &dyn Fn(&str) -> ColumnValue) -> ColumnValue {
+        match self {
+            Operand::Column(name) => row(name),
+            Operand::Literal(value) => value.clone(),
+        }
+    }
+}
+
+struct ConditionParser {
+    tokens : Vec<Token>,
+    position : usize,
+}
+
+impl ConditionParser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Result<Token> {
+        match self.tokens.get(self.position) {
+            Some(token) => {
+                self.position += 1;
+                Ok(token.clone())
+            },
+            None => olr_err!(NotValidField, "Unexpected end of condition"),
+        }
+    }
+
+    fn accept_word(&mut self, word : &str) -> bool {
+        if self.peek() == Some(&Token::Word(word.to_string())) {
+            self.position += 1;
+            return true;
+        }
+        false
+    }
+
+    fn expect_symbol(&mut self, symbol : &'static str) -> Result<()> {
+        match self.next()? {
+            Token::Symbol(x) if x == symbol => Ok(()),
+            token => olr_err!(NotValidField, "Expected '{}' in condition, but got: {:?}", symbol, token),
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Condition> {
+        let mut result = self.parse_and()?;
+        while self.accept_word("OR") {
+            result = Condition::Or(result.into(), self.parse_and()?.into());
+        }
+        Ok(result)
+    }
+
+    fn parse_and(&mut self) -> Result<Condition> {
+        let mut result = self.parse_not()?;
+        while self.accept_word("AND") {
+            result = Condition::And(result.into(), self.parse_not()?.into());
+        }
+        Ok(result)
+    }
+
+    fn parse_not(&mut self) -> Result<Condition> {
+        if self.accept_word("NOT") {
+            return Ok(Condition::Not(self.parse_not()?.into()));
+        }
+
+        if self.peek() == Some(&Token::Symbol("(")) {
+            self.position += 1;
+            let result = self.parse_or()?;
+            self.expect_symbol(")")?;
+            return Ok(result);
+        }
+
+        let operand = self.parse_operand()?;
+        self.parse_predicate(operand)
+    }
+
+    fn parse_predicate(&mut self, operand : Operand) -> Result<Condition> {
+        if self.accept_word("IS") {
+            let negate = self.accept_word("NOT");
+            if !self.accept_word("NULL") {
+                return olr_err!(NotValidField, "Expected NULL after IS in condition");
+            }
+            return Ok(Condition::IsNull(operand, negate));
+        }
+
+        let negate = self.accept_word("NOT");
+
+        if self.accept_word("IN") {
+            self.expect_symbol("(")?;
+            let mut list = vec![self.parse_operand()?];
+            loop {
+                match self.next()? {
+                    Token::Symbol(",") => list.push(self.parse_operand()?),
+                    Token::Symbol(")") => break,
+                    token => return olr_err!(NotValidField, "Unexpected token {:?} in IN list of condition", token),
+                }
+            }
+            return Ok(Condition::In(operand, list, negate));
+        }
+
+        if self.accept_word("LIKE") {
+            return match self.next()? {
+                Token::Text(pattern) => Ok(Condition::Like(operand, pattern, negate)),
+                token => olr_err!(NotValidField, "Expected string after LIKE in condition, but got: {:?}", token),
+            };
+        }
+
+        if negate {
+            return olr_err!(NotValidField, "Expected IN or LIKE after NOT in condition");
+        }
+
+        let (ordering, negate) = match self.next()? {
+            Token::Symbol("=") => (Ordering::Equal, false),
+            Token::Symbol("!=") | Token::Symbol("<>") => (Ordering::Equal, true),
+            Token::Symbol("<") => (Ordering::Less, false),
+            Token::Symbol(">=") => (Ordering::Less, true),
+            Token::Symbol(">") => (Ordering::Greater, false),
+            Token::Symbol("<=") => (Ordering::Greater, true),
+            token => return olr_err!(NotValidField, "Expected comparison in condition, but got: {:?}", token),
+        };
+
+        Ok(Condition::Compare(operand, ordering, negate, self.parse_operand()?))
+    }
+
+    fn parse_operand(&mut self) -> Result<Operand> {
+        match self.next()? {
+            Token::Word(x) if x == "NULL" => Ok(Operand::Literal(ColumnValue::Null)),
+            Token::Word(x) | Token::Quoted(x) => Ok(Operand::Column(x)),
+            Token::Text(x) => Ok(Operand::Literal(ColumnValue::Text(x))),
+            Token::Number(x) => Ok(Operand::Literal(ColumnValue::Number(x))),
+            token => olr_err!(NotValidField, "Expected column or literal in condition, but got: {:?}", token),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::builder::values::ColumnValue;
+
+    use super::{Condition, Operand};
+
+    fn row(name : &str) -> ColumnValue {
+        match name {
+            "REGION" => ColumnValue::Text("EU".to_string()),
+            "AMOUNT" => ColumnValue::Number("150.5".to_string()),
+            "CODE" => ColumnValue::Text("ORD-17".to_string()),
+            _ => ColumnValue::Null,
+        }
+    }
+
+    #[test]
+    fn test_condition() {
+        let check = |text : &str| Condition::parse(text).unwrap().matches(&row);
+
+        assert!(check("REGION = 'EU'"));
+        assert!(check("region = 'EU' and amount > 100"));
+        assert!(!check("REGION = 'EU' AND NOT AMOUNT >= 100"));
+        assert!(check("REGION IN ('US', 'EU') OR MISSING = 1"));
+        assert!(check("MISSING IS NULL AND AMOUNT IS NOT NULL"));
+        assert!(check("CODE LIKE 'ORD-%'"));
+        assert!(!check("MISSING = 1 OR MISSING != 1"));
+        assert!(!check("NOT (MISSING = 1)"));
+    }
+
+    #[test]
+    fn test_null_logic() {
+        let check = |text : &str| Condition::parse(text).unwrap().matches(&row);
+
+        // Unknown does not match, NOT of unknown is unknown too
+        assert!(!check("MISSING = 1"));
+        assert!(!check("NOT MISSING = 1"));
+        assert!(!check("MISSING IN (1, 2)"));
+        assert!(!check("MISSING NOT IN (1, 2)"));
+        assert!(!check("AMOUNT NOT IN (1, NULL)"));
+        assert!(check("AMOUNT IN (150.5, NULL)"));
+        assert!(!check("MISSING LIKE '%'"));
+        assert!(!check("MISSING NOT LIKE 'A%'"));
+
+        // AND with false and OR with true are known
+        assert!(check("NOT (MISSING = 1 AND AMOUNT < 0)"));
+        assert!(!check("NOT (MISSING = 1 AND AMOUNT > 0)"));
+        assert!(check("MISSING = 1 OR AMOUNT > 0"));
+        assert!(!check("MISSING = 1 AND AMOUNT > 0"));
+        assert!(check("NOT (MISSING = 1 OR AMOUNT < 0) OR MISSING IS NULL"));
+    }
+
+    #[test]
+    fn test_like() {
+        let check = |text : &str| Condition::parse(text).unwrap().matches(&row);
+
+        assert!(check("CODE LIKE 'ORD-__'"));
+        assert!(!check("CODE LIKE 'ORD-_'"));
+        assert!(check("CODE LIKE '%-%7'"));
+        assert!(check("CODE LIKE '%%D%'"));
+        assert!(check("CODE LIKE '_R%1_'"));
+        assert!(!check("CODE LIKE '%8'"));
+        assert!(check("CODE NOT LIKE 'ord%'"));
+        assert!(check("REGION LIKE 'EU%'"));
+        assert!(!check("REGION LIKE '_'"));
+
+        // Pattern with many '%' does not backtrack exponentially
+        let text = Operand::Literal(ColumnValue::Text("a".repeat(200)));
+        assert!(!Condition::Like(text, format!("{}b", "%a".repeat(30)), false).matches(&row));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for text in ["REGION = ", "REGION = 'EU' AMOUNT", "AMOUNT > 1.2.3", "REGION = 'EU", "REGION IS 1", "REGION NOT = 1",
+                     "REGION LIKE 1", "REGION IN ('EU'", "(REGION = 'EU'", "AMOUNT ? 1"] {
+            assert!(Condition::parse(text).is_err(), "{}", text);
+        }
+        assert!(Condition::parse("AMOUNT > -1.25").is_ok());
+    }
+}
@@ -56,6 +56,7 @@ pub const SCHEMA_FORMAT_NAME : u8 = 0;
 pub const SCHEMA_FORMAT_FULL : u8 = 1;
 pub const SCHEMA_FORMAT_REPEATED : u8 =2;
 pub const SCHEMA_FORMAT_OBJ : u8 = 4;
+pub const SCHEMA_FORMAT_PARTITION : u8 = 8;
 
 pub const TIMESTAMP_JUST_BEGIN : u8 = 0;
 pub const TIMESTAMP_ALL_PAYLOADS : u8 = 1;
@@ -108,6 +109,7 @@ pub const VALUE_AFTER_SUPP : u8 = 3;
 pub const XID_FORMAT_TEXT_HEX : u8 = 0;
 pub const XID_FORMAT_TEXT_DEC : u8 = 1;
 pub const XID_FORMAT_NUMERIC : u8 = 2;
+pub const XID_FORMAT_TEXT_REVERSED : u8 = 3;
 
 #[derive(Debug)]
 pub struct BuilderFormats {
@@ -0,0 +1,151 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{common::{constants, types::TypeLobId}, locales::Locales, oradefs::oracle_table::OracleColumn};
+
+use super::values::ColumnValue;
+
+const LOCATOR_MIN_SIZE : usize = 20;
+const LOCATOR_OFFSET_FLAGS1 : usize = 4;
+const LOCATOR_OFFSET_FLAGS2 : usize = 5;
+const LOCATOR_OFFSET_LOB_ID : usize = 10;
+const LOCATOR_OFFSET_INLINE_SIZE : usize = 20;
+const LOCATOR_OFFSET_SIZE : usize = 30;
+const LOCATOR_INLINE_HEADER_SIZE : usize = 16;
+
+const LOCATOR_FLAGS1_VARYING_WIDTH : u8 = 0x80; // Character data is in AL16UTF16
+const LOCATOR_FLAGS2_INLINE : u8 = 0x08;        // Data is stored in row after locator
+
+/// LOB locator from row data
+#[derive(Debug, PartialEq)]
+pub struct LobLocator<'a> {
+    pub lob_id : TypeLobId,
+    pub is_varying_width : bool,
+    pub inline : Option<&'a [u8]>,
+    pub size : Option<usize>,
+}
+
+impl<'a> LobLocator<'a> {
+    pub fn parse(data : &'a [u8]) -> Option<Self> {
+        if data.len() < LOCATOR_MIN_SIZE {
+            return None;
+        }
+
+        let lob_id = TypeLobId::from_bytes(&data[LOCATOR_OFFSET_LOB_ID ..])?;
+        let is_varying_width = data[LOCATOR_OFFSET_FLAGS1] & LOCATOR_FLAGS1_VARYING_WIDTH != 0;
+        let read_u16 = |offset : usize| data.get(offset .. offset + 2).map(|x| u16::from_be_bytes([x[0], x[1]]) as usize);
+        let read_u32 = |offset : usize| data.get(offset .. offset + 4).map(|x| u32::from_be_bytes([x[0], x[1], x[2], x[3]]) as usize);
+
+        if data[LOCATOR_OFFSET_FLAGS2] & LOCATOR_FLAGS2_INLINE != 0 {
+            // Inline body: its length, header and data
+            let body_size = read_u16(LOCATOR_OFFSET_INLINE_SIZE)?;
+            let start = LOCATOR_OFFSET_INLINE_SIZE + 2 + LOCATOR_INLINE_HEADER_SIZE;
+            let end = LOCATOR_OFFSET_INLINE_SIZE + 2 + body_size;
+            let inline = data.get(start .. end.min(data.len()))?;
+
+            return Some(Self { lob_id, is_varying_width, inline : Some(inline), size : Some(inline.len()) });
+        }
+
+        Some(Self { lob_id, is_varying_width, inline : None, size : read_u32(LOCATOR_OFFSET_SIZE) })
+    }
+}
+
+/// Pages of LOBs written in transaction
+#[derive(Debug, Default)]
+pub struct LobContext {
+    pages : HashMap<TypeLobId, BTreeMap<u32, Vec<u8>>>,
+}
+
+impl LobContext {
+    pub fn add_page(&mut self, lob_id : TypeLobId, page_no : u32, data : &[u8]) {
+        self.pages.entry(lob_id).or_default().insert(page_no, data.to_vec());
+    }
+
+    /// Data of LOB assembled from pages in order of their numbers and cut to size from locator
+    pub fn get(&self, lob_id : &TypeLobId, size : Option<usize>) -> Option<Vec<u8>> {
+        let mut result : Vec<u8> = self.pages.get(lob_id)?.values().flatten().cloned().collect();
+        if let Some(size) = size {
+            result.truncate(size);
+        }
+        Some(result)
+    }
+
+    /// Full value of CLOB, NCLOB or BLOB column. Character LOBs of varying width are in AL16UTF16,
+    /// others are in database or national charset of column. None if out of row LOB is not changed
+    /// in transaction, its value is unknown and locator is not a value of column.
+    pub fn decode(&self, column : &OracleColumn, data : &[u8], is_null : bool, locales : &Locales) -> Option<ColumnValue> {
+        if is_null || data.is_empty() {
+            return Some(ColumnValue::Null);
+        }
+
+        let locator = match LobLocator::parse(data) {
+            Some(locator) => locator,
+            None => return Some(ColumnValue::Raw(data.to_vec())),
+        };
+
+        let value = match locator.inline {
+            Some(inline) => inline.to_vec(),
+            None => self.get(&locator.lob_id, locator.size)?,
+        };
+
+        Some(match column.type_no {
+            constants::SYS_COL_TYPE_CLOB if locator.is_varying_width => {
+                let chars : Vec<u16> = value.chunks_exact(2).map(|x| u16::from_be_bytes([x[0], x[1]])).collect();
+                ColumnValue::Text(String::from_utf16_lossy(&chars))
+            },
+            constants::SYS_COL_TYPE_CLOB => ColumnValue::Text(locales.decode_text(&value, column.charset_form)),
+            _ => ColumnValue::Raw(value),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{builder::values::ColumnValue, common::{constants, types::TypeLobId}, locales::Locales, oradefs::oracle_table::OracleColumn};
+
+    use super::LobContext;
+
+    fn column(type_no : u16) -> OracleColumn {
+        OracleColumn { col_no : 1, seg_col_no : 1, name : "DOC".to_string(), type_no, length : 4000, 
+                       precision : None, scale : None, nullable : true, charset_form : 1, property : 0 }
+    }
+
+    #[test]
+    fn test_lob_values() {
+        let lob_id = [0, 0, 0, 1, 0, 0, 0, 0, 0x12, 0x34];
+
+        // Inline CLOB in AL16UTF16
+        let mut locator = vec![0u8; 22];
+        locator[4] = 0x80;
+        locator[5] = 0x08;
+        locator[10 .. 20].copy_from_slice(&lob_id);
+        locator[21] = 16 + 4;
+        locator.extend([0u8; 16]);
+        locator.extend([0, b'O', 0, b'K']);
+
+        let locales = Locales::new();
+        locales.set_charsets(178, 2000).unwrap();
+
+        let lobs = LobContext::default();
+        assert_eq!(lobs.decode(&column(constants::SYS_COL_TYPE_CLOB), &locator, false, &locales), Some(ColumnValue::Text("OK".to_string())));
+
+        // Inline CLOB of fixed width in WE8MSWIN1252
+        locator[4] = 0;
+        locator.truncate(22 + 16);
+        locator[21] = 16 + 2;
+        locator.extend([0x80, b'1']);
+        assert_eq!(lobs.decode(&column(constants::SYS_COL_TYPE_CLOB), &locator, false, &locales), Some(ColumnValue::Text("\u{20AC}1".to_string())));
+
+        // Out of row BLOB from two pages
+        let mut locator = vec![0u8; 34];
+        locator[10 .. 20].copy_from_slice(&lob_id);
+        locator[33] = 5;
+
+        // Locator of LOB which is not changed in transaction is not its value
+        let mut lobs = LobContext::default();
+        assert_eq!(lobs.decode(&column(constants::SYS_COL_TYPE_BLOB), &locator, false, &locales), None);
+
+        lobs.add_page(TypeLobId::from_bytes(&lob_id).unwrap(), 1, &[4, 5, 6, 7]);
+        lobs.add_page(TypeLobId::from_bytes(&lob_id).unwrap(), 0, &[1, 2, 3]);
+        assert_eq!(lobs.decode(&column(constants::SYS_COL_TYPE_BLOB), &locator, false, &locales), Some(ColumnValue::Raw(vec![1, 2, 3, 4, 5])));
+    }
+}
@@ -1,14 +1,73 @@
-use std::{collections::BTreeMap, fs::OpenOptions, io::Write, sync::{Arc, Mutex}};
+use std::{collections::{BTreeMap, HashMap, HashSet}, sync::{Arc, Mutex}};
 use log::{debug, warn};
 
-use formats::BuilderFormats;
+use formats::{BuilderFormats, ATTRIBUTES_FORMAT_BEGIN, ATTRIBUTES_FORMAT_COMMIT, ATTRIBUTES_FORMAT_DML, DB_FORMAT_ADD_DDL, MESSAGE_FORMAT_ADD_OFFSET, MESSAGE_FORMAT_ADD_SEQUENCES, MESSAGE_FORMAT_FULL, MESSAGE_FORMAT_SKIP_BEGIN, MESSAGE_FORMAT_SKIP_COMMIT, RID_FORMAT_TEXT, SCN_ALL_COMMIT_VALUE, SCN_ALL_PAYLOADS, SCN_FORMAT_TEXT_HEX, SCHEMA_FORMAT_FULL, SCHEMA_FORMAT_OBJ, SCHEMA_FORMAT_PARTITION, SCHEMA_FORMAT_REPEATED, UNKNOWN_TYPE_SHOW, XID_FORMAT_NUMERIC, XID_FORMAT_TEXT_DEC, XID_FORMAT_TEXT_REVERSED};
+use lobs::LobContext;
 use queue::BuilderQueue;
-use serde_json::json;
+use values::ColumnValue;
+use writer::MessageWriter;
+use serde_json::{json, Value};
 
-use crate::{common::{errors::Result, types::{TypeRecordScn, TypeTimestamp, TypeXid}}, ctx::Ctx, locales::Locales, metadata::Metadata, parser::opcodes::{opcode0501::OpCode0501, opcode1102::OpCode1102}};
+use crate::{common::{errors::Result, types::{TypeFb, TypeRecordScn, TypeRedoPosition, TypeRowId, TypeTimestamp, TypeXid}}, ctx::Ctx, locales::Locales, metadata::Metadata, olr_perr, oradefs::oracle_table::OracleTable, parser::opcodes::{opcode0501::OpCode0501, opcode1002::OpCode1002, opcode1004::OpCode1004, opcode1018::OpCode1018, opcode1102::OpCode1102, opcode1103::OpCode1103, opcode1105::OpCode1105, opcode1106::OpCode1106, opcode1111::OpCode1111, opcode1112::OpCode1112, opcode1801::OpCode1801, RowColumn, Vector, VectorData}, transactions::transaction::SessionAttributes};
 
+pub mod condition;
 pub mod formats;
+pub mod lobs;
 pub mod queue;
+pub mod values;
+pub mod writer;
+
+/// Column of row piece which is kept until the whole row is collected
+type PieceColumn = (usize, Option<Vec<u8>>);
+
+/// Changed row, which is shown in message
+#[derive(Debug, Clone, Copy)]
+struct RowLocation {
+    obj : u32,
+    data_obj : u32,
+    xid : TypeXid,
+    bdba : u32,
+    slot : u16,
+}
+
+impl From<&OpCode0501<'_>> for RowLocation {
+    fn from(undo : &OpCode0501) -> Self {
+        Self { obj : undo.obj, data_obj : undo.data_obj, xid : undo.xid, bdba : undo.bdba, slot : undo.slot }
+    }
+}
+
+/// Piece of chained or migrated row. Column indexes are counted from the start of piece.
+#[derive(Debug)]
+struct RowPiece {
+    fb : TypeFb,
+    nrid : Option<(u32, u16)>,
+    row : RowLocation,
+    columns : Vec<PieceColumn>,
+    supplog : Vec<PieceColumn>,
+}
+
+/// Head of chained row with columns and supplemental log columns of all its pieces
+type AssembledRow = (RowLocation, Vec<PieceColumn>, Vec<PieceColumn>);
+
+/// Update of row which is split into several pieces, images are collected until the last piece
+#[derive(Debug)]
+struct PieceUpdate {
+    row : RowLocation,
+    before : BTreeMap<usize, Option<Vec<u8>>>,
+    after : BTreeMap<usize, Option<Vec<u8>>>,
+    supplog : Vec<PieceColumn>,
+}
+
+/// Transaction state between begin and commit messages
+#[derive(Debug, Default)]
+struct TransactionMessage {
+    num : u64,
+    commit_scn : Option<TypeRecordScn>,
+    begin : Option<Value>,
+    payload : Vec<Value>,
+    pieces : HashMap<(&'static str, u32, u16), RowPiece>,
+    update : Option<PieceUpdate>,
+}
 
 #[derive(Debug)]
 pub struct JsonBuilder {
@@ -17,15 +76,18 @@ pub struct JsonBuilder {
     metadata_ptr : Arc<Metadata>, 
     formats : BuilderFormats,
     queue : Mutex<BuilderQueue>,
+    transactions : Mutex<HashMap<TypeXid, TransactionMessage>>,
+    sent_schemas : Mutex<HashMap<u32, Arc<OracleTable>>>,
+    writer : Mutex<Box<dyn MessageWriter>>,
 }
 
 impl JsonBuilder {
     pub fn new(context_ptr : Arc<Ctx>, locales_ptr : Arc<Locales>, metadata_ptr : Arc<Metadata>, 
                 db_format : u8, attributes_format : u8, interval_dts_format : u8, interval_ytm_format : u8, message_format : u8, 
                 rid_format : u8, xid_format : u8, timestamp_format : u8, timestamp_tz_format : u8, timestamp_all : u8, char_format : u8,
-                scn_format : u8, scn_all : u8, unknown_format : u8, schema_format : u8, column_format : u8, unknown_type : u8) -> Result<Self> {
+                scn_format : u8, scn_all : u8, unknown_format : u8, schema_format : u8, column_format : u8, unknown_type : u8,
+                writer : Box<dyn MessageWriter>) -> Result<Self> {
         debug!("Initialize JsonBuilder");
-        let _ = OpenOptions::new().write(true).create(true).truncate(true).open("out.txt").unwrap(); // Убрать
         Ok(Self {
             context_ptr : context_ptr.clone(), locales_ptr, metadata_ptr, 
             formats : BuilderFormats {
@@ -48,95 +110,1111 @@ impl JsonBuilder {
                 unknown_type,
             },
             queue : Mutex::new(BuilderQueue::new(context_ptr)?),
+            transactions : Mutex::new(HashMap::new()),
+            sent_schemas : Mutex::new(HashMap::new()),
+            writer : Mutex::new(writer),
         })
     }
 
-    pub fn process_insert(&self, scn : TypeRecordScn, timestamp : TypeTimestamp, undo : OpCode0501, mut redo : OpCode1102) -> Result<()> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn process_dml(&self, scn : TypeRecordScn, timestamp : TypeTimestamp, position : TypeRedoPosition, undo : Vector, redo : Vector, attributes : Option<&SessionAttributes>, lobs : &LobContext) -> Result<()> {
+        match (undo.into_data(), redo.into_data()) {
+            (VectorData::OpCode0501(undo), VectorData::OpCode1002(redo)) => self.process_iot_insert(scn, timestamp, position, undo, redo, attributes, lobs),
+            (VectorData::OpCode0501(undo), VectorData::OpCode1004(redo)) => self.process_iot_delete(scn, timestamp, position, undo, redo, attributes, lobs),
+            (VectorData::OpCode0501(undo), VectorData::OpCode1018(redo)) => self.process_iot_update(scn, timestamp, position, undo, redo, attributes, lobs),
+            (VectorData::OpCode0501(undo), VectorData::OpCode1102(redo)) => self.process_insert(scn, timestamp, position, undo, redo, attributes, lobs),
+            (VectorData::OpCode0501(undo), VectorData::OpCode1103(redo)) => self.process_delete(scn, timestamp, position, undo, redo, attributes, lobs),
+            (VectorData::OpCode0501(undo), VectorData::OpCode1105(redo)) => self.process_update(scn, timestamp, position, undo, redo, attributes, lobs),
+            (VectorData::OpCode0501(undo), VectorData::OpCode1106(redo)) => self.process_overwrite(scn, timestamp, position, undo, redo, attributes, lobs),
+            (VectorData::OpCode0501(undo), VectorData::OpCode1111(redo)) => self.process_multi_insert(scn, timestamp, position, undo, redo, attributes, lobs),
+            (VectorData::OpCode0501(undo), VectorData::OpCode1112(redo)) => self.process_multi_delete(scn, timestamp, position, undo, redo, attributes, lobs),
+            (_, _) => Ok(()),
+        }
+    }
+
+    /// Adds session attributes to message if `attributes_format` has flag of message type
+    fn add_attributes(&self, value : &mut Value, attributes : Option<&SessionAttributes>, flag : u8) {
+        if self.formats.attributes_format & flag == 0 {
+            return;
+        }
+
+        if let Some(attributes) = attributes {
+            value["ATTRIBUTES"] = json!(attributes);
+        }
+    }
+
+    fn scn_value(&self, scn : TypeRecordScn) -> Value {
+        let scn : u64 = scn.into();
+        match self.formats.scn_format & SCN_FORMAT_TEXT_HEX {
+            0 => json!(scn),
+            _ => json!(format!("0x{:016x}", scn)),
+        }
+    }
+
+    /// SCN of change in transaction, or commit SCN when it is known and `SCN_ALL_COMMIT_VALUE` is set
+    fn transaction_scn_value(&self, xid : TypeXid, scn : TypeRecordScn) -> Value {
+        if self.formats.scn_all & SCN_ALL_COMMIT_VALUE != 0 {
+            if let Some(commit_scn) = self.transactions.lock().unwrap().get(&xid).and_then(|x| x.commit_scn) {
+                return self.scn_value(commit_scn);
+            }
+        }
+        self.scn_value(scn)
+    }
+
+    fn xid_value(&self, xid : TypeXid) -> Value {
+        match self.formats.xid_format {
+            XID_FORMAT_TEXT_DEC => json!(xid.to_string()),
+            XID_FORMAT_NUMERIC => json!(u64::from(xid)),
+            XID_FORMAT_TEXT_REVERSED => json!(format!("{:04X}{:04X}{:08X}", 
+                xid.undo_segment_number.swap_bytes(), xid.slot_number.swap_bytes(), xid.sequence_number.swap_bytes())),
+            _ => json!(format!("0x{:04x}.{:03x}.{:08x}", xid.undo_segment_number, xid.slot_number, xid.sequence_number)),
+        }
+    }
 
-        let mut schema = self.metadata_ptr.get_schema();
+    /// Commit SCN has to be known before begin of replayed transaction for `SCN_ALL_COMMIT_VALUE`
+    pub fn set_commit_scn(&self, xid : TypeXid, scn : TypeRecordScn) {
+        self.transactions.lock().unwrap().entry(xid).or_default().commit_scn = Some(scn);
+    }
 
-        let mut guard = self.queue.lock().unwrap();
-        
-        let table = schema.get_table(undo.obj)?;
+    /// Messages of pluggable database are tagged with its name
+    fn write_message(&self, key : Option<&str>, mut value : Value) -> Result<()> {
+        if let Some(container_name) = self.metadata_ptr.container_name() {
+            value["PDB"] = json!(container_name);
+        }
 
-        if table.is_none() {
-            warn!("No table with obj_id: {}", undo.obj);
+        self.writer.lock().unwrap().write(key, &value)
+    }
+
+    /// Sends DML payload as separate message with row `key` or keeps it for transaction message in FULL mode
+    fn push_payload(&self, xid : TypeXid, position : TypeRedoPosition, key : Option<String>, mut value : Value) -> Result<()> {
+        let mut transactions = self.transactions.lock().unwrap();
+        let transaction = transactions.entry(xid).or_default();
+        transaction.num += 1;
+
+        if self.formats.message_format & MESSAGE_FORMAT_ADD_SEQUENCES != 0 {
+            value["NUM"] = json!(transaction.num);
+        }
+
+        if self.formats.message_format & MESSAGE_FORMAT_ADD_OFFSET != 0 {
+            value["REDO_SEQUENCE"] = json!(position.sequence);
+            value["REDO_BLOCK"] = json!(position.block);
+            value["REDO_OFFSET"] = json!(position.offset);
+        }
+
+        if self.formats.message_format & MESSAGE_FORMAT_FULL != 0 {
+            transaction.payload.push(value);
             return Ok(());
         }
 
-        let table = table.unwrap();
+        self.write_message(key.as_deref(), value)
+    }
+
+    /// Owner and table name, OBJ# and column definitions according to `schema_format`
+    fn schema_value(&self, obj : u32, table : &Arc<OracleTable>) -> Value {
+        let mut value = json!({
+            "OWNER": table.owner(),
+            "TABLE": table.name(),
+        });
+
+        if self.formats.schema_format & SCHEMA_FORMAT_OBJ != 0 {
+            value["OBJ"] = json!(obj);
+        }
+
+        if self.formats.schema_format & SCHEMA_FORMAT_PARTITION != 0 {
+            if let Some(partition) = table.partition() {
+                value["PARTITION"] = json!(partition);
+            }
+        }
+
+        if self.formats.schema_format & SCHEMA_FORMAT_FULL != 0 {
+            // Without REPEATED flag columns are sent once per table definition
+            let is_sent = match self.formats.schema_format & SCHEMA_FORMAT_REPEATED {
+                0 => self.sent_schemas.lock().unwrap().insert(obj, table.clone()).is_some_and(|x| Arc::ptr_eq(&x, table)),
+                _ => false,
+            };
+
+            if !is_sent {
+                let columns : Vec<Value> = table.columns().iter().map(|column| json!({
+                    "NAME": column.name,
+                    "TYPE": column.type_name(),
+                    "LENGTH": column.length,
+                    "PRECISION": column.precision,
+                    "SCALE": column.scale,
+                    "NULLABLE": column.nullable,
+                })).collect();
+                value["COLUMNS"] = json!(columns);
+            }
+        }
+
+        value
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn process_insert(&self, scn : TypeRecordScn, timestamp : TypeTimestamp, position : TypeRedoPosition, undo : OpCode0501, redo : OpCode1102, attributes : Option<&SessionAttributes>, lobs : &LobContext) -> Result<()> {
+        if let Some(data) = redo.compressed {
+            return self.process_compressed("insert", scn, timestamp, position, (&undo).into(), data, attributes);
+        }
 
-        let columns_num = redo.cc;
         let mut nulls_reader = redo.get_nulls_field();
-        let mut columns = BTreeMap::new();
+        let mut after = Vec::with_capacity(redo.cc as usize);
 
         let mut nulls: u8 = 0;
-        for i in 0 .. columns_num {
+        for i in 0 .. redo.cc {
             let mask = 1u8 << (i & 0b111);
             if mask == 1 {
                 nulls = nulls_reader.read_u8()?;
             }
             
             let field_reader = redo.get_data_field(i as usize);
-            
-            if nulls & mask == 0 {
-                columns.insert(i, format!("Col {}: {:?}", i, field_reader.data()));
-            } else {
-                columns.insert(i, format!("Col {}: Null", i));
+            after.push((i as usize, if nulls & mask != 0 { None } else { Some(field_reader.data()) }));
+        }
+
+        // Row which does not fit in block is inserted by pieces, the last piece goes first
+        if !redo.fb.is_first() || !redo.fb.is_last() {
+            return self.process_piece("insert", scn, timestamp, position, redo.fb, redo.nrid, (&undo).into(), &after, &[], attributes, lobs);
+        }
+
+        self.process_row("insert", scn, timestamp, position, (&undo).into(), None, Some(&after), attributes, lobs)
+    }
+
+    /// Deleted row is in the undo image, supplemental log adds columns which are not there
+    #[allow(clippy::too_many_arguments)]
+    pub fn process_delete(&self, scn : TypeRecordScn, timestamp : TypeTimestamp, position : TypeRedoPosition, undo : OpCode0501, _redo : OpCode1103, attributes : Option<&SessionAttributes>, lobs : &LobContext) -> Result<()> {
+        if let Some(data) = undo.compressed {
+            return self.process_compressed("delete", scn, timestamp, position, (&undo).into(), data, attributes);
+        }
+
+        if !undo.fb.is_first() || !undo.fb.is_last() {
+            return self.process_piece("delete", scn, timestamp, position, undo.fb, undo.nrid, (&undo).into(), &undo.columns, &undo.supplog_columns, attributes, lobs);
+        }
+
+        let before = Self::merge_columns(&undo.columns, &undo.supplog_columns);
+        self.process_row("delete", scn, timestamp, position, (&undo).into(), Some(&before), None, attributes, lobs)
+    }
+
+    /// Each row of multi-row insert is sent as separate message
+    #[allow(clippy::too_many_arguments)]
+    pub fn process_multi_insert(&self, scn : TypeRecordScn, timestamp : TypeTimestamp, position : TypeRedoPosition, undo : OpCode0501, redo : OpCode1111, attributes : Option<&SessionAttributes>, lobs : &LobContext) -> Result<()> {
+        for row in &redo.rows {
+            let mut location : RowLocation = (&undo).into();
+            location.bdba = redo.bdba;
+            location.slot = row.slot;
+            self.process_row("insert", scn, timestamp, position, location, None, Some(&row.columns), attributes, lobs)?;
+        }
+        Ok(())
+    }
+
+    /// Deleted rows are in the undo image of multi-row delete
+    #[allow(clippy::too_many_arguments)]
+    pub fn process_multi_delete(&self, scn : TypeRecordScn, timestamp : TypeTimestamp, position : TypeRedoPosition, undo : OpCode0501, redo : OpCode1112, attributes : Option<&SessionAttributes>, lobs : &LobContext) -> Result<()> {
+        for row in &undo.rows {
+            let mut location : RowLocation = (&undo).into();
+            location.bdba = redo.bdba;
+            location.slot = row.slot;
+            self.process_row("delete", scn, timestamp, position, location, Some(&row.columns), None, attributes, lobs)?;
+        }
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn process_iot_insert(&self, scn : TypeRecordScn, timestamp : TypeTimestamp, position : TypeRedoPosition, undo : OpCode0501, redo : OpCode1002, attributes : Option<&SessionAttributes>, lobs : &LobContext) -> Result<()> {
+        let location = RowLocation { bdba : redo.bdba, slot : redo.slot, ..(&undo).into() };
+        let after = match self.iot_columns(location, scn, &redo.key, &redo.keydata)? {
+            Some(x) => x,
+            None => return Ok(()),
+        };
+        self.process_row("insert", scn, timestamp, position, location, None, Some(&after), attributes, lobs)
+    }
+
+    /// Deleted row is in the undo of leaf row restore
+    #[allow(clippy::too_many_arguments)]
+    pub fn process_iot_delete(&self, scn : TypeRecordScn, timestamp : TypeTimestamp, position : TypeRedoPosition, undo : OpCode0501, redo : OpCode1004, attributes : Option<&SessionAttributes>, lobs : &LobContext) -> Result<()> {
+        let location = RowLocation { bdba : redo.bdba, slot : redo.slot, ..(&undo).into() };
+        let before = match self.iot_columns(location, scn, &undo.key, &undo.keydata)? {
+            Some(x) => x,
+            None => return Ok(()),
+        };
+        self.process_row("delete", scn, timestamp, position, location, Some(&before), None, attributes, lobs)
+    }
+
+    /// Update of non-key columns, key is the same in both images. Update of key is logged as delete and insert.
+    #[allow(clippy::too_many_arguments)]
+    pub fn process_iot_update(&self, scn : TypeRecordScn, timestamp : TypeTimestamp, position : TypeRedoPosition, undo : OpCode0501, redo : OpCode1018, attributes : Option<&SessionAttributes>, lobs : &LobContext) -> Result<()> {
+        let location = RowLocation { bdba : redo.bdba, slot : redo.slot, ..(&undo).into() };
+        let before = match self.iot_columns(location, scn, &undo.key, &undo.keydata)? {
+            Some(x) => x,
+            None => return Ok(()),
+        };
+        let after = match self.iot_columns(location, scn, &undo.key, &redo.keydata)? {
+            Some(x) => x,
+            None => return Ok(()),
+        };
+        self.process_row("update", scn, timestamp, position, location, Some(&before), Some(&after), attributes, lobs)
+    }
+
+    /// Converts columns of index leaf row to row columns, None if index does not belong to index-organized table
+    fn iot_columns<'a>(&self, location : RowLocation, scn : TypeRecordScn, key : &[Option<&'a [u8]>], keydata : &[Option<&'a [u8]>]) -> Result<Option<Vec<RowColumn<'a>>>> {
+        let table = match self.metadata_ptr.get_schema().get_table_for_segment(location.obj, location.data_obj, scn.into())? {
+            Some(table) if table.is_iot() => table,
+            _ => return Ok(None),
+        };
+
+        let mut result : Vec<RowColumn> = key.iter().chain(keydata)
+            .enumerate()
+            .filter_map(|(i, data)| table.iot_column(i).map(|x| (x, *data)))
+            .collect();
+        result.sort_by_key(|x| x.0);
+        Ok(Some(result))
+    }
+
+    /// Columns of compressed row refer to symbol table of block, which is not in redo log.
+    /// Such row is sent as raw data if `unknown_type` is SHOW, skipped with warning otherwise.
+    #[allow(clippy::too_many_arguments)]
+    fn process_compressed(&self, op : &str, scn : TypeRecordScn, timestamp : TypeTimestamp, position : TypeRedoPosition, location : RowLocation, data : &[u8], attributes : Option<&SessionAttributes>) -> Result<()> {
+        let table = match self.metadata_ptr.get_schema().get_table_for_segment(location.obj, location.data_obj, scn.into())? {
+            Some(table) => table,
+            None => {
+                warn!("No table with obj_id: {}", location.obj);
+                return Ok(());
             }
+        };
+
+        if self.formats.unknown_type != UNKNOWN_TYPE_SHOW {
+            warn!("Compressed row of table {}.{} is not supported, {} at scn {} is skipped", table.owner(), table.name(), op, scn);
+            return Ok(());
         }
 
+        let _guard = self.queue.lock().unwrap();
 
-        let value = json!({
-            "OP" : "insert",
-            "SCN": scn.to_string(),
+        let mut value = json!({
+            "OP" : "unsupported",
+            "DML" : op,
+            "REASON" : "compressed row",
             "TIMESTAMP": timestamp.to_string(),
-            "XID": undo.xid.to_string(),
-            "TABLE": table.name(),
-            "DATA": columns,
-            "DATA_OBJ": undo.data_obj,
+            "XID": self.xid_value(location.xid),
+            "SCHEMA": self.schema_value(location.obj, &table),
+            "DATA_OBJ": location.data_obj,
+            "RAW": data.iter().map(|x| format!("{:02X}", x)).collect::<String>(),
         });
 
-        let mut output_file = OpenOptions::new().write(true).append(true).open("out.txt").unwrap();
-        output_file.write(serde_json::to_string_pretty(&value).unwrap().as_bytes()).unwrap();
-        output_file.write(b"\n").unwrap();
+        if self.formats.rid_format & RID_FORMAT_TEXT != 0 {
+            value["RID"] = json!(TypeRowId::new(location.data_obj, location.bdba, location.slot).to_string());
+        }
 
-        Ok(())
+        if self.formats.scn_all & SCN_ALL_PAYLOADS != 0 {
+            value["SCN"] = self.transaction_scn_value(location.xid, scn);
+        }
+
+        self.add_attributes(&mut value, attributes, ATTRIBUTES_FORMAT_DML);
+
+        self.push_payload(location.xid, position, None, value)
     }
 
-    pub fn process_begin(&self, scn : TypeRecordScn, timestamp : TypeTimestamp, xid : TypeXid) -> Result<()> {
-        let mut guard = self.queue.lock().unwrap();
-        
-        let mut output_file = OpenOptions::new().write(true).append(true).open("out.txt").unwrap();
+    /// Undo and redo contain changed columns only, supplemental log adds columns to both images
+    #[allow(clippy::too_many_arguments)]
+    pub fn process_update(&self, scn : TypeRecordScn, timestamp : TypeTimestamp, position : TypeRedoPosition, undo : OpCode0501, redo : OpCode1105, attributes : Option<&SessionAttributes>, lobs : &LobContext) -> Result<()> {
+        self.process_row_update(scn, timestamp, position, &undo, &redo.columns, attributes, lobs)
+    }
 
-        let value = json!({
+    /// Overwrite of row piece happens when updated row is migrated, redo contains all columns of piece
+    #[allow(clippy::too_many_arguments)]
+    pub fn process_overwrite(&self, scn : TypeRecordScn, timestamp : TypeTimestamp, position : TypeRedoPosition, undo : OpCode0501, redo : OpCode1106, attributes : Option<&SessionAttributes>, lobs : &LobContext) -> Result<()> {
+        self.process_row_update(scn, timestamp, position, &undo, &redo.columns, attributes, lobs)
+    }
+
+    /// Update of chained row is logged by pieces. Supplemental log of each piece has position of its 
+    /// columns in row and FB flags of the whole change, message is sent after the last piece.
+    #[allow(clippy::too_many_arguments)]
+    fn process_row_update(&self, scn : TypeRecordScn, timestamp : TypeTimestamp, position : TypeRedoPosition, undo : &OpCode0501, redo_columns : &[RowColumn], attributes : Option<&SessionAttributes>, lobs : &LobContext) -> Result<()> {
+        if undo.supplog_type == 0 || (undo.supplog_fb.is_first() && undo.supplog_fb.is_last()) {
+            let before = Self::merge_columns(&Self::shift_columns(&undo.columns, undo.supplog_before), &undo.supplog_columns);
+            let after = Self::merge_columns(&Self::shift_columns(redo_columns, undo.supplog_after), &undo.supplog_columns);
+            return self.process_row("update", scn, timestamp, position, undo.into(), Some(&before), Some(&after), attributes, lobs);
+        }
+
+        let update = {
+            let mut transactions = self.transactions.lock().unwrap();
+            let transaction = transactions.entry(undo.xid).or_default();
+
+            let mut row : RowLocation = undo.into();
+            row.bdba = undo.supplog_bdba;
+            row.slot = undo.supplog_slot;
+
+            let update = transaction.update.get_or_insert_with(|| PieceUpdate { row, before : BTreeMap::new(), after : BTreeMap::new(), supplog : Vec::new() });
+            update.before.extend(Self::shift_columns(&undo.columns, undo.supplog_before).into_iter().map(|(i, x)| (i, x.map(|x| x.to_vec()))));
+            update.after.extend(Self::shift_columns(redo_columns, undo.supplog_after).into_iter().map(|(i, x)| (i, x.map(|x| x.to_vec()))));
+            update.supplog.extend(undo.supplog_columns.iter().map(|(i, x)| (*i, x.map(|x| x.to_vec()))));
+
+            if !undo.supplog_fb.is_last() {
+                return Ok(());
+            }
+            transaction.update.take().unwrap()
+        };
+
+        let supplog = Self::borrow_columns(&update.supplog);
+        let before = Self::merge_columns(&update.before.iter().map(|(i, x)| (*i, x.as_deref())).collect::<Vec<_>>(), &supplog);
+        let after = Self::merge_columns(&update.after.iter().map(|(i, x)| (*i, x.as_deref())).collect::<Vec<_>>(), &supplog);
+        self.process_row("update", scn, timestamp, position, update.row, Some(&before), Some(&after), attributes, lobs)
+    }
+
+    /// Keeps piece of chained row until all pieces from the head to the last one are known
+    #[allow(clippy::too_many_arguments)]
+    fn process_piece(&self, op : &'static str, scn : TypeRecordScn, timestamp : TypeTimestamp, position : TypeRedoPosition, fb : TypeFb, nrid : Option<(u32, u16)>, 
+                    row : RowLocation, columns : &[RowColumn], supplog : &[RowColumn], attributes : Option<&SessionAttributes>, lobs : &LobContext) -> Result<()> {
+        let assembled = {
+            let mut transactions = self.transactions.lock().unwrap();
+            let pieces = &mut transactions.entry(row.xid).or_default().pieces;
+
+            pieces.insert((op, row.bdba, row.slot), RowPiece {
+                fb, nrid, row,
+                columns : columns.iter().map(|(i, x)| (*i, x.map(|x| x.to_vec()))).collect(),
+                supplog : supplog.iter().map(|(i, x)| (*i, x.map(|x| x.to_vec()))).collect(),
+            });
+
+            let heads : Vec<(u32, u16)> = pieces.iter()
+                .filter(|(key, piece)| key.0 == op && piece.fb.is_first())
+                .map(|(key, _)| (key.1, key.2))
+                .collect();
+
+            let mut assembled = None;
+            for head in heads {
+                assembled = Self::assemble_pieces(pieces, op, head)?;
+                if assembled.is_some() {
+                    break;
+                }
+            }
+            assembled
+        };
+
+        let (row, columns, supplog) = match assembled {
+            Some(x) => x,
+            None => return Ok(()),
+        };
+
+        let columns = Self::merge_columns(&Self::borrow_columns(&columns), &Self::borrow_columns(&supplog));
+        match op {
+            "insert" => self.process_row(op, scn, timestamp, position, row, None, Some(&columns), attributes, lobs),
+            _ => self.process_row(op, scn, timestamp, position, row, Some(&columns), None, attributes, lobs),
+        }
+    }
+
+    /// Removes pieces of row starting from `head` and joins their columns. Column which is continued 
+    /// in the next piece (N flag) is concatenated with the first column of that piece. None if some piece is not known yet.
+    fn assemble_pieces(pieces : &mut HashMap<(&'static str, u32, u16), RowPiece>, op : &'static str, head : (u32, u16)) -> Result<Option<AssembledRow>> {
+        let mut keys = vec![(op, head.0, head.1)];
+        let mut visited = HashSet::from([head]);
+        loop {
+            let piece = match pieces.get(keys.last().unwrap()) {
+                Some(piece) => piece,
+                None => return Ok(None),
+            };
+            if piece.fb.is_last() {
+                break;
+            }
+            let (bdba, slot) = match piece.nrid {
+                Some(nrid) => nrid,
+                None => return Ok(None),
+            };
+            // Corrupted chain would be followed forever
+            if !visited.insert((bdba, slot)) {
+                return olr_perr!("Chained row of {} with head bdba: {}, slot: {} refers to bdba: {}, slot: {} twice", op, head.0, head.1, bdba, slot);
+            }
+            keys.push((op, bdba, slot));
+        }
+
+        let row = pieces[&keys[0]].row;
+        let mut columns : Vec<PieceColumn> = Vec::new();
+        let mut supplog = Vec::new();
+        let mut offset = 0;
+
+        for key in keys {
+            let mut piece = pieces.remove(&key).unwrap();
+
+            for (i, data) in piece.columns.drain(..) {
+                match columns.last_mut() {
+                    Some((last, Some(last_data))) if *last == offset + i => last_data.extend(data.unwrap_or_default()),
+                    _ => columns.push((offset + i, data)),
+                }
+            }
+
+            // Column continued in the next piece keeps its index
+            offset = match columns.last() {
+                Some((last, _)) if piece.fb.is_next() => *last,
+                Some((last, _)) => last + 1,
+                None => 0,
+            };
+            supplog.append(&mut piece.supplog);
+        }
+
+        Ok(Some((row, columns, supplog)))
+    }
+
+    /// Converts column numbers of row piece to numbers in row, `start` is 1-based number of the first column of piece
+    fn shift_columns<'a>(columns : &[RowColumn<'a>], start : u16) -> Vec<RowColumn<'a>> {
+        let shift = start.saturating_sub(1) as usize;
+        columns.iter().map(|(i, x)| (i + shift, *x)).collect()
+    }
+
+    fn borrow_columns(columns : &[PieceColumn]) -> Vec<RowColumn<'_>> {
+        columns.iter().map(|(i, x)| (*i, x.as_deref())).collect()
+    }
+
+    /// Adds columns of `extra` which are absent in `columns`, result is sorted by column index
+    fn merge_columns<'a>(columns : &[RowColumn<'a>], extra : &[RowColumn<'a>]) -> Vec<RowColumn<'a>> {
+        let mut result : BTreeMap<usize, Option<&'a [u8]>> = extra.iter().cloned().collect();
+        result.extend(columns.iter().cloned());
+        result.into_iter().collect()
+    }
+
+    /// Values of encrypted columns are replaced by plain text, None if table has no encrypted columns
+    fn decrypt_columns(&self, table : &OracleTable, columns : Option<&[RowColumn]>) -> Result<Option<Vec<PieceColumn>>> {
+        let (columns, obj) = match (columns, table.obj()) {
+            (Some(columns), Some(obj)) if table.columns().iter().any(|x| x.is_encrypted()) => (columns, obj),
+            _ => return Ok(None),
+        };
+
+        columns.iter().map(|(i, data)| Ok((*i, match (table.segment_column(*i), data) {
+            (Some(column), Some(data)) if column.is_encrypted() => Some(self.metadata_ptr.decrypt_column(obj, data, column.has_salt())?),
+            (_, data) => data.map(|x| x.to_vec()),
+        }))).collect::<Result<Vec<_>>>().map(Some)
+    }
+
+    /// Decoded values of columns, LOB columns are shown with full value instead of locator.
+    /// Out of row LOB which is not changed in transaction has no value and is omitted.
+    fn image_value(columns : &[RowColumn], values : &BTreeMap<usize, ColumnValue>) -> BTreeMap<usize, String> {
+        columns.iter().filter_map(|(i, _)| match values.get(i)? {
+            ColumnValue::Null => Some((*i, format!("Col {}: Null", i))),
+            value => Some((*i, format!("Col {}: {}", i, value))),
+        }).collect()
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn process_row(&self, op : &str, scn : TypeRecordScn, timestamp : TypeTimestamp, position : TypeRedoPosition, location : RowLocation, 
+                    before : Option<&[RowColumn]>, after : Option<&[RowColumn]>, attributes : Option<&SessionAttributes>, lobs : &LobContext) -> Result<()> {
+
+        let table = self.metadata_ptr.get_schema().get_table_for_segment(location.obj, location.data_obj, scn.into())?;
+
+        let _guard = self.queue.lock().unwrap();
+
+        if table.is_none() {
+            warn!("No table with obj_id: {}", location.obj);
+            return Ok(());
+        }
+
+        let table = table.unwrap();
+
+        let before_plain = self.decrypt_columns(&table, before)?;
+        let after_plain = self.decrypt_columns(&table, after)?;
+        let before_columns = before_plain.as_deref().map(Self::borrow_columns);
+        let after_columns = after_plain.as_deref().map(Self::borrow_columns);
+        let before = before_columns.as_deref().or(before);
+        let after = after_columns.as_deref().or(after);
+
+        let decode = |columns : Option<&[RowColumn]>| -> BTreeMap<usize, ColumnValue> {
+            columns.unwrap_or_default().iter()
+                .filter_map(|(i, data)| Some((*i, match table.segment_column(*i) {
+                    Some(column) if column.is_lob() => lobs.decode(column, data.unwrap_or_default(), data.is_none(), &self.locales_ptr)?,
+                    column => ColumnValue::decode(column, data.unwrap_or_default(), data.is_none(), &self.locales_ptr),
+                })))
+                .collect()
+        };
+        let before_values = decode(before);
+        let after_values = decode(after);
+
+        let value_of = |values : &BTreeMap<usize, ColumnValue>, name : &str| table.columns()
+            .iter()
+            .find(|x| x.name == name)
+            .and_then(|x| values.get(&(x.seg_col_no as usize - 1)).cloned());
+
+        // Row condition is checked on after-image, columns which were not changed are taken from before-image
+        if let Some(condition) = self.metadata_ptr.get_condition(table.owner(), table.name()) {
+            let row = |name : &str| value_of(&after_values, name)
+                .or_else(|| value_of(&before_values, name))
+                .unwrap_or(ColumnValue::Null);
+
+            if !condition.matches(&row) {
+                return Ok(());
+            }
+        }
+
+        let mut value = json!({
+            "OP" : op,
+            "TIMESTAMP": timestamp.to_string(),
+            "XID": self.xid_value(location.xid),
+            "SCHEMA": self.schema_value(location.obj, &table),
+            "DATA_OBJ": location.data_obj,
+        });
+
+        match (before, after) {
+            (None, Some(after)) => value["DATA"] = json!(Self::image_value(after, &after_values)),
+            (before, after) => {
+                if let Some(before) = before {
+                    value["BEFORE"] = json!(Self::image_value(before, &before_values));
+                }
+                if let Some(after) = after {
+                    value["AFTER"] = json!(Self::image_value(after, &after_values));
+                }
+            },
+        }
+
+        // Key columns from filter, primary key of table otherwise. Key identifies row before change, null key column is JSON null.
+        // Writer gets key as message key, so changes of one row go to the same partition of message broker.
+        let keys = self.metadata_ptr.get_keys(table.owner(), table.name()).unwrap_or_else(|| table.primary_key().clone());
+        let mut message_key = None;
+        if !keys.is_empty() {
+            let key : BTreeMap<&String, Option<String>> = keys.iter().map(|x| {
+                let key_value = value_of(&before_values, x)
+                    .or_else(|| value_of(&after_values, x))
+                    .unwrap_or(ColumnValue::Null);
+                (x, key_value.as_text())
+            }).collect();
+            value["KEY"] = json!(key);
+            message_key = Some(value["KEY"].to_string());
+        }
+
+        if self.formats.rid_format & RID_FORMAT_TEXT != 0 {
+            value["RID"] = json!(TypeRowId::new(location.data_obj, location.bdba, location.slot).to_string());
+        }
+
+        if self.formats.scn_all & SCN_ALL_PAYLOADS != 0 {
+            value["SCN"] = self.transaction_scn_value(location.xid, scn);
+        }
+
+        self.add_attributes(&mut value, attributes, ATTRIBUTES_FORMAT_DML);
+
+        self.push_payload(location.xid, position, message_key, value)
+    }
+
+    pub fn process_begin(&self, scn : TypeRecordScn, timestamp : TypeTimestamp, xid : TypeXid, attributes : Option<&SessionAttributes>) -> Result<()> {
+        let _guard = self.queue.lock().unwrap();
+
+        let mut value = json!({
             "OP" : "start",
-            "SCN": scn.to_string(),
+            "SCN": self.transaction_scn_value(xid, scn),
             "TIMESTAMP": timestamp.to_string(),
-            "XID": xid.to_string(),
+            "XID": self.xid_value(xid),
         });
 
-        output_file.write(value.to_string().as_bytes()).unwrap();
-        output_file.write(b"\n").unwrap();
+        self.add_attributes(&mut value, attributes, ATTRIBUTES_FORMAT_BEGIN);
 
-        Ok(())
+        if self.formats.message_format & MESSAGE_FORMAT_FULL != 0 {
+            self.transactions.lock().unwrap().entry(xid).or_default().begin = Some(value);
+            return Ok(());
+        }
+
+        if self.formats.message_format & MESSAGE_FORMAT_SKIP_BEGIN != 0 {
+            return Ok(());
+        }
+
+        self.write_message(None, value)
     }
 
-    pub fn process_commit(&self, scn : TypeRecordScn, timestamp : TypeTimestamp, xid : TypeXid, is_rollback : bool) -> Result<()> {
-        let mut guard = self.queue.lock().unwrap();
-        
-        let mut output_file = OpenOptions::new().write(true).append(true).open("out.txt").unwrap();
+    pub fn process_commit(&self, scn : TypeRecordScn, timestamp : TypeTimestamp, xid : TypeXid, is_rollback : bool, attributes : Option<&SessionAttributes>) -> Result<()> {
+        let _guard = self.queue.lock().unwrap();
 
-        let value = json!({
+        let mut transaction = self.transactions.lock().unwrap().remove(&xid).unwrap_or_default();
+        let incomplete = self.incomplete_rows(xid, &transaction, is_rollback);
+
+        let mut value = json!({
             "OP" : if is_rollback {"rollback"} else {"commit"},
-            "SCN": scn.to_string(),
+            "SCN": self.scn_value(scn),
+            "TIMESTAMP": timestamp.to_string(),
+            "XID": self.xid_value(xid),
+        });
+
+        self.add_attributes(&mut value, attributes, ATTRIBUTES_FORMAT_COMMIT);
+
+        // One message with begin info, all changes in "PAYLOAD" and commit info
+        if self.formats.message_format & MESSAGE_FORMAT_FULL != 0 {
+            let mut message = transaction.begin.unwrap_or(json!({ "XID": self.xid_value(xid) }));
+            message["OP"] = json!(if is_rollback {"rollback"} else {"transaction"});
+            message["COMMIT_SCN"] = value["SCN"].take();
+            message["COMMIT_TIMESTAMP"] = value["TIMESTAMP"].take();
+            transaction.payload.extend(incomplete);
+            message["PAYLOAD"] = json!(transaction.payload);
+            if let Some(attributes) = value.get_mut("ATTRIBUTES") {
+                message["ATTRIBUTES"] = attributes.take();
+            }
+            return self.write_message(None, message);
+        }
+
+        for row in incomplete {
+            self.write_message(None, row)?;
+        }
+
+        if self.formats.message_format & MESSAGE_FORMAT_SKIP_COMMIT != 0 {
+            return Ok(());
+        }
+
+        self.write_message(None, value)
+    }
+
+    /// Rows with pieces which are still missing at commit can not be shown. Consumer gets their location
+    /// instead of losing change silently.
+    fn incomplete_rows(&self, xid : TypeXid, transaction : &TransactionMessage, is_rollback : bool) -> Vec<Value> {
+        let mut rows : Vec<(&str, RowLocation)> = transaction.pieces.iter().map(|((op, _, _), piece)| (*op, piece.row)).collect();
+        rows.extend(transaction.update.as_ref().map(|x| ("update", x.row)));
+
+        if rows.is_empty() {
+            return Vec::new();
+        }
+
+        warn!("Transaction {} has incomplete row pieces: {}", xid, rows.len());
+        if is_rollback {
+            return Vec::new();
+        }
+
+        rows.sort_by_key(|(op, row)| (*op, row.bdba, row.slot));
+        rows.into_iter().map(|(op, row)| json!({
+            "OP": "incomplete",
+            "INCOMPLETE_OP": op,
+            "XID": self.xid_value(xid),
+            "OBJ": row.obj,
+            "DATA_OBJ": row.data_obj,
+            "RID": TypeRowId::new(row.data_obj, row.bdba, row.slot).to_string(),
+        })).collect()
+    }
+
+    /// Rows of table are removed without DML redo, so consumer has to clear its copy of table
+    fn process_truncate(&self, scn : TypeRecordScn, timestamp : TypeTimestamp, ddl : &OpCode1801) -> Result<()> {
+        let table = match self.metadata_ptr.get_schema().get_table(ddl.obj, scn.into())? {
+            Some(table) => table,
+            None => {
+                warn!("No table with obj_id: {} for truncate", ddl.obj);
+                return Ok(());
+            }
+        };
+
+        let _guard = self.queue.lock().unwrap();
+
+        let value = json!({
+            "OP" : "truncate",
+            "SCN": self.scn_value(scn),
             "TIMESTAMP": timestamp.to_string(),
-            "XID": xid.to_string(),
+            "XID": self.xid_value(ddl.xid),
+            "SCHEMA": self.schema_value(ddl.obj, &table),
+            "TYPE": ddl.ddl_type_name(),
         });
 
-        output_file.write(value.to_string().as_bytes()).unwrap();
-        output_file.write(b"\n").unwrap();
+        self.write_message(None, value)
+    }
+
+    pub fn process_ddl(&self, scn : TypeRecordScn, timestamp : TypeTimestamp, ddl : OpCode1801) -> Result<()> {
+        if ddl.is_table_ddl() {
+            self.metadata_ptr.get_schema().invalidate_table(ddl.obj, scn.into());
+        }
+
+        if ddl.is_truncate() && self.metadata_ptr.is_object_tracked(ddl.obj, None) {
+            self.process_truncate(scn, timestamp, &ddl)?;
+        }
+
+        if self.formats.db_format & DB_FORMAT_ADD_DDL == 0 {
+            return Ok(());
+        }
+
+        let _guard = self.queue.lock().unwrap();
+
+        let value = json!({
+            "OP" : "ddl",
+            "SCN": self.scn_value(scn),
+            "TIMESTAMP": timestamp.to_string(),
+            "XID": self.xid_value(ddl.xid),
+            "OBJ": ddl.obj,
+            "OWNER": ddl.owner,
+            "TYPE": ddl.ddl_type_name(),
+            "SQL": ddl.sql_text,
+        });
+
+        self.write_message(None, value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use crate::{builder::formats::{self, BuilderFormats}, common::{constants, errors::Result, testing, types::{TypeRedoPosition, TypeRowId, TypeXid}}, metadata::Metadata, oradefs::oracle_table::OracleTable, parser::opcodes::VectorData};
+
+    use super::{lobs::LobContext, RowLocation};
+
+    fn set_table(metadata_ptr : &Metadata) {
+        let columns = vec![testing::column(1, "ID", constants::SYS_COL_TYPE_NUMBER), testing::column(2, "A", constants::SYS_COL_TYPE_VARCHAR),
+                           testing::column(3, "B", constants::SYS_COL_TYPE_VARCHAR)];
+        let table = OracleTable::new("SCOTT".to_string(), "T1".to_string(), columns, vec!["ID".to_string()]);
+        testing::set_tables(metadata_ptr, vec![(100, 101, table)]);
+    }
+
+    #[test]
+    fn test_null_key() -> Result<()> {
+        let context_ptr = testing::context();
+        let metadata_ptr = testing::metadata(context_ptr.clone());
+        let (builder_ptr, output) = testing::builder(context_ptr, metadata_ptr.clone(), formats::MESSAGE_FORMAT_DEFAULT);
+
+        let columns = vec![testing::column(1, "ID", constants::SYS_COL_TYPE_NUMBER), testing::column(2, "CODE", constants::SYS_COL_TYPE_VARCHAR)];
+        let table = OracleTable::new("SCOTT".to_string(), "T1".to_string(), columns, vec!["ID".to_string(), "CODE".to_string()]);
+        testing::set_tables(&metadata_ptr, vec![(100, 101, table)]);
+
+        let location = RowLocation { obj : 100, data_obj : 101, xid : TypeXid::new(1, 2, 3), bdba : 0x01000010, slot : 0 };
+        let after = [(0, Some([0xC1, 0x02].as_slice())), (1, None)];
+        builder_ptr.process_row("insert", 10.into(), 1.into(), Default::default(), location, None, Some(&after), None, &LobContext::default())?;
+
+        let messages = output.take_keyed();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].1["KEY"], json!({"ID": "1", "CODE": null}));
+        assert_eq!(messages[0].0.as_deref(), Some(r#"{"CODE":null,"ID":"1"}"#));
+        Ok(())
+    }
+
+    #[test]
+    fn test_row_pieces() -> Result<()> {
+        let context_ptr = testing::context();
+        let metadata_ptr = testing::metadata(context_ptr.clone());
+        let (builder_ptr, output) = testing::builder(context_ptr, metadata_ptr.clone(), formats::MESSAGE_FORMAT_DEFAULT);
+        set_table(&metadata_ptr);
+
+        let head = RowLocation { obj : 100, data_obj : 101, xid : TypeXid::new(1, 2, 3), bdba : 0x01000010, slot : 0 };
+        let tail = RowLocation { bdba : 0x01000011, slot : 4, ..head };
+
+        // Insert logs the last piece first, column A is continued from the head to the last piece
+        builder_ptr.process_piece("insert", 10.into(), 1.into(), Default::default(), 0x04.into(), None, tail,
+            &[(0, Some(b"cd".as_slice())), (1, Some(b"e".as_slice()))], &[], None, &LobContext::default())?;
+        assert!(output.take().is_empty());
+        builder_ptr.process_piece("insert", 10.into(), 2.into(), Default::default(), 0x09.into(), Some((tail.bdba, tail.slot)), head,
+            &[(0, Some([0xC1, 0x02].as_slice())), (1, Some(b"ab".as_slice()))], &[], None, &LobContext::default())?;
+
+        let messages = output.take();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0]["DATA"], json!({"0": "Col 0: 1", "1": "Col 1: abcd", "2": "Col 2: e"}));
+
+        // Delete of row with head piece without columns
+        builder_ptr.process_piece("delete", 11.into(), 3.into(), Default::default(), 0x09.into(), Some((tail.bdba, tail.slot)), head,
+            &[], &[], None, &LobContext::default())?;
+        builder_ptr.process_piece("delete", 11.into(), 4.into(), Default::default(), 0x04.into(), None, tail,
+            &[(0, Some([0xC1, 0x02].as_slice())), (1, Some(b"ab".as_slice())), (2, None)], &[], None, &LobContext::default())?;
+
+        let messages = output.take();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0]["BEFORE"], json!({"0": "Col 0: 1", "1": "Col 1: ab", "2": "Col 2: Null"}));
+        Ok(())
+    }
+
+    #[test]
+    fn test_broken_chain() -> Result<()> {
+        let context_ptr = testing::context();
+        let metadata_ptr = testing::metadata(context_ptr.clone());
+        let (builder_ptr, output) = testing::builder(context_ptr, metadata_ptr.clone(), formats::MESSAGE_FORMAT_DEFAULT);
+        set_table(&metadata_ptr);
+
+        let xid = TypeXid::new(1, 2, 3);
+        let head = RowLocation { obj : 100, data_obj : 101, xid, bdba : 0x01000010, slot : 0 };
+        let middle = RowLocation { bdba : 0x01000011, slot : 4, ..head };
+        let columns = [(0, Some([0xC1, 0x02].as_slice()))];
+
+        // Middle piece refers back to the head
+        builder_ptr.process_piece("insert", 10.into(), 1.into(), Default::default(), 0x00.into(), Some((head.bdba, head.slot)), middle, &columns, &[], None, &LobContext::default())?;
+        assert!(builder_ptr.process_piece("insert", 10.into(), 1.into(), Default::default(), 0x08.into(), Some((middle.bdba, middle.slot)), head, &columns, &[], None, &LobContext::default()).is_err());
+
+        // Last piece of delete is never logged, row is reported at commit
+        builder_ptr.process_piece("delete", 11.into(), 1.into(), Default::default(), 0x08.into(), Some((0x01000020, 1)), head, &columns, &[], None, &LobContext::default())?;
+        builder_ptr.process_commit(12.into(), 2.into(), xid, false, None)?;
+
+        let messages = output.take();
+        assert_eq!(messages.iter().map(|x| x["OP"].as_str().unwrap()).collect::<Vec<_>>(), ["incomplete", "incomplete", "incomplete", "commit"]);
+        assert_eq!(messages[0]["INCOMPLETE_OP"], "delete");
+        assert_eq!(messages[0]["RID"], TypeRowId::new(101, head.bdba, head.slot).to_string());
+        assert_eq!(messages[1]["INCOMPLETE_OP"], "insert");
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_update() -> Result<()> {
+        let context_ptr = testing::context();
+        let metadata_ptr = testing::metadata(context_ptr.clone());
+        let (builder_ptr, output) = testing::builder(context_ptr.clone(), metadata_ptr.clone(), formats::MESSAGE_FORMAT_DEFAULT);
+        let mut parser = testing::parser(context_ptr, builder_ptr.clone(), metadata_ptr.clone());
+        set_table(&metadata_ptr);
+
+        // Undo of URP with one column and supplemental log of piece which starts at column `start`
+        let undo_bytes = |fb : u8, start : u8, col_num : u8, value : &[u8], supplog : &[&[u8]]| {
+            let mut ktudb = [0u8; 20];
+            ktudb[8 .. 16].copy_from_slice(&[0x01, 0x00, 0x02, 0x00, 0x03, 0x00, 0x00, 0x00]);
+            let mut ktub = [0u8; 24];
+            ktub[0 .. 8].copy_from_slice(&[100, 0, 0, 0, 101, 0, 0, 0]);
+            ktub[16 .. 18].copy_from_slice(&[11, 1]);
+            let mut kdoopcode = [0u8; 28];
+            kdoopcode[10] = constants::OP_URP;
+            kdoopcode[23] = 1;
+            let numbers = [col_num, 0];
+            let mut header = [0u8; 26];
+            header[0] = 1;
+            header[1] = fb;
+            header[2] = supplog.len() as u8;
+            header[6] = start;
+            header[8] = start;
+
+            let supplog_numbers : Vec<u8> = (1 ..= supplog.len() as u8).flat_map(|x| [x, 0]).collect();
+            let supplog_lengths : Vec<u8> = supplog.iter().flat_map(|x| [x.len() as u8, 0]).collect();
+            let mut fields : Vec<&[u8]> = vec![&ktudb, &ktub, &[constants::KTBOP_Z, 0, 0, 0, 0, 0, 0, 0], &kdoopcode, &numbers, value, &header];
+            if !supplog.is_empty() {
+                fields.extend([supplog_numbers.as_slice(), supplog_lengths.as_slice()]);
+                fields.extend(supplog);
+            }
+            testing::vector_bytes((5, 1), &fields)
+        };
+
+        let head = undo_bytes(0x08, 1, 1, b"a0", &[&[0xC1, 0x02]]);
+        let last = undo_bytes(0x04, 3, 0, b"b0", &[]);
+        for (data, redo) in [(&head, (1, Some(b"a1".as_slice()))), (&last, (0, Some(b"b1".as_slice())))] {
+            let undo = match testing::parse_vector(&mut parser, data).into_data() {
+                VectorData::OpCode0501(undo) => undo,
+                data => panic!("Unexpected vector: {}", data),
+            };
+            assert!(output.take().is_empty());
+            builder_ptr.process_row_update(12.into(), 5.into(), Default::default(), &undo, &[redo], None, &LobContext::default())?;
+        }
+
+        let messages = output.take();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0]["BEFORE"], json!({"0": "Col 0: 1", "1": "Col 1: a0", "2": "Col 2: b0"}));
+        assert_eq!(messages[0]["AFTER"], json!({"0": "Col 0: 1", "1": "Col 1: a1", "2": "Col 2: b1"}));
+        Ok(())
+    }
+
+    #[test]
+    fn test_unchanged_lob() -> Result<()> {
+        let context_ptr = testing::context();
+        let metadata_ptr = testing::metadata(context_ptr.clone());
+        let (builder_ptr, output) = testing::builder(context_ptr, metadata_ptr.clone(), formats::MESSAGE_FORMAT_DEFAULT);
+
+        let columns = vec![testing::column(1, "ID", constants::SYS_COL_TYPE_NUMBER), testing::column(2, "DOC", constants::SYS_COL_TYPE_BLOB)];
+        testing::set_tables(&metadata_ptr, vec![(100, 101, OracleTable::new("SCOTT".to_string(), "T1".to_string(), columns, vec![]))]);
+
+        // Out of row locator without pages in transaction
+        let mut locator = [0u8; 34];
+        locator[10 .. 20].copy_from_slice(&[0, 0, 0, 1, 0, 0, 0, 0, 0x12, 0x34]);
+        locator[33] = 5;
+
+        let location = RowLocation { obj : 100, data_obj : 101, xid : TypeXid::new(1, 2, 3), bdba : 0x01000010, slot : 0 };
+        let before = [(0, Some([0xC1, 0x02].as_slice())), (1, Some(locator.as_slice()))];
+        let after = [(0, Some([0xC1, 0x03].as_slice())), (1, Some(locator.as_slice()))];
+        builder_ptr.process_row("update", 10.into(), 1.into(), Default::default(), location, Some(&before), Some(&after), None, &LobContext::default())?;
+
+        let messages = output.take();
+        assert_eq!(messages[0]["BEFORE"], json!({"0": "Col 0: 1"}));
+        assert_eq!(messages[0]["AFTER"], json!({"0": "Col 0: 2"}));
+        Ok(())
+    }
+
+    #[test]
+    fn test_truncate() -> Result<()> {
+        let context_ptr = testing::context();
+        let metadata_ptr = testing::metadata(context_ptr.clone());
+        let (builder_ptr, output) = testing::builder(context_ptr.clone(), metadata_ptr.clone(), formats::MESSAGE_FORMAT_DEFAULT);
+        let mut parser = testing::parser(context_ptr, builder_ptr.clone(), metadata_ptr.clone());
+        set_table(&metadata_ptr);
+        let table = OracleTable::new("SCOTT".to_string(), "T1".to_string(), vec![testing::column(1, "ID", constants::SYS_COL_TYPE_NUMBER)], vec![]);
+        testing::set_dictionary(&metadata_ptr, vec![(100, 102, table)]);
+
+        let data = testing::truncate_vector_bytes(100);
+        let ddl = match testing::parse_vector(&mut parser, &data).into_data() {
+            VectorData::OpCode1801(ddl) => ddl,
+            data => panic!("Unexpected vector: {}", data),
+        };
+        builder_ptr.process_ddl(20.into(), 6.into(), ddl)?;
+
+        let messages = output.take();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0]["OP"], "truncate");
+        assert_eq!(messages[0]["SCHEMA"]["TABLE"], "T1");
+        assert_eq!(metadata_ptr.get_schema().get_table(100, 20)?.and_then(|x| x.data_obj()), Some(102));
+        Ok(())
+    }
+
+    #[test]
+    fn test_encrypted_without_key() {
+        let context_ptr = testing::context();
+        let metadata_ptr = testing::metadata(context_ptr.clone());
+        let (builder_ptr, output) = testing::builder(context_ptr, metadata_ptr.clone(), formats::MESSAGE_FORMAT_DEFAULT);
+
+        let mut secret = testing::column(2, "SECRET", constants::SYS_COL_TYPE_VARCHAR);
+        secret.property = constants::SYS_COL_PROPERTY_ENCRYPTED;
+        let table = OracleTable::new("SCOTT".to_string(), "T1".to_string(), vec![testing::column(1, "ID", constants::SYS_COL_TYPE_NUMBER), secret], vec![]);
+        testing::set_tables(&metadata_ptr, vec![(100, 101, table)]);
+
+        // Cipher text is not sent as value of column
+        let location = RowLocation { obj : 100, data_obj : 101, xid : TypeXid::new(1, 2, 3), bdba : 0x01000010, slot : 0 };
+        let after = [(0, Some([0xC1, 0x02].as_slice())), (1, Some([0x5A; 52].as_slice()))];
+        assert!(builder_ptr.process_row("insert", 10.into(), 1.into(), Default::default(), location, None, Some(&after), None, &LobContext::default()).is_err());
+        assert!(output.take().is_empty());
+    }
+
+    #[test]
+    fn test_full_message() -> Result<()> {
+        let context_ptr = testing::context();
+        let metadata_ptr = testing::metadata(context_ptr.clone());
+        let message_format = formats::MESSAGE_FORMAT_FULL | formats::MESSAGE_FORMAT_ADD_SEQUENCES | formats::MESSAGE_FORMAT_ADD_OFFSET;
+        let (builder_ptr, output) = testing::builder(context_ptr, metadata_ptr.clone(), message_format);
+        set_table(&metadata_ptr);
+
+        let xid = TypeXid::new(1, 2, 3);
+        let location = RowLocation { obj : 100, data_obj : 101, xid, bdba : 0x01000010, slot : 0 };
+        builder_ptr.process_begin(10.into(), 1.into(), xid, None)?;
+        for (slot, position) in [(0, TypeRedoPosition::new(7, 20, 16)), (1, TypeRedoPosition::new(8, 3, 420))] {
+            let after = [(0, Some([0xC1, 0x02].as_slice()))];
+            builder_ptr.process_row("insert", 11.into(), 1.into(), position, RowLocation { slot, ..location }, None, Some(&after), None, &LobContext::default())?;
+        }
+        assert!(output.take().is_empty());
+        builder_ptr.process_commit(12.into(), 2.into(), xid, false, None)?;
+
+        // Begin, changes and commit are one message
+        let messages = output.take();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0]["OP"], "transaction");
+        assert_eq!(messages[0]["SCN"], 10);
+        assert_eq!(messages[0]["COMMIT_SCN"], 12);
+        assert_eq!(messages[0]["XID"], "0x0001.002.00000003");
+
+        let payload = messages[0]["PAYLOAD"].as_array().unwrap();
+        assert_eq!(payload.len(), 2);
+        assert_eq!((&payload[0]["NUM"], &payload[0]["REDO_SEQUENCE"], &payload[0]["REDO_BLOCK"], &payload[0]["REDO_OFFSET"]), (&json!(1), &json!(7), &json!(20), &json!(16)));
+        assert_eq!((&payload[1]["NUM"], &payload[1]["REDO_SEQUENCE"], &payload[1]["REDO_BLOCK"], &payload[1]["REDO_OFFSET"]), (&json!(2), &json!(8), &json!(3), &json!(420)));
+
+        // Sequence starts again in next transaction
+        builder_ptr.process_begin(20.into(), 3.into(), xid, None)?;
+        builder_ptr.process_row("delete", 21.into(), 3.into(), Default::default(), location, Some(&[(0, Some([0xC1, 0x02].as_slice()))]), None, None, &LobContext::default())?;
+        builder_ptr.process_commit(22.into(), 4.into(), xid, true, None)?;
+
+        let messages = output.take();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0]["OP"], "rollback");
+        assert_eq!(messages[0]["PAYLOAD"][0]["NUM"], 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_skip_begin_commit() -> Result<()> {
+        let context_ptr = testing::context();
+        let metadata_ptr = testing::metadata(context_ptr.clone());
+        let formats = BuilderFormats { message_format : formats::MESSAGE_FORMAT_SKIP_BEGIN | formats::MESSAGE_FORMAT_SKIP_COMMIT, ..testing::formats() };
+        let (builder_ptr, output) = testing::builder_with_formats(context_ptr, metadata_ptr.clone(), formats);
+        set_table(&metadata_ptr);
+
+        let xid = TypeXid::new(1, 2, 3);
+        let location = RowLocation { obj : 100, data_obj : 101, xid, bdba : 0x01000010, slot : 0 };
+        builder_ptr.process_begin(10.into(), 1.into(), xid, None)?;
+        builder_ptr.process_row("insert", 11.into(), 1.into(), Default::default(), location, None, Some(&[(0, Some([0xC1, 0x02].as_slice()))]), None, &LobContext::default())?;
+        builder_ptr.process_commit(12.into(), 2.into(), xid, false, None)?;
+
+        let messages = output.take();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0]["OP"], "insert");
+        assert!(messages[0].get("NUM").is_none());
+        assert!(messages[0].get("REDO_OFFSET").is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_schema_format() -> Result<()> {
+        let context_ptr = testing::context();
+        let metadata_ptr = testing::metadata(context_ptr.clone());
+        let schema_format = formats::SCHEMA_FORMAT_FULL | formats::SCHEMA_FORMAT_OBJ | formats::SCHEMA_FORMAT_PARTITION;
+        let (builder_ptr, output) = testing::builder_with_formats(context_ptr.clone(), metadata_ptr.clone(), BuilderFormats { schema_format, ..testing::formats() });
+
+        let mut id = testing::column(1, "ID", constants::SYS_COL_TYPE_NUMBER);
+        (id.precision, id.scale, id.nullable) = (Some(10), Some(0), false);
+        let mut table = OracleTable::new("SCOTT".to_string(), "T1".to_string(), vec![id, testing::column(2, "A", constants::SYS_COL_TYPE_VARCHAR)], vec![]);
+        table.set_partition(Some("P1".to_string()));
+        testing::set_tables(&metadata_ptr, vec![(100, 101, table.clone())]);
+
+        let location = RowLocation { obj : 100, data_obj : 101, xid : TypeXid::new(1, 2, 3), bdba : 0x01000010, slot : 0 };
+        let after = [(0, Some([0xC1, 0x02].as_slice()))];
+        for _ in 0 .. 2 {
+            builder_ptr.process_row("insert", 10.into(), 1.into(), Default::default(), location, None, Some(&after), None, &LobContext::default())?;
+        }
+
+        // Columns are sent only with the first change of table
+        let messages = output.take();
+        assert_eq!(messages[0]["SCHEMA"], json!({
+            "OWNER": "SCOTT", "TABLE": "T1", "OBJ": 100, "PARTITION": "P1",
+            "COLUMNS": [
+                {"NAME": "ID", "TYPE": "NUMBER", "LENGTH": 100, "PRECISION": 10, "SCALE": 0, "NULLABLE": false},
+                {"NAME": "A", "TYPE": "VARCHAR2", "LENGTH": 100, "PRECISION": null, "SCALE": null, "NULLABLE": true},
+            ],
+        }));
+        assert_eq!(messages[1]["SCHEMA"], json!({"OWNER": "SCOTT", "TABLE": "T1", "OBJ": 100, "PARTITION": "P1"}));
+
+        // New definition of table is sent again
+        testing::set_tables(&metadata_ptr, vec![(100, 101, table)]);
+        builder_ptr.process_row("insert", 11.into(), 1.into(), Default::default(), location, None, Some(&after), None, &LobContext::default())?;
+        assert_eq!(output.take()[0]["SCHEMA"]["COLUMNS"].as_array().map(|x| x.len()), Some(2));
+
+        // Columns in every message with REPEATED flag, only names without other flags
+        let schema_format = formats::SCHEMA_FORMAT_FULL | formats::SCHEMA_FORMAT_REPEATED;
+        let (builder_ptr, output) = testing::builder_with_formats(context_ptr.clone(), metadata_ptr.clone(), BuilderFormats { schema_format, ..testing::formats() });
+        for _ in 0 .. 2 {
+            builder_ptr.process_row("insert", 12.into(), 1.into(), Default::default(), location, None, Some(&after), None, &LobContext::default())?;
+        }
+        let messages = output.take();
+        assert!(messages.iter().all(|x| x["SCHEMA"]["COLUMNS"].as_array().is_some_and(|x| x.len() == 2)));
+        assert!(messages.iter().all(|x| x["SCHEMA"].get("OBJ").is_none() && x["SCHEMA"].get("PARTITION").is_none()));
+
+        let (builder_ptr, output) = testing::builder(context_ptr, metadata_ptr.clone(), formats::MESSAGE_FORMAT_DEFAULT);
+        builder_ptr.process_row("insert", 13.into(), 1.into(), Default::default(), location, None, Some(&after), None, &LobContext::default())?;
+        assert_eq!(output.take()[0]["SCHEMA"], json!({"OWNER": "SCOTT", "TABLE": "T1"}));
+        Ok(())
+    }
+
+    #[test]
+    fn test_xid_format() -> Result<()> {
+        let context_ptr = testing::context();
+        let metadata_ptr = testing::metadata(context_ptr.clone());
+        let xid = TypeXid::new(10, 27, 12109);
+
+        for (xid_format, expected) in [(formats::XID_FORMAT_TEXT_HEX, json!("0x000a.01b.00002f4d")), (formats::XID_FORMAT_TEXT_DEC, json!("10.27.12109")),
+                                       (formats::XID_FORMAT_NUMERIC, json!(0x000A001B00002F4Du64)), (formats::XID_FORMAT_TEXT_REVERSED, json!("0A001B004D2F0000"))] {
+            let (builder_ptr, output) = testing::builder_with_formats(context_ptr.clone(), metadata_ptr.clone(), BuilderFormats { xid_format, ..testing::formats() });
+            builder_ptr.process_begin(10.into(), 1.into(), xid, None)?;
+            builder_ptr.process_commit(11.into(), 2.into(), xid, false, None)?;
+
+            let messages = output.take();
+            assert!(messages.iter().all(|x| x["XID"] == expected), "XID format {}: {:?}", xid_format, messages);
+
+            // Text is accepted back as xid, e.g. in skip-xid filter. Numeric xid of 16 digits needs leading zero.
+            let text = expected.as_u64().map(|x| format!("0{}", x)).unwrap_or_else(|| expected.as_str().unwrap().to_string());
+            assert_eq!(text.parse::<TypeXid>()?, xid);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_scn_format() -> Result<()> {
+        let context_ptr = testing::context();
+        let metadata_ptr = testing::metadata(context_ptr.clone());
+        set_table(&metadata_ptr);
+
+        let xid = TypeXid::new(1, 2, 3);
+        let location = RowLocation { obj : 100, data_obj : 101, xid, bdba : 0x01000010, slot : 0 };
+        let after = [(0, Some([0xC1, 0x02].as_slice()))];
+        let scns = |formats : BuilderFormats, commit_scn : bool| -> Result<Vec<serde_json::Value>> {
+            let (builder_ptr, output) = testing::builder_with_formats(context_ptr.clone(), metadata_ptr.clone(), formats);
+            if commit_scn {
+                builder_ptr.set_commit_scn(xid, 300.into());
+            }
+            builder_ptr.process_begin(100.into(), 1.into(), xid, None)?;
+            builder_ptr.process_row("insert", 200.into(), 1.into(), Default::default(), location, None, Some(&after), None, &LobContext::default())?;
+            builder_ptr.process_commit(300.into(), 2.into(), xid, false, None)?;
+            Ok(output.take().into_iter().map(|mut x| x["SCN"].take()).collect())
+        };
+
+        // SCN only in begin and commit by default
+        assert_eq!(scns(testing::formats(), false)?, [json!(100), json!(null), json!(300)]);
+        assert_eq!(scns(BuilderFormats { scn_format : formats::SCN_FORMAT_TEXT_HEX, ..testing::formats() }, false)?, 
+                   [json!("0x0000000000000064"), json!(null), json!("0x000000000000012c")]);
+        assert_eq!(scns(BuilderFormats { scn_all : formats::SCN_ALL_PAYLOADS, ..testing::formats() }, false)?, [json!(100), json!(200), json!(300)]);
 
+        // Commit SCN everywhere, if it is known before begin
+        let formats = BuilderFormats { scn_all : formats::SCN_ALL_PAYLOADS | formats::SCN_ALL_COMMIT_VALUE, ..testing::formats() };
+        assert_eq!(scns(formats, true)?, [json!(300), json!(300), json!(300)]);
         Ok(())
     }
 }
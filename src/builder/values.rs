@@ -0,0 +1,191 @@
+use std::cmp::Ordering;
+
+use crate::{common::constants, locales::Locales, oradefs::oracle_table::OracleColumn};
+
+/// Decoded value of column
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnValue {
+    Null,
+    Number(String),
+    Text(String),
+    Raw(Vec<u8>),
+}
+
+impl ColumnValue {
+    /// Text is converted from charset of database, or national charset for NCHAR and NVARCHAR2
+    pub fn decode(column : Option<&OracleColumn>, data : &[u8], is_null : bool, locales : &Locales) -> Self {
+        if is_null || data.is_empty() {
+            return ColumnValue::Null;
+        }
+
+        match column {
+            Some(column) if matches!(column.type_no, constants::SYS_COL_TYPE_VARCHAR | constants::SYS_COL_TYPE_CHAR) => 
+                ColumnValue::Text(locales.decode_text(data, column.charset_form)),
+            Some(column) if column.type_no == constants::SYS_COL_TYPE_NUMBER => match decode_number(data) {
+                Some(number) => ColumnValue::Number(number),
+                None => ColumnValue::Raw(data.to_vec()),
+            },
+            _ => ColumnValue::Raw(data.to_vec()),
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, ColumnValue::Null)
+    }
+
+    pub fn as_text(&self) -> Option<String> {
+        match self {
+            ColumnValue::Null => None,
+            ColumnValue::Number(x) | ColumnValue::Text(x) => Some(x.clone()),
+            ColumnValue::Raw(x) => Some(x.iter().map(|b| format!("{:02X}", b)).collect()),
+        }
+    }
+
+    /// SQL-like comparison: numbers are compared by value, other values as text. Null is not comparable.
+    pub fn compare(&self, other : &ColumnValue) -> Option<Ordering> {
+        match (self, other) {
+            (ColumnValue::Null, _) | (_, ColumnValue::Null) => None,
+            (ColumnValue::Number(a), ColumnValue::Number(b)) => compare_numbers(a, b),
+            (a, b) => a.as_text().cmp(&b.as_text()).into(),
+        }
+    }
+}
+
+impl std::fmt::Display for ColumnValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.as_text() {
+            Some(text) => write!(f, "{}", text),
+            None => write!(f, "null"),
+        }
+    }
+}
+
+/// Compares decimal strings without loss of precision, None if one of them is not a number
+fn compare_numbers(a : &str, b : &str) -> Option<Ordering> {
+    // Sign, integer digits without leading zeros and fraction digits without trailing zeros
+    fn split(number : &str) -> Option<(bool, &str, &str)> {
+        let (is_negative, number) = match number.strip_prefix('-') {
+            Some(x) => (true, x),
+            None => (false, number),
+        };
+        let (integer, fraction) = number.split_once('.').unwrap_or((number, ""));
+
+        if (integer.is_empty() && fraction.is_empty()) || !integer.bytes().chain(fraction.bytes()).all(|x| x.is_ascii_digit()) {
+            return None;
+        }
+
+        let (integer, fraction) = (integer.trim_start_matches('0'), fraction.trim_end_matches('0'));
+        Some((is_negative && !(integer.is_empty() && fraction.is_empty()), integer, fraction))
+    }
+
+    let (a_negative, a_integer, a_fraction) = split(a)?;
+    let (b_negative, b_integer, b_fraction) = split(b)?;
+
+    let magnitude = a_integer.len().cmp(&b_integer.len())
+        .then_with(|| a_integer.cmp(b_integer))
+        .then_with(|| a_fraction.cmp(b_fraction));
+
+    Some(match (a_negative, b_negative) {
+        (false, false) => magnitude,
+        (true, true) => magnitude.reverse(),
+        (false, true) => Ordering::Greater,
+        (true, false) => Ordering::Less,
+    })
+}
+
+/// Converts Oracle NUMBER to decimal string
+pub fn decode_number(data : &[u8]) -> Option<String> {
+    let (&head, mut digits) = data.split_first()?;
+
+    if head == 0x80 {
+        return Some("0".to_string());
+    }
+
+    let is_negative = head < 0x80;
+    let exponent = if is_negative {
+        if digits.last() == Some(&102) {
+            digits = &digits[.. digits.len() - 1];
+        }
+        0x3E - head as i32
+    } else {
+        head as i32 - 0xC1
+    };
+
+    let mut mantissa = String::with_capacity(digits.len() * 2);
+    for &digit in digits {
+        let value = if is_negative { 101i32 - digit as i32 } else { digit as i32 - 1 };
+        if !(0 .. 100).contains(&value) {
+            return None;
+        }
+        mantissa.push_str(format!("{:02}", value).as_str());
+    }
+
+    // Position of decimal point in mantissa
+    let point = (exponent + 1) * 2;
+    let mut result = if point <= 0 {
+        format!("0.{}{}", "0".repeat(-point as usize), mantissa)
+    } else if point as usize >= mantissa.len() {
+        format!("{}{}", mantissa, "0".repeat(point as usize - mantissa.len()))
+    } else {
+        format!("{}.{}", &mantissa[.. point as usize], &mantissa[point as usize ..])
+    };
+
+    if result.contains('.') {
+        result = result.trim_end_matches('0').trim_end_matches('.').to_string();
+    }
+
+    let result = match result.trim_start_matches('0') {
+        x if x.starts_with('.') => format!("0{}", x),
+        "" => "0".to_string(),
+        x => x.to_string(),
+    };
+
+    Some(if is_negative { format!("-{}", result) } else { result })
+}
+
+#[cfg(test)]
+mod test {
+    use std::cmp::Ordering;
+
+    use crate::{common::constants, locales::Locales, oradefs::oracle_table::OracleColumn};
+
+    use super::{compare_numbers, decode_number, ColumnValue};
+
+    #[test]
+    fn test_decode_number() {
+        assert_eq!(decode_number(&[0x80]).unwrap(), "0");
+        assert_eq!(decode_number(&[0xC1, 0x02]).unwrap(), "1");
+        assert_eq!(decode_number(&[0xC2, 0x02]).unwrap(), "100");
+        assert_eq!(decode_number(&[0xC2, 0x0D, 0x23]).unwrap(), "1234");
+        assert_eq!(decode_number(&[0xC1, 0x02, 0x33]).unwrap(), "1.5");
+        assert_eq!(decode_number(&[0xC0, 0x33]).unwrap(), "0.5");
+        assert_eq!(decode_number(&[0x3E, 0x64, 0x66]).unwrap(), "-1");
+        assert_eq!(decode_number(&[0x3D, 0x59, 0x43, 0x66]).unwrap(), "-1234");
+    }
+
+    #[test]
+    fn test_compare_numbers() {
+        assert_eq!(compare_numbers("12345678901234567890123", "12345678901234567890124"), Some(Ordering::Less));
+        assert_eq!(compare_numbers("0.10000000000000000001", "0.1"), Some(Ordering::Greater));
+        assert_eq!(compare_numbers("100", "99.99"), Some(Ordering::Greater));
+        assert_eq!(compare_numbers("-100", "-99.99"), Some(Ordering::Less));
+        assert_eq!(compare_numbers("007.50", "7.5"), Some(Ordering::Equal));
+        assert_eq!(compare_numbers("-0", "0.0"), Some(Ordering::Equal));
+        assert_eq!(compare_numbers("-1", "0.5"), Some(Ordering::Less));
+        assert_eq!(compare_numbers("1.2.3", "1"), None);
+    }
+
+    #[test]
+    fn test_decode_text() {
+        let column = |type_no, charset_form| OracleColumn { col_no : 1, seg_col_no : 1, name : "NAME".to_string(), type_no, length : 100, 
+                                                         precision : None, scale : None, nullable : true, charset_form, property : 0 };
+        let locales = Locales::new();
+        locales.set_charsets(178, 2000).unwrap();
+
+        let varchar = column(constants::SYS_COL_TYPE_VARCHAR, 1);
+        let nvarchar = column(constants::SYS_COL_TYPE_VARCHAR, constants::SYS_COL_CHARSET_FORM_NCHAR);
+        assert_eq!(ColumnValue::decode(Some(&varchar), &[0x47, 0xFC, 0x80], false, &locales), ColumnValue::Text("Gü€".to_string()));
+        assert_eq!(ColumnValue::decode(Some(&nvarchar), &[0x00, 0x47, 0x04, 0x16], false, &locales), ColumnValue::Text("GЖ".to_string()));
+        assert_eq!(ColumnValue::decode(Some(&varchar), &[], true, &locales), ColumnValue::Null);
+    }
+}
@@ -0,0 +1,33 @@
+use std::{fmt::Debug, fs::File, io::Write, path::Path};
+
+use serde_json::Value;
+
+use crate::{common::{errors::Result, OLRErrorCode::*}, olr_err};
+
+/// Destination of messages of `JsonBuilder`. Key identifies changed row, so message broker
+/// can use it as partitioning key and keep changes of one row in order.
+pub trait MessageWriter : Debug + Send {
+    fn write(&mut self, key : Option<&str>, message : &Value) -> Result<()>;
+}
+
+/// Writes messages to file, one JSON per line. Key is a part of message, so it is not written separately.
+#[derive(Debug)]
+pub struct FileWriter {
+    file : File,
+}
+
+impl FileWriter {
+    pub fn new(path : &Path) -> Result<Self> {
+        let file = std::fs::OpenOptions::new().write(true).create(true).truncate(true).open(path)
+            .map_err(|err| olr_err!(FileWriting, "Can not open output file: {}. Error: {}", path.display(), err))?;
+
+        Ok(Self { file })
+    }
+}
+
+impl MessageWriter for FileWriter {
+    fn write(&mut self, _key : Option<&str>, message : &Value) -> Result<()> {
+        writeln!(self.file, "{}", message)
+            .map_err(|err| olr_err!(FileWriting, "Can not write message in output file. Error: {}", err))
+    }
+}
@@ -6,10 +6,12 @@ pub const MEMORY_ALIGNMENT : usize = 512;
 pub const OPTIONS_SYSTEM_TABLE : u8 = 1;
 pub const OPTIONS_SCHEMA_TABLE : u8 = 2;
 
+pub const REDO_VERSION_11_2 : u32 = 0x0B200000;
 pub const REDO_VERSION_12_1 : u32 = 0x0C100000;
 pub const REDO_VERSION_12_2 : u32 = 0x0C200000;
 pub const REDO_VERSION_18_0 : u32 = 0x12000000;
 pub const REDO_VERSION_19_0 : u32 = 0x13000000;
+pub const REDO_VERSION_21_0 : u32 = 0x15000000;
 pub const REDO_VERSION_23_0 : u32 = 0x17000000;
 
 pub const FLAG_KTUCF_OP0504 : u8 = 0x02;
@@ -55,4 +57,50 @@ pub const OP_021 : u8 = 0x15;
 pub const OP_CMP : u8 = 0x16;
 pub const OP_DCU : u8 = 0x17;
 pub const OP_MRK : u8 = 0x18;
-pub const OP_ROWDEPENDENCIES : u8 = 0x40;
\ No newline at end of file
+pub const OP_ROWDEPENDENCIES : u8 = 0x40;
+pub const SYS_COL_TYPE_VARCHAR : u16 = 1;
+pub const SYS_COL_TYPE_NUMBER : u16 = 2;
+pub const SYS_COL_TYPE_RAW : u16 = 23;
+pub const SYS_COL_TYPE_CHAR : u16 = 96;
+pub const SYS_COL_TYPE_CLOB : u16 = 112;
+pub const SYS_COL_TYPE_BLOB : u16 = 113;
+
+pub const SYS_COL_CHARSET_FORM_NCHAR : u8 = 2;
+
+pub const SYS_COL_PROPERTY_ENCRYPTED : u64 = 0x4000000;
+pub const SYS_COL_PROPERTY_NO_SALT : u64 = 0x20000000;
+
+pub const SYS_ENC_ALG_AES128 : u16 = 2;
+pub const SYS_ENC_ALG_AES192 : u16 = 3;
+pub const SYS_ENC_ALG_AES256 : u16 = 4;
+pub const SYS_ENC_INT_ALG_SHA1 : u16 = 1;
+
+pub const KDLI_CODE_INFO : u8 = 0x01;
+pub const KDLI_CODE_LOAD_COMMON : u8 = 0x02;
+pub const KDLI_CODE_LOAD_DATA : u8 = 0x04;
+
+pub const DISABLE_CHECKS_GRANTS : u64 = 0x01;
+pub const DISABLE_CHECKS_SUPPLEMENTAL_LOG : u64 = 0x02;
+pub const DISABLE_CHECKS_BLOCK_SUM : u64 = 0x04; // Checksum of data blocks is not calculated, redo log header block is always checked
+pub const DISABLE_CHECKS_JSON_TAGS : u64 = 0x08;
+pub const DISABLE_CHECKS_BLOCK_NUMBER : u64 = 0x10; // Number and sequence of data blocks are not compared with their position in file
+pub const DISABLE_CHECKS_RECORD_SIZE : u64 = 0x20; // Record may cross the end of its LWN
+
+pub const SYS_USER_SPARE1_SUPP_LOG_PRIMARY : u64 = 0x01;
+pub const SYS_USER_SPARE1_SUPP_LOG_ALL : u64 = 0x08;
+
+pub const SYS_CDEF_TYPE_SUPP_LOG_PRIMARY : u16 = 14;
+pub const SYS_CDEF_TYPE_SUPP_LOG_ALL : u16 = 17;
+
+pub const SYS_OBJ_TYPE_INDEX : u16 = 1;
+pub const SYS_OBJ_TYPE_TABLE_PARTITION : u16 = 19;
+pub const SYS_OBJ_TYPE_TABLE_SUBPARTITION : u16 = 34;
+
+pub const SYS_TAB_PROPERTY_IOT : u64 = 64;
+pub const SYS_TAB_PROPERTY_IOT2 : u64 = 536870912;
+
+pub const SYS_IND_TYPE_IOT_TOP : u16 = 4;
+
+pub const KDILK_CODE_PURGE : u8 = 0x02;
+pub const KDILK_CODE_RESTORE : u8 = 0x05;
+pub const KDILK_CODE_UPDATE : u8 = 0x12;
@@ -28,9 +28,16 @@ pub enum OLRErrorCode {
     OracleConnection,
     OracleQuery,
     SchemaReading,
+    SupplementalLog,
+    Decryption,
     TransactionStart = 300000,
     TransactionClose,
     TransactionMemory,
+    BadFileHeader = 400000,
+    BadBlockChecksum,
+    BadBlockNumber,
+    BadBlockSequence,
+    BadRecordSize,
 }
 
 #[derive(Debug)]
@@ -3,6 +3,9 @@ pub mod types;
 pub mod constants;
 pub mod errors;
 pub mod memory_pool;
+pub mod tde;
+#[cfg(test)]
+pub mod testing;
 pub mod thread;
 
 pub use errors::OLRErrorCode;
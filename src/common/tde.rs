@@ -0,0 +1,196 @@
+use std::{collections::HashMap, path::Path};
+
+use aes::{Aes128, Aes192, Aes256};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use cbc::cipher::{block_padding::NoPadding, BlockDecryptMut, KeyIvInit};
+use hmac::{Hmac, Mac};
+use log::info;
+use sha1::Sha1;
+
+use crate::{common::{constants, errors::Result}, olr_err};
+use crate::common::OLRErrorCode::*;
+
+const AES_BLOCK_SIZE : usize = 16;
+const SHA1_MAC_SIZE : usize = 20;
+const SALT_SIZE : usize = 16;
+const MASTER_KEY_PREFIX : &'static str = "ORACLE.SECURITY.DB.ENCRYPTION.";
+
+/// Master keys exported from wallet. Each entry is line `ORACLE.SECURITY.DB.ENCRYPTION.<key id> = <base64 key>`,
+/// like `mkstore -viewEntry` prints it, other lines are skipped.
+#[derive(Debug, Default)]
+pub struct Keystore {
+    master_keys : HashMap<String, Vec<u8>>,
+}
+
+impl Keystore {
+    pub fn from_file(path : &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|err| olr_err!(FileReading, "Can not read keystore file: {:?}. Error: {}", path, err))?;
+
+        let keystore = Self::parse(&text)?;
+        info!("Load {} master keys from keystore: {:?}", keystore.master_keys.len(), path);
+        Ok(keystore)
+    }
+
+    fn parse(text : &str) -> Result<Self> {
+        let mut master_keys = HashMap::new();
+
+        for line in text.lines() {
+            let (name, value) = match line.split_once('=') {
+                Some((name, value)) => (name.trim(), value.trim()),
+                None => continue,
+            };
+
+            let key_id = match name.strip_prefix(MASTER_KEY_PREFIX) {
+                Some(key_id) if !key_id.is_empty() => key_id,
+                _ => continue,
+            };
+
+            let key = STANDARD.decode(value)
+                .map_err(|err| olr_err!(ParseError, "Master key: {} is not base64 value. Error: {}", key_id, err))?;
+
+            if !matches!(key.len(), 16 | 24 | 32) {
+                return olr_err!(ParseError, "Master key: {} has length: {}, expected AES key of 16, 24 or 32 bytes", key_id, key.len());
+            }
+
+            master_keys.insert(key_id.to_string(), key);
+        }
+
+        Ok(Self { master_keys })
+    }
+
+    pub fn master_key(&self, key_id : &str) -> Option<&Vec<u8>> {
+        self.master_keys.get(key_id)
+    }
+}
+
+/// Table key of encrypted columns from SYS.ENC$
+#[derive(Debug, Clone)]
+pub struct ColumnKey {
+    key : Vec<u8>,
+    mac_key : Option<Vec<u8>>,
+}
+
+impl ColumnKey {
+    /// Unwraps table key COLKLC with master key. Bytes after AES key are key of SHA-1 MAC.
+    pub fn new(master_key : &[u8], enc_alg : u16, int_alg : u16, wrapped_key : &[u8]) -> Result<Self> {
+        let key_size = match enc_alg {
+            constants::SYS_ENC_ALG_AES128 => 16,
+            constants::SYS_ENC_ALG_AES192 => 24,
+            constants::SYS_ENC_ALG_AES256 => 32,
+            _ => return olr_err!(Decryption, "Encryption algorithm: {} of column is not supported, expected AES", enc_alg),
+        };
+
+        let key = decrypt_aes_cbc(master_key, wrapped_key)?;
+        if key.len() < key_size {
+            return olr_err!(Decryption, "Table key has length: {}, expected: {}", key.len(), key_size);
+        }
+
+        let mac_key = match int_alg {
+            constants::SYS_ENC_INT_ALG_SHA1 if key.len() == key_size => return olr_err!(Decryption, "Table key has no MAC key after AES key"),
+            constants::SYS_ENC_INT_ALG_SHA1 => Some(key[key_size ..].to_vec()),
+            _ => None,
+        };
+
+        Ok(Self { key : key[.. key_size].to_vec(), mac_key })
+    }
+
+    /// Column value is encrypted with padding, salt is prepended to it and SHA-1 MAC is appended after cipher text.
+    /// MAC is HMAC-SHA1 of value with salt.
+    pub fn decrypt(&self, data : &[u8], has_salt : bool) -> Result<Vec<u8>> {
+        let (cipher_text, mac) = match self.mac_key {
+            Some(_) if data.len() >= SHA1_MAC_SIZE => data.split_at(data.len() - SHA1_MAC_SIZE),
+            Some(_) => return olr_err!(Decryption, "Encrypted value is shorter than MAC: {}", data.len()),
+            None => (data, [].as_slice()),
+        };
+
+        let mut plain_text = decrypt_aes_cbc(&self.key, cipher_text)?;
+
+        let padding = plain_text.last().cloned().unwrap_or(0) as usize;
+        if padding == 0 || padding > AES_BLOCK_SIZE || padding > plain_text.len()
+                || plain_text[plain_text.len() - padding ..].iter().any(|x| *x as usize != padding) {
+            return olr_err!(Decryption, "Wrong padding of decrypted value");
+        }
+        plain_text.truncate(plain_text.len() - padding);
+
+        if let Some(ref mac_key) = self.mac_key {
+            let mut hmac = <Hmac<Sha1> as Mac>::new_from_slice(mac_key)
+                .map_err(|err| olr_err!(Decryption, "Wrong MAC key. Error: {}", err))?;
+            hmac.update(&plain_text);
+            if hmac.verify_slice(mac).is_err() {
+                return olr_err!(Decryption, "MAC of decrypted value does not match");
+            }
+        }
+
+        if has_salt {
+            if plain_text.len() < SALT_SIZE {
+                return olr_err!(Decryption, "Decrypted value is shorter than salt: {}", plain_text.len());
+            }
+            plain_text.drain(.. SALT_SIZE);
+        }
+
+        Ok(plain_text)
+    }
+}
+
+/// AES in CBC mode with zero initialization vector, key size selects AES-128, AES-192 or AES-256
+pub fn decrypt_aes_cbc(key : &[u8], data : &[u8]) -> Result<Vec<u8>> {
+    if !data.len().is_multiple_of(AES_BLOCK_SIZE) {
+        return olr_err!(Decryption, "Encrypted data length: {} is not multiple of AES block", data.len());
+    }
+
+    let iv = [0u8; AES_BLOCK_SIZE];
+    let result = match key.len() {
+        16 => cbc::Decryptor::<Aes128>::new_from_slices(key, &iv).map(|x| x.decrypt_padded_vec_mut::<NoPadding>(data)),
+        24 => cbc::Decryptor::<Aes192>::new_from_slices(key, &iv).map(|x| x.decrypt_padded_vec_mut::<NoPadding>(data)),
+        32 => cbc::Decryptor::<Aes256>::new_from_slices(key, &iv).map(|x| x.decrypt_padded_vec_mut::<NoPadding>(data)),
+        _ => return olr_err!(Decryption, "AES key has length: {}, expected: 16, 24 or 32", key.len()),
+    };
+
+    match result {
+        Ok(Ok(plain_text)) => Ok(plain_text),
+        _ => olr_err!(Decryption, "Can not decrypt data of length: {}", data.len()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use aes::Aes256;
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use cbc::cipher::{block_padding::{NoPadding, Pkcs7}, BlockEncryptMut, KeyIvInit};
+    use hmac::{Hmac, Mac};
+    use sha1::Sha1;
+
+    use crate::common::constants;
+
+    use super::{ColumnKey, Keystore};
+
+    #[test]
+    fn test_column_decrypt() {
+        let master_key = [7u8; 32];
+        let table_key = [[9u8; 32], [5u8; 32]].concat();
+        let iv = [0u8; 16];
+
+        let text = format!("Oracle Secret Store Tool\nORACLE.SECURITY.DB.ENCRYPTION.AbCd = {}\n", STANDARD.encode(master_key));
+        let keystore = Keystore::parse(&text).unwrap();
+        assert!(keystore.master_key("XyZ").is_none());
+
+        let wrapped_key = cbc::Encryptor::<Aes256>::new(&master_key.into(), &iv.into()).encrypt_padded_vec_mut::<NoPadding>(&table_key);
+        assert!(ColumnKey::new(keystore.master_key("AbCd").unwrap(), constants::SYS_ENC_ALG_AES256, constants::SYS_ENC_INT_ALG_SHA1, &wrapped_key[.. 32]).is_err());
+        let key = ColumnKey::new(keystore.master_key("AbCd").unwrap(), constants::SYS_ENC_ALG_AES256, constants::SYS_ENC_INT_ALG_SHA1, &wrapped_key).unwrap();
+
+        let salted = [[1u8; 16].as_slice(), b"secret"].concat();
+        let mut data = cbc::Encryptor::<Aes256>::new_from_slices(&table_key[.. 32], &iv).unwrap().encrypt_padded_vec_mut::<Pkcs7>(&salted);
+        let mut hmac = <Hmac<Sha1> as Mac>::new_from_slice(&table_key[32 ..]).unwrap();
+        hmac.update(&salted);
+        data.extend_from_slice(&hmac.finalize().into_bytes());
+
+        assert_eq!(key.decrypt(&data, true).unwrap(), b"secret");
+        assert!(key.decrypt(&data[.. 20], true).is_err());
+
+        // Damaged MAC
+        let last = data.len() - 1;
+        data[last] ^= 1;
+        assert!(key.decrypt(&data, true).is_err());
+    }
+}
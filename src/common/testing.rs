@@ -0,0 +1,227 @@
+//! Objects of replicator for unit tests, without database and redo files
+
+use std::{collections::HashMap, path::PathBuf, sync::{Arc, Mutex}};
+
+use serde_json::Value;
+
+use crate::{builder::{formats::{self, BuilderFormats}, writer::MessageWriter, JsonBuilder}, common::{constants, errors::Result, types::TypeXid}, ctx::{Ctx, Dump}, locales::Locales, metadata::Metadata, oradefs::{oracle_schema::OracleSchemaResource, oracle_table::{OracleColumn, OracleTable}, table_history::TableHistory}, parser::{byte_reader::ByteReader, byte_writer::ByteWriter, opcodes::{opcode1801, Vector}, parser_impl::Parser}, transactions::transaction_buffer::TransactionBuffer};
+
+pub fn context() -> Arc<Ctx> {
+    Arc::new(Ctx::new(Dump::default(), 0, 0, 0, 0, "checkpoint".to_string(), 600, 500, 100, 20, 16, 64, 4).unwrap())
+}
+
+pub fn metadata(context_ptr : Arc<Ctx>) -> Arc<Metadata> {
+    Arc::new(Metadata::new(context_ptr, Arc::new(Locales::new()), "TEST".to_string(), -1, 0.into(), 0, String::new(), 0))
+}
+
+/// Message with its key
+pub type KeyedMessage = (Option<String>, Value);
+
+/// Messages of builder kept in memory, clones share the same messages
+#[derive(Debug, Clone, Default)]
+pub struct MemoryWriter {
+    messages : Arc<Mutex<Vec<KeyedMessage>>>,
+}
+
+impl MemoryWriter {
+    /// Messages written since the previous call
+    pub fn take(&self) -> Vec<Value> {
+        self.take_keyed().into_iter().map(|(_, x)| x).collect()
+    }
+
+    /// Messages with their keys written since the previous call
+    pub fn take_keyed(&self) -> Vec<KeyedMessage> {
+        std::mem::take(&mut self.messages.lock().unwrap())
+    }
+}
+
+impl MessageWriter for MemoryWriter {
+    fn write(&mut self, key : Option<&str>, message : &Value) -> Result<()> {
+        self.messages.lock().unwrap().push((key.map(|x| x.to_string()), message.clone()));
+        Ok(())
+    }
+}
+
+/// Formats of builder in tests, fields can be changed with struct update syntax
+pub fn formats() -> BuilderFormats {
+    BuilderFormats {
+        db_format : formats::DB_FORMAT_DEFAULT, 
+        attributes_format : formats::ATTRIBUTES_FORMAT_DEFAULT, 
+        interval_dts_format : formats::INTERVAL_DTS_FORMAT_UNIX_NANO, 
+        interval_ytm_format : formats::INTERVAL_YTM_FORMAT_MONTHS, 
+        message_format : formats::MESSAGE_FORMAT_DEFAULT, 
+        rid_format : formats::RID_FORMAT_SKIP, 
+        xid_format : formats::XID_FORMAT_TEXT_HEX, 
+        timestamp_format : formats::TIMESTAMP_FORMAT_UNIX_NANO, 
+        timestamp_tz_format : formats::TIMESTAMP_TZ_FORMAT_UNIX_NANO_STRING, 
+        timestamp_all : formats::TIMESTAMP_JUST_BEGIN, 
+        char_format : formats::CHAR_FORMAT_UTF8, 
+        scn_format : formats::SCN_FORMAT_NUMERIC, 
+        scn_all : formats::SCN_JUST_BEGIN, 
+        unknown_format : formats::UNKNOWN_FORMAT_QUESTION_MARK, 
+        schema_format : formats::SCHEMA_FORMAT_NAME, 
+        column_format : formats::COLUMN_FORMAT_CHANGED, 
+        unknown_type : formats::UNKNOWN_TYPE_HIDE,
+    }
+}
+
+/// Builder which keeps messages in returned writer
+pub fn builder(context_ptr : Arc<Ctx>, metadata_ptr : Arc<Metadata>, message_format : u8) -> (Arc<JsonBuilder>, MemoryWriter) {
+    builder_with_formats(context_ptr, metadata_ptr, BuilderFormats { message_format, ..formats() })
+}
+
+/// Builder with `formats` which keeps messages in returned writer
+pub fn builder_with_formats(context_ptr : Arc<Ctx>, metadata_ptr : Arc<Metadata>, formats : BuilderFormats) -> (Arc<JsonBuilder>, MemoryWriter) {
+    let writer = MemoryWriter::default();
+    let builder_ptr = Arc::new(JsonBuilder::new(context_ptr, Arc::new(Locales::new()), metadata_ptr,
+        formats.db_format, formats.attributes_format, formats.interval_dts_format, formats.interval_ytm_format,
+        formats.message_format, formats.rid_format, formats.xid_format, formats.timestamp_format, formats.timestamp_tz_format,
+        formats.timestamp_all, formats.char_format, formats.scn_format, formats.scn_all, formats.unknown_format,
+        formats.schema_format, formats.column_format, formats.unknown_type, Box::new(writer.clone())).unwrap());
+    (builder_ptr, writer)
+}
+
+/// Parser of 19c redo which sends changes to `builder_ptr`
+pub fn parser(context_ptr : Arc<Ctx>, builder_ptr : Arc<JsonBuilder>, metadata_ptr : Arc<Metadata>) -> Parser {
+    let transaction_buffer = Arc::new(Mutex::new(TransactionBuffer::new(context_ptr.clone(), builder_ptr.clone(), metadata_ptr.clone(), false)));
+    Parser::new(context_ptr, builder_ptr, metadata_ptr, transaction_buffer, PathBuf::new(), 0).unwrap()
+}
+
+/// Little endian 19c redo vector with fields, data of each field is aligned to 4 bytes
+pub fn vector_bytes(op_code : (u8, u8), fields : &[&[u8]]) -> Vec<u8> {
+    let sizes_size = (fields.len() + 1) * 2;
+    let fields_size : usize = fields.iter().map(|x| (x.len() + 3) & !3).sum();
+    let mut buffer = vec![0u8; 32 + ((sizes_size + 3) & !3) + fields_size];
+    let mut writer = ByteWriter::from_bytes(&mut buffer);
+
+    writer.write_u8(op_code.0).unwrap();
+    writer.write_u8(op_code.1).unwrap();
+    writer.write_u16(1).unwrap();       // class
+    writer.write_u16(1).unwrap();       // afn
+    writer.skip_bytes(2);
+    writer.write_u32(0x00400010).unwrap(); // dba
+    writer.skip_bytes(20);              // scn, seq, typ, con_id, flag
+    writer.write_u16(sizes_size as u16).unwrap();
+    for field in fields {
+        writer.write_u16(field.len() as u16).unwrap();
+    }
+    writer.set_cursor((writer.cursor() + 3) & !3).unwrap();
+
+    for field in fields {
+        writer.write_bytes(field).unwrap();
+        writer.set_cursor((writer.cursor() + 3) & !3).unwrap();
+    }
+
+    buffer
+}
+
+/// Undo (11.1 DRP) and redo (11.2 IRP) vectors of insert of row with `columns` by transaction `xid`
+pub fn insert_vectors_bytes(obj : u32, data_obj : u32, xid : TypeXid, columns : &[&[u8]]) -> (Vec<u8>, Vec<u8>) {
+    let mut xid_bytes = [0u8; 8];
+    xid_bytes[0 .. 2].copy_from_slice(&xid.undo_segment_number.to_le_bytes());
+    xid_bytes[2 .. 4].copy_from_slice(&xid.slot_number.to_le_bytes());
+    xid_bytes[4 .. 8].copy_from_slice(&xid.sequence_number.to_le_bytes());
+
+    let mut ktudb = [0u8; 20];
+    ktudb[8 .. 16].copy_from_slice(&xid_bytes);
+    let mut ktub = [0u8; 24];
+    ktub[0 .. 4].copy_from_slice(&obj.to_le_bytes());
+    ktub[4 .. 8].copy_from_slice(&data_obj.to_le_bytes());
+    ktub[16 .. 18].copy_from_slice(&[11, 1]);
+    let mut drp = [0u8; 20];
+    drp[0 .. 4].copy_from_slice(&0x01000010u32.to_le_bytes());
+    drp[10] = constants::OP_DRP;
+    let undo = vector_bytes((5, 1), &[&ktudb, &ktub, &[constants::KTBOP_Z, 0, 0, 0, 0, 0, 0, 0], &drp]);
+
+    let mut ktbredo = [0u8; 20];
+    ktbredo[0] = constants::KTBOP_F;
+    ktbredo[4 .. 12].copy_from_slice(&xid_bytes);
+    let mut irp = [0u8; 48];
+    irp[0 .. 4].copy_from_slice(&0x01000010u32.to_le_bytes());
+    irp[10] = constants::OP_IRP;
+    irp[16] = 0x2C;             // FB: K, F, L
+    irp[18] = columns.len() as u8;
+    let mut fields : Vec<&[u8]> = vec![&ktbredo, &irp];
+    fields.extend(columns);
+    let redo = vector_bytes((11, 2), &fields);
+
+    (undo, redo)
+}
+
+/// Undo of index leaf row (10.22) by transaction `xid` with kdilk `code`, key and key data of row
+pub fn index_undo_bytes(obj : u32, data_obj : u32, xid : TypeXid, code : u8, key : &[u8], keydata : &[u8]) -> Vec<u8> {
+    let mut ktudb = [0u8; 20];
+    ktudb[8 .. 10].copy_from_slice(&xid.undo_segment_number.to_le_bytes());
+    ktudb[10 .. 12].copy_from_slice(&xid.slot_number.to_le_bytes());
+    ktudb[12 .. 16].copy_from_slice(&xid.sequence_number.to_le_bytes());
+    let mut ktub = [0u8; 24];
+    ktub[0 .. 4].copy_from_slice(&obj.to_le_bytes());
+    ktub[4 .. 8].copy_from_slice(&data_obj.to_le_bytes());
+    ktub[16 .. 18].copy_from_slice(&[10, 22]);
+    let mut kdilk = [0u8; 20];
+    kdilk[0] = code;
+    kdilk[8 .. 12].copy_from_slice(&0x01000020u32.to_le_bytes());
+    vector_bytes((5, 1), &[&ktudb, &ktub, &[constants::KTBOP_Z, 0, 0, 0, 0, 0, 0, 0], &kdilk, key, keydata])
+}
+
+/// Commit or rollback (5.4) of transaction `xid`, undo segment number is kept in class of vector
+pub fn commit_vector_bytes(xid : TypeXid, is_rollback : bool) -> Vec<u8> {
+    let mut ktucm = [0u8; 20];
+    ktucm[0 .. 2].copy_from_slice(&xid.slot_number.to_le_bytes());
+    ktucm[4 .. 8].copy_from_slice(&xid.sequence_number.to_le_bytes());
+    ktucm[16] = if is_rollback { constants::FLAG_KTUCF_ROLLBACK } else { 0 };
+
+    let mut data = vector_bytes((5, 4), &[&ktucm]);
+    data[2 .. 4].copy_from_slice(&(xid.undo_segment_number * 2 + 15).to_le_bytes());
+    data
+}
+
+pub fn parse_vector<'a>(parser : &mut Parser, data : &'a [u8]) -> Vector<'a> {
+    let mut reader = ByteReader::from_bytes(data);
+    Vector::parse(parser, &mut reader, constants::REDO_VERSION_19_0).unwrap()
+}
+
+/// Column of segment position `seg_col_no` with `type_no` from SYS.COL$
+pub fn column(seg_col_no : u16, name : &str, type_no : u16) -> OracleColumn {
+    OracleColumn { col_no : seg_col_no, seg_col_no, name : name.to_string(), type_no, length : 100, 
+                   precision : None, scale : None, nullable : true, charset_form : 1, property : 0 }
+}
+
+fn table(obj : u32, data_obj : u32, mut table : OracleTable) -> Arc<OracleTable> {
+    table.set_obj(obj);
+    table.set_data_obj(Some(data_obj));
+    Arc::new(table)
+}
+
+/// Makes definitions of tables known for all SCNs, so schema does not read dictionary
+pub fn set_tables(metadata_ptr : &Metadata, tables : Vec<(u32, u32, OracleTable)>) {
+    let tables : HashMap<u32, TableHistory> = tables.into_iter().map(|(obj, data_obj, x)| {
+        let mut history = TableHistory::default();
+        history.insert(0, Some(table(obj, data_obj, x)));
+        (obj, history)
+    }).collect();
+
+    metadata_ptr.get_schema().set_tables(tables);
+}
+
+/// Definitions of tables which schema reads from dictionary when it has no definition for SCN
+pub fn set_dictionary(metadata_ptr : &Metadata, tables : Vec<(u32, u32, OracleTable)>) {
+    let tables = tables.into_iter().map(|(obj, data_obj, x)| (obj, table(obj, data_obj, x))).collect();
+    metadata_ptr.get_schema().set_resource(OracleSchemaResource::FromTables(tables));
+}
+
+/// Fields of "truncate table T1" vector of user SCOTT: header, session info, owner, sql text, object numbers
+pub fn truncate_vector_bytes(obj : u32) -> Vec<u8> {
+    ddl_vector_bytes(opcode1801::DDL_TYPE_TRUNCATE_TABLE, "truncate table T1", obj)
+}
+
+/// DDL vector of user SCOTT with `ddl_type`, `sql_text` and object number `obj`
+pub fn ddl_vector_bytes(ddl_type : u16, sql_text : &str, obj : u32) -> Vec<u8> {
+    let mut header = [0x01, 0x00, 0x00, 0x00, 0x0A, 0x00, 0x1B, 0x00, 0x4D, 0x2F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00];
+    header[12 .. 14].copy_from_slice(&ddl_type.to_le_bytes());
+    let sql_text = format!("{}\0", sql_text);
+    vector_bytes((24, 1), &[
+        &header, &[0x05, 0x00], &[0x13, 0x00], b"SCOTT", &[0x03, 0x00], b"SQL*Plus", &[0x00, 0x00],
+        sql_text.as_bytes(), &[0x23, 0x01, 0x00, 0x00], &[0x00; 8], &[0x00; 4], &obj.to_le_bytes(),
+    ])
+}
@@ -0,0 +1,22 @@
+use std::fmt::{Formatter, Display};
+
+pub const LOB_ID_SIZE : usize = 10;
+
+/// Identifier of LOB from its locator and LOB redo
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TypeLobId([u8; LOB_ID_SIZE]);
+
+impl TypeLobId {
+    pub fn from_bytes(data : &[u8]) -> Option<Self> {
+        Some(Self(data.get(.. LOB_ID_SIZE)?.try_into().ok()?))
+    }
+}
+
+impl Display for TypeLobId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for byte in self.0 {
+            write!(f, "{:02X}", byte)?;
+        }
+        Ok(())
+    }
+}
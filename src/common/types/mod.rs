@@ -6,6 +6,9 @@ pub mod xid;
 pub mod fb;
 pub mod timestamp;
 pub mod record_scn;
+pub mod row_id;
+pub mod redo_position;
+pub mod lob_id;
 
 pub use scn::TypeScn;
 pub use rba::TypeRBA;
@@ -14,6 +17,9 @@ pub use xid::TypeXid;
 pub use fb::TypeFb;
 pub use timestamp::TypeTimestamp;
 pub use record_scn::TypeRecordScn;
+pub use row_id::TypeRowId;
+pub use redo_position::TypeRedoPosition;
+pub use lob_id::TypeLobId;
 
 pub type TypeSeq = u32;
 pub type TypeConId = i16;
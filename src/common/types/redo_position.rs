@@ -0,0 +1,23 @@
+use std::fmt::{Formatter, Display};
+
+use super::TypeSeq;
+
+/// Place of redo record in archive log
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TypeRedoPosition {
+    pub sequence : TypeSeq,
+    pub block : u32,
+    pub offset : u16,
+}
+
+impl TypeRedoPosition {
+    pub fn new(sequence : TypeSeq, block : u32, offset : u16) -> Self {
+        Self { sequence, block, offset }
+    }
+}
+
+impl Display for TypeRedoPosition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.sequence, self.block, self.offset)
+    }
+}
@@ -0,0 +1,55 @@
+use std::fmt::{Formatter, Display};
+
+const ROWID_MAP : &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Extended ROWID: data object, relative file, block and row
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TypeRowId {
+    pub data_obj : u32,
+    pub dba : u32,
+    pub slot : u16,
+}
+
+impl TypeRowId {
+    pub fn new(data_obj : u32, dba : u32, slot : u16) -> Self {
+        Self { data_obj, dba, slot }
+    }
+
+    pub fn file(&self) -> u16 {
+        (self.dba >> 22) as u16
+    }
+
+    pub fn block(&self) -> u32 {
+        self.dba & 0x003FFFFF
+    }
+}
+
+impl Display for TypeRowId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut result = [0u8; 18];
+        let mut encode = |offset : usize, size : usize, value : u64| {
+            for i in 0 .. size {
+                result[offset + i] = ROWID_MAP[((value >> (6 * (size - 1 - i))) & 0x3F) as usize];
+            }
+        };
+
+        encode(0, 6, self.data_obj as u64);
+        encode(6, 3, self.file() as u64);
+        encode(9, 6, self.block() as u64);
+        encode(15, 3, self.slot as u64);
+
+        write!(f, "{}", std::str::from_utf8(&result).unwrap())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TypeRowId;
+
+    #[test]
+    fn test_row_id() {
+        // data_obj 73393, file 7, block 237, row 0
+        let row_id = TypeRowId::new(73393, (7 << 22) | 237, 0);
+        assert_eq!(row_id.to_string(), "AAAR6xAAHAAAADtAAA");
+    }
+}
@@ -9,6 +9,12 @@ impl From<u32> for TypeTimestamp {
     }
 }
 
+impl From<TypeTimestamp> for u32 {
+    fn from(val: TypeTimestamp) -> Self {
+        val.0
+    }
+}
+
 impl Debug for TypeTimestamp where TypeTimestamp : Display {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         Display::fmt(self, f)
@@ -1,4 +1,7 @@
-use std::fmt::{Formatter, Debug, Display};
+use std::{fmt::{Debug, Display, Formatter}, str::FromStr};
+
+use crate::{common::errors::OLRError, olr_err};
+use crate::common::OLRErrorCode::*;
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct TypeXid {
@@ -32,3 +35,61 @@ impl Display for TypeXid {
         write!(f, "{}.{}.{}", self.undo_segment_number, self.slot_number, self.sequence_number)
     }
 }
+
+impl From<TypeXid> for u64 {
+    fn from(value: TypeXid) -> Self {
+        ((value.undo_segment_number as u64) << 48) | ((value.slot_number as u64) << 32) | value.sequence_number as u64
+    }
+}
+
+/// Accepts textual forms of all xid formats: "0xUUUU.SSS.QQQQQQQQ", "U.S.Q", numeric and reversed "UUUUSSSSQQQQQQQQ"
+/// with bytes of each part swapped. Reversed form has exactly 16 hex digits, so numeric xid of 16 digits needs leading zero.
+impl FromStr for TypeXid {
+    type Err = OLRError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        if text.len() == 16 && text.bytes().all(|x| x.is_ascii_hexdigit()) {
+            let xid = match (u16::from_str_radix(&text[0 .. 4], 16), u16::from_str_radix(&text[4 .. 8], 16), u32::from_str_radix(&text[8 .. 16], 16)) {
+                (Ok(usn), Ok(slt), Ok(seq)) => TypeXid::new(usn.swap_bytes(), slt.swap_bytes(), seq.swap_bytes()),
+                _ => return olr_err!(NotValidField, "Not valid xid: {}", text),
+            };
+            return Ok(xid);
+        }
+
+        let (parts, radix) = match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+            Some(hex) => (hex.split('.').collect::<Vec<&str>>(), 16),
+            None => (text.split('.').collect::<Vec<&str>>(), 10),
+        };
+
+        let xid = match parts.as_slice() {
+            [usn, slt, seq] => match (u16::from_str_radix(usn, radix), u16::from_str_radix(slt, radix), u32::from_str_radix(seq, radix)) {
+                (Ok(usn), Ok(slt), Ok(seq)) => Some(TypeXid::new(usn, slt, seq)),
+                _ => None,
+            },
+            [number] => u64::from_str_radix(number, radix).ok().map(TypeXid::from),
+            _ => None,
+        };
+
+        match xid {
+            Some(xid) => Ok(xid),
+            None => olr_err!(NotValidField, "Not valid xid: {}", text),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TypeXid;
+
+    #[test]
+    fn test_parse_xid() {
+        let xid = TypeXid::new(2, 3, 4020);
+        assert_eq!("0x0002.003.00000fb4".parse::<TypeXid>().unwrap(), xid);
+        assert_eq!("2.3.4020".parse::<TypeXid>().unwrap(), xid);
+        assert_eq!(u64::from(xid).to_string().parse::<TypeXid>().unwrap(), xid);
+        assert_eq!("02000300B40F0000".parse::<TypeXid>().unwrap(), xid);
+        assert_eq!(format!("{:04X}{:04X}{:08X}", 2u16.swap_bytes(), 3u16.swap_bytes(), 4020u32.swap_bytes()).parse::<TypeXid>().unwrap(), xid);
+        assert!("2.3".parse::<TypeXid>().is_err());
+        assert!("0x2.3.zz".parse::<TypeXid>().is_err());
+    }
+}
@@ -21,6 +21,7 @@ pub struct Ctx {
     pub disable_checks : u64,
 
     // State
+    pub state_path : String,
     pub checkpoint_interval_s : u64,
     pub checkpoint_interval_mb : u64,
     pub checkpoint_keep : u64,
@@ -32,13 +33,13 @@ pub struct Ctx {
 
 impl Ctx {
     pub fn new(dump : Dump, log_level : u64, trace : u64, flags : u64, disable_checks : u64, 
-        checkpoint_interval_s : u64, checkpoint_interval_mb : u64, checkpoint_keep : u64,
+        state_path : String, checkpoint_interval_s : u64, checkpoint_interval_mb : u64, checkpoint_keep : u64,
         schema_force_interval : u64, memory_min_mb: usize , memory_max_mb: usize, read_buffer_max: usize) -> Result<Self> {
         debug!("Initialize Ctx");
         
         Ok(Self {
             dump, log_level : log_level, trace, flags, disable_checks,
-            checkpoint_interval_s, checkpoint_interval_mb, checkpoint_keep,
+            state_path, checkpoint_interval_s, checkpoint_interval_mb, checkpoint_keep,
             schema_force_interval,
             memory_manager : MemoryPool::new(memory_min_mb, memory_max_mb, read_buffer_max)?.into()
         })
@@ -1,4 +1,4 @@
-
-pub trait CharacterMapper {
+pub trait CharacterMapper : Send + Sync {
+    fn name(&self) -> &'static str;
     fn map_to_utf8(&self, string : &[u8]) -> Vec<u8>;
 }
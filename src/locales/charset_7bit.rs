@@ -13,6 +13,10 @@ impl CharSet7Bit {
 }
 
 impl CharacterMapper for CharSet7Bit {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
     fn map_to_utf8(&self, string : &[u8]) -> Vec<u8> {
         let mut result : Vec<u8> = Vec::with_capacity(string.len());
         for i in string {
@@ -0,0 +1,114 @@
+use super::charset::CharacterMapper;
+
+/// UTF8 and AL32UTF8, data is already in UTF-8
+#[derive(Debug)]
+pub struct CharSetUtf8 {
+    name : &'static str,
+}
+
+impl CharSetUtf8 {
+    pub fn new(name : &'static str) -> Self {
+        Self {name}
+    }
+}
+
+impl CharacterMapper for CharSetUtf8 {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn map_to_utf8(&self, string : &[u8]) -> Vec<u8> {
+        String::from_utf8_lossy(string).into_owned().into_bytes()
+    }
+}
+
+/// AL16UTF16, big endian UTF-16 of national character set
+#[derive(Debug)]
+pub struct CharSetUtf16 {
+    name : &'static str,
+}
+
+impl CharSetUtf16 {
+    pub fn new(name : &'static str) -> Self {
+        Self {name}
+    }
+}
+
+impl CharacterMapper for CharSetUtf16 {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn map_to_utf8(&self, string : &[u8]) -> Vec<u8> {
+        let chars : Vec<u16> = string.chunks_exact(2).map(|x| u16::from_be_bytes([x[0], x[1]])).collect();
+        String::from_utf16_lossy(&chars).into_bytes()
+    }
+}
+
+/// Single byte charset which is ASCII in lower half, `map` has code points of bytes 0x80 .. 0xFF
+#[derive(Debug)]
+pub struct CharSet8Bit {
+    name : &'static str,
+    map  : &'static [u16],
+}
+
+impl CharSet8Bit {
+    pub fn new(name : &'static str, map : &'static [u16]) -> Self {
+        Self {name, map}
+    }
+}
+
+impl CharacterMapper for CharSet8Bit {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn map_to_utf8(&self, string : &[u8]) -> Vec<u8> {
+        string.iter()
+            .map(|x| match *x {
+                0x00 ..= 0x7F => *x as char,
+                x => char::from_u32(self.map[x as usize - 0x80] as u32).unwrap_or(char::REPLACEMENT_CHARACTER),
+            })
+            .collect::<String>()
+            .into_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unicode() {
+        let utf8 = CharSetUtf8::new("AL32UTF8");
+        assert_eq!(utf8.map_to_utf8("Grüße".as_bytes()), "Grüße".as_bytes());
+
+        let utf16 = CharSetUtf16::new("AL16UTF16");
+        assert_eq!(utf16.map_to_utf8(&[0x00, 0x47, 0x00, 0xFC, 0x04, 0x16]), "GüЖ".as_bytes());
+
+        let latin = CharSet8Bit::new("WE8MSWIN1252", UNICODE_MAP_WE8MSWIN1252);
+        assert_eq!(latin.map_to_utf8(&[0x80, 0x20, 0x47, 0xFC]), "€ Gü".as_bytes());
+    }
+}
+
+pub const UNICODE_MAP_WE8ISO8859P1 : &'static [u16] = &[
+    0x80, 0x81, 0x82, 0x83, 0x84, 0x85, 0x86, 0x87, 0x88, 0x89, 0x8A, 0x8B, 0x8C, 0x8D, 0x8E, 0x8F, 
+    0x90, 0x91, 0x92, 0x93, 0x94, 0x95, 0x96, 0x97, 0x98, 0x99, 0x9A, 0x9B, 0x9C, 0x9D, 0x9E, 0x9F, 
+    0xA0, 0xA1, 0xA2, 0xA3, 0xA4, 0xA5, 0xA6, 0xA7, 0xA8, 0xA9, 0xAA, 0xAB, 0xAC, 0xAD, 0xAE, 0xAF, 
+    0xB0, 0xB1, 0xB2, 0xB3, 0xB4, 0xB5, 0xB6, 0xB7, 0xB8, 0xB9, 0xBA, 0xBB, 0xBC, 0xBD, 0xBE, 0xBF, 
+    0xC0, 0xC1, 0xC2, 0xC3, 0xC4, 0xC5, 0xC6, 0xC7, 0xC8, 0xC9, 0xCA, 0xCB, 0xCC, 0xCD, 0xCE, 0xCF, 
+    0xD0, 0xD1, 0xD2, 0xD3, 0xD4, 0xD5, 0xD6, 0xD7, 0xD8, 0xD9, 0xDA, 0xDB, 0xDC, 0xDD, 0xDE, 0xDF, 
+    0xE0, 0xE1, 0xE2, 0xE3, 0xE4, 0xE5, 0xE6, 0xE7, 0xE8, 0xE9, 0xEA, 0xEB, 0xEC, 0xED, 0xEE, 0xEF, 
+    0xF0, 0xF1, 0xF2, 0xF3, 0xF4, 0xF5, 0xF6, 0xF7, 0xF8, 0xF9, 0xFA, 0xFB, 0xFC, 0xFD, 0xFE, 0xFF
+];
+
+pub const UNICODE_MAP_WE8MSWIN1252 : &'static [u16] = &[
+    0x20AC, 0xFFFD, 0x201A, 0x0192, 0x201E, 0x2026, 0x2020, 0x2021, 0x02C6, 0x2030, 0x0160, 0x2039, 0x0152, 0xFFFD, 0x017D, 0xFFFD, 
+    0xFFFD, 0x2018, 0x2019, 0x201C, 0x201D, 0x2022, 0x2013, 0x2014, 0x02DC, 0x2122, 0x0161, 0x203A, 0x0153, 0xFFFD, 0x017E, 0x0178, 
+    0xA0, 0xA1, 0xA2, 0xA3, 0xA4, 0xA5, 0xA6, 0xA7, 0xA8, 0xA9, 0xAA, 0xAB, 0xAC, 0xAD, 0xAE, 0xAF, 
+    0xB0, 0xB1, 0xB2, 0xB3, 0xB4, 0xB5, 0xB6, 0xB7, 0xB8, 0xB9, 0xBA, 0xBB, 0xBC, 0xBD, 0xBE, 0xBF, 
+    0xC0, 0xC1, 0xC2, 0xC3, 0xC4, 0xC5, 0xC6, 0xC7, 0xC8, 0xC9, 0xCA, 0xCB, 0xCC, 0xCD, 0xCE, 0xCF, 
+    0xD0, 0xD1, 0xD2, 0xD3, 0xD4, 0xD5, 0xD6, 0xD7, 0xD8, 0xD9, 0xDA, 0xDB, 0xDC, 0xDD, 0xDE, 0xDF, 
+    0xE0, 0xE1, 0xE2, 0xE3, 0xE4, 0xE5, 0xE6, 0xE7, 0xE8, 0xE9, 0xEA, 0xEB, 0xEC, 0xED, 0xEE, 0xEF, 
+    0xF0, 0xF1, 0xF2, 0xF3, 0xF4, 0xF5, 0xF6, 0xF7, 0xF8, 0xF9, 0xFA, 0xFB, 0xFC, 0xFD, 0xFE, 0xFF
+];
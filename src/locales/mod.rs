@@ -1,18 +1,26 @@
 pub mod charset;
 pub mod charset_7bit;
+pub mod charset_unicode;
 
-use std::fmt::{Formatter, Debug};
+use std::{fmt::{Debug, Formatter}, sync::OnceLock};
 
 use charset::CharacterMapper;
 use charset_7bit::CharSet7Bit;
-use log::debug;
+use charset_unicode::{CharSet8Bit, CharSetUtf16, CharSetUtf8};
+use log::{debug, info};
 
-use crate::{common::errors::Result, olr_err};
+use crate::{common::{constants, errors::Result}, olr_err};
 use crate::common::OLRErrorCode::*;
 
+/// Character set and national character set of database
+struct DatabaseCharsets {
+    charset : Box<dyn CharacterMapper>,
+    national : Box<dyn CharacterMapper>,
+}
+
 #[derive(Default)]
 pub struct Locales {
-    // pub character_map   : HashMap<u64, Arc<dyn CharacterMapper>>,
+    charsets : OnceLock<DatabaseCharsets>,
     // pub timezone_map    : HashMap<u16, &'static str>,
 }
 
@@ -28,6 +36,31 @@ impl Locales {
         Self::default()
     }
 
+    /// Sets charsets of database, text columns are converted from them to UTF-8
+    pub fn set_charsets(&self, charset_id : u64, national_id : u64) -> Result<()> {
+        let charsets = DatabaseCharsets { charset : Self::get_char_set(charset_id)?, national : Self::get_char_set(national_id)? };
+        info!("Database charset: {} national charset: {}", charsets.charset.name(), charsets.national.name());
+
+        if self.charsets.set(charsets).is_err() {
+            return olr_err!(UnknownCharset, "Charsets of database are already set");
+        }
+        Ok(())
+    }
+
+    /// Converts text of column with `charset_form` to UTF-8. Text is taken as UTF-8 until charsets are set.
+    pub fn decode_text(&self, data : &[u8], charset_form : u8) -> String {
+        let charsets = match self.charsets.get() {
+            Some(charsets) => charsets,
+            None => return String::from_utf8_lossy(data).into_owned(),
+        };
+
+        let mapper = match charset_form {
+            constants::SYS_COL_CHARSET_FORM_NCHAR => &charsets.national,
+            _ => &charsets.charset,
+        };
+        String::from_utf8_lossy(&mapper.map_to_utf8(data)).into_owned()
+    }
+
     pub fn get_char_set(charset_id : u64) -> Result<Box<dyn CharacterMapper>> {
         match charset_id {
             // 7-bit charsets
@@ -45,6 +78,13 @@ impl Locales {
             205 => Ok(Box::new(CharSet7Bit::new("N7SIEMENS9780X", charset_7bit::UNICODE_MAP_N7SIEMENS9780X))),
             206 => Ok(Box::new(CharSet7Bit::new("I7SIEMENS9780X", charset_7bit::UNICODE_MAP_I7SIEMENS9780X))),
             207 => Ok(Box::new(CharSet7Bit::new("D7SIEMENS9780X", charset_7bit::UNICODE_MAP_D7SIEMENS9780X))),
+            // 8-bit charsets
+            31 => Ok(Box::new(CharSet8Bit::new("WE8ISO8859P1", charset_unicode::UNICODE_MAP_WE8ISO8859P1))),
+            178 => Ok(Box::new(CharSet8Bit::new("WE8MSWIN1252", charset_unicode::UNICODE_MAP_WE8MSWIN1252))),
+            // Unicode charsets
+            871 => Ok(Box::new(CharSetUtf8::new("UTF8"))),
+            873 => Ok(Box::new(CharSetUtf8::new("AL32UTF8"))),
+            2000 => Ok(Box::new(CharSetUtf16::new("AL16UTF16"))),
             _ => olr_err!(UnknownCharset, "Unknown charset: {}", charset_id)
         }
     }
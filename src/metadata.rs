@@ -1,8 +1,60 @@
-use std::{collections::HashSet, sync::{Arc, Mutex, MutexGuard}};
+use std::{collections::{HashMap, HashSet}, fs::File, io::BufReader, path::PathBuf, sync::{Arc, Mutex, MutexGuard, RwLock}, time::{Duration, Instant}};
 
-use log::{debug, warn};
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
 
-use crate::{common::{errors::Result, types::{TypeConId, TypeScn, TypeSeq}}, ctx::Ctx, locales::Locales, oradefs::{db_object::DataBaseObject, oracle_schema::{OracleSchema, OracleSchemaResource}}};
+use crate::{builder::condition::Condition, common::{constants, errors::Result, tde::{ColumnKey, Keystore}, types::{TypeConId, TypeScn, TypeSeq, TypeXid}}, ctx::Ctx, locales::Locales, olr_err, parser::opcodes::opcode1801::{self, OpCode1801}, oradefs::{db_object::DataBaseObject, oracle_schema::{OracleSchema, OracleSchemaResource}, table_history::TableHistory}};
+use crate::common::OLRErrorCode::*;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Checkpoint {
+    #[serde(flatten)]
+    position : CheckpointPosition,
+    tables : HashMap<u32, TableHistory>,
+}
+
+/// Position in redo up to which changes were parsed: redo sequence, offset in its file and SCN
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CheckpointPosition {
+    pub sequence : TypeSeq,
+    #[serde(default)]
+    pub offset : u64,
+    pub scn : u64,
+}
+
+/// Progress of replication since the last written checkpoint
+#[derive(Debug)]
+struct CheckpointState {
+    count : u64,
+    time : Instant,
+    bytes : u64,
+}
+
+/// Segments of tables which match filter. OBJ# and DATAOBJ# are different number spaces,
+/// so they are kept apart. Set is read for each change vector and changed by DDL only.
+#[derive(Debug, Default)]
+struct TrackedObjects {
+    is_filtered : bool,
+    objs : HashSet<u32>,
+    data_objs : HashSet<u32>,
+}
+
+impl TrackedObjects {
+    /// Returns false if OBJ# was tracked already
+    fn insert(&mut self, obj : u32, data_obj : Option<u32>) -> bool {
+        self.data_objs.extend(data_obj);
+        self.objs.insert(obj)
+    }
+}
+
+/// Keys of transparent data encryption
+#[derive(Debug, Default)]
+struct Encryption {
+    keystore : Option<Keystore>,
+    column_keys : HashMap<u32, ColumnKey>,
+    encrypted_files : HashMap<u32, u32>,
+    skipped_files : HashSet<u32>,
+}
 
 #[derive(Debug)]
 pub struct Metadata {
@@ -15,10 +67,18 @@ pub struct Metadata {
     start_time      : String,
     start_time_rel  : u64,
 
+    container_name : Mutex<Option<String>>,
     schema_objects : Mutex<Vec<DataBaseObject>>,
     users : Mutex<HashSet<String>>,
+    tracked_objects : RwLock<TrackedObjects>,
+    skip_xids : Mutex<HashSet<TypeXid>>,
+    dump_xids : Mutex<HashSet<TypeXid>>,
+    skip_users : Mutex<HashSet<String>>,
 
     schema : Mutex<OracleSchema>,
+    encryption : Mutex<Encryption>,
+    checkpoint_state : Mutex<CheckpointState>,
+    checkpoint_position : Mutex<Option<CheckpointPosition>>,
 }
 
 impl Metadata {
@@ -32,13 +92,18 @@ impl Metadata {
         debug!("Initialize Metadata");
         let result = Self {
             context_ptr, locales_ptr, source_name, container_id, start_scn, start_sequence, 
-            start_time, start_time_rel, schema_objects : Vec::new().into(), users : HashSet::new().into(),
-            schema : Default::default(),
+            start_time, start_time_rel, container_name : None.into(), schema_objects : Vec::new().into(), users : HashSet::new().into(),
+            tracked_objects : Default::default(),
+            skip_xids : HashSet::new().into(), dump_xids : HashSet::new().into(),
+            skip_users : HashSet::new().into(),
+            schema : Default::default(), encryption : Default::default(),
+            checkpoint_state : CheckpointState { count : 0, time : Instant::now(), bytes : 0 }.into(),
+            checkpoint_position : None.into(),
         };
         result
     }
 
-    pub fn add_object<'a>(&'a self, mut user : String, mut table : String, options : u8) -> MutexGuard<'a, Vec<DataBaseObject>> {
+    pub fn add_object<'a>(&'a self, mut user : String, mut table : String, options : u8) -> Result<MutexGuard<'a, Vec<DataBaseObject>>> {
         let mut guard = self.schema_objects.lock().unwrap();
         if user.as_bytes().iter().any(|x| u8::is_ascii_lowercase(x)) {
             warn!("In table parameter User: {} not all chars are uppercase. Try force rename.", user);
@@ -50,8 +115,9 @@ impl Metadata {
             table = table.to_ascii_uppercase();
         }
 
-        guard.push(DataBaseObject::new(user, table, options));
-        guard
+        guard.push(DataBaseObject::new(user, table, options)?);
+        self.tracked_objects.write().unwrap().is_filtered = true;
+        Ok(guard)
     }
 
     pub fn add_user(&self, user : String) {
@@ -59,14 +125,521 @@ impl Metadata {
         guard.insert(user);
     }
 
+    pub fn add_skip_xid(&self, xid : TypeXid) {
+        self.skip_xids.lock().unwrap().insert(xid);
+    }
+
+    pub fn add_dump_xid(&self, xid : TypeXid) {
+        self.dump_xids.lock().unwrap().insert(xid);
+    }
+
+    pub fn add_skip_user(&self, user : String) {
+        self.skip_users.lock().unwrap().insert(user);
+    }
+
+    pub fn is_user_skipped(&self, user : &str) -> bool {
+        self.skip_users.lock().unwrap().contains(user)
+    }
+
+    pub fn is_xid_skipped(&self, xid : TypeXid) -> bool {
+        self.skip_xids.lock().unwrap().contains(&xid)
+    }
+
+    pub fn is_xid_dumped(&self, xid : TypeXid) -> bool {
+        self.dump_xids.lock().unwrap().contains(&xid)
+    }
+
+    pub fn has_dump_xids(&self) -> bool {
+        !self.dump_xids.lock().unwrap().is_empty()
+    }
+
     pub fn set_schema_resource(&self, resource : OracleSchemaResource) -> Result<()> {
         let mut guard = self.schema.lock().unwrap();
         guard.set_resource(resource);
         Ok(())
     }
 
+    /// Dictionary of pluggable database is read after switch of session to container of `con-id`
+    pub fn switch_container(&self) -> Result<()> {
+        if self.container_id < 0 {
+            return Ok(());
+        }
+
+        let name = self.schema.lock().unwrap().switch_container(self.container_id as u32)?;
+        info!("Replicate container: {} con_id: {}", name, self.container_id);
+        *self.container_name.lock().unwrap() = Some(name);
+        Ok(())
+    }
+
+    /// Text columns are converted to UTF-8 from charsets of database
+    pub fn load_charsets(&self) -> Result<()> {
+        let (charset_id, national_id) = self.schema.lock().unwrap().get_database_charsets()?;
+        self.locales_ptr.set_charsets(charset_id, national_id)
+    }
+
+    pub fn container_name(&self) -> Option<String> {
+        self.container_name.lock().unwrap().clone()
+    }
+
+    /// Records of other containers are skipped when `con-id` is set. Container id 0 is not bound to any container.
+    pub fn is_container_tracked(&self, con_id : Option<u32>) -> bool {
+        self.container_id < 0 || con_id.is_none_or(|x| x == 0 || x == self.container_id as u32)
+    }
+
     pub fn get_schema(&self) -> MutexGuard<'_, OracleSchema> {
         self.schema.lock().unwrap()
     }
 
+    /// Resolves filter patterns to set of OBJ# and DATAOBJ# of matching tables
+    pub fn resolve_filter(&self) -> Result<()> {
+        let objects = self.schema_objects.lock().unwrap();
+        if objects.is_empty() {
+            return Ok(());
+        }
+
+        let mut schema = self.schema.lock().unwrap();
+        let mut tracked = self.tracked_objects.write().unwrap();
+
+        for (obj, data_obj, owner, name) in schema.get_user_tables()? {
+            if objects.iter().any(|x| x.matches(&owner, &name)) {
+                debug!("Track table: {}.{} obj: {} data_obj: {:?}", owner, name, obj, data_obj);
+                tracked.insert(obj, data_obj);
+            }
+        }
+
+        // Rows of partitioned table are changed in segments of partitions
+        schema.load_partitions()?;
+        for (obj, partition_obj, partition_data_obj) in schema.get_table_partitions() {
+            if tracked.objs.contains(&obj) {
+                debug!("Track partition: {} data_obj: {:?} of table obj: {}", partition_obj, partition_data_obj, obj);
+                tracked.insert(partition_obj, partition_data_obj);
+            }
+        }
+
+        // Undo of LOB changes refers to LOB segment instead of table
+        for (obj, lob_obj, lob_data_obj) in schema.get_lob_segments()? {
+            if tracked.objs.contains(&obj) {
+                debug!("Track LOB segment: {} data_obj: {:?} of table obj: {}", lob_obj, lob_data_obj, obj);
+                tracked.insert(lob_obj, lob_data_obj);
+            }
+        }
+
+        // Rows of index-organized table are changed in its primary key index
+        for (obj, index_obj, index_data_obj) in schema.get_iot_indexes()? {
+            if tracked.objs.contains(&obj) {
+                debug!("Track index-organized table index: {} data_obj: {:?} of table obj: {}", index_obj, index_data_obj, obj);
+                tracked.insert(index_obj, index_data_obj);
+            }
+        }
+
+        info!("Filter matched {} objects", tracked.objs.len());
+        Ok(())
+    }
+
+    pub fn set_keystore(&self, keystore : Keystore) {
+        self.encryption.lock().unwrap().keystore = Some(keystore);
+    }
+
+    /// Unwraps table keys of encrypted columns with master keys from keystore and reads data files of encrypted tablespaces.
+    /// Dictionary is not queried when keystore is not configured.
+    pub fn load_encryption(&self) -> Result<()> {
+        let schema = self.schema.lock().unwrap();
+        let mut encryption = self.encryption.lock().unwrap();
+        if encryption.keystore.is_none() {
+            return Ok(());
+        }
+
+        for (obj, master_key_id, enc_alg, int_alg, wrapped_key) in schema.get_column_keys()? {
+            let master_key = match encryption.keystore.as_ref().and_then(|x| x.master_key(&master_key_id)) {
+                Some(master_key) => master_key,
+                None => {
+                    warn!("No master key: {} in keystore, changes of encrypted columns of table obj: {} can not be decrypted", master_key_id, obj);
+                    continue;
+                }
+            };
+
+            match ColumnKey::new(master_key, enc_alg, int_alg, &wrapped_key) {
+                Ok(key) => { encryption.column_keys.insert(obj, key); },
+                Err(err) => warn!("Can not unwrap key of encrypted columns of table obj: {}. Error: {}", obj, err),
+            }
+        }
+
+        encryption.encrypted_files = schema.get_encrypted_files()?.into_iter().collect();
+
+        info!("Encryption keys of tables: {} data files of encrypted tablespaces: {}", encryption.column_keys.len(), encryption.encrypted_files.len());
+        Ok(())
+    }
+
+    /// Decrypts value of encrypted column of table `obj`. Unknown key or damaged value stops replication,
+    /// so cipher text is never sent as value.
+    pub fn decrypt_column(&self, obj : u32, data : &[u8], has_salt : bool) -> Result<Vec<u8>> {
+        let encryption = self.encryption.lock().unwrap();
+
+        match encryption.column_keys.get(&obj) {
+            Some(key) => key.decrypt(data, has_salt)
+                .map_err(|err| olr_err!(Decryption, "Can not decrypt column of table obj: {}. Error: {}", obj, err)),
+            None => olr_err!(Decryption, "No key of encrypted columns of table obj: {}, add master key to keystore file", obj),
+        }
+    }
+
+    /// Change vectors of encrypted tablespace are encrypted as a whole, decryption of them is not supported,
+    /// so they are skipped. Warning is logged once for each data file.
+    pub fn is_file_encrypted(&self, afn : u32) -> bool {
+        let mut encryption = self.encryption.lock().unwrap();
+
+        match encryption.encrypted_files.get(&afn).copied() {
+            Some(ts) => {
+                if encryption.skipped_files.insert(afn) {
+                    warn!("Changes of encrypted tablespace: {} (file: {}) can not be decrypted and are skipped, tablespace encryption is not supported", ts, afn);
+                }
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Checks that update and delete events of filtered tables will contain key columns. Supplemental logging of
+    /// primary key is enough unless filter defines its own key columns, those need logging of all columns.
+    /// Problems fail startup unless supplemental log check is disabled, then they are only reported.
+    pub fn check_supplemental_log(&self) -> Result<()> {
+        let objects = self.schema_objects.lock().unwrap();
+        if objects.is_empty() {
+            return Ok(());
+        }
+
+        let schema = self.schema.lock().unwrap();
+        let (min, primary, all) = schema.get_database_supplemental_log()?;
+        info!("Database supplemental logging: minimal: {} primary key: {} all: {}", min, primary, all);
+
+        let mut problems = Vec::new();
+
+        if min == "NO" {
+            problems.push("minimal supplemental logging is disabled for database, run: ALTER DATABASE ADD SUPPLEMENTAL LOG DATA".to_string());
+        }
+
+        if all != "YES" {
+            let mut users_spare1 = HashMap::new();
+
+            for (obj, _, owner, name) in schema.get_user_tables()? {
+                let keys = match objects.iter().find(|x| x.matches(&owner, &name)) {
+                    Some(object) => object.keys().clone(),
+                    None => continue,
+                };
+
+                if !users_spare1.contains_key(&owner) {
+                    let spare1 = schema.get_user_spare1(&owner)?.unwrap_or(0);
+                    users_spare1.insert(owner.clone(), spare1);
+                }
+                let spare1 = users_spare1[&owner];
+
+                let groups = schema.get_supplemental_log_groups(obj)?;
+                let is_all = spare1 & constants::SYS_USER_SPARE1_SUPP_LOG_ALL != 0 || groups.contains(&constants::SYS_CDEF_TYPE_SUPP_LOG_ALL);
+                let is_primary = primary == "YES" || spare1 & constants::SYS_USER_SPARE1_SUPP_LOG_PRIMARY != 0 || groups.contains(&constants::SYS_CDEF_TYPE_SUPP_LOG_PRIMARY);
+
+                if is_all || (is_primary && keys.is_empty()) {
+                    continue;
+                }
+
+                let columns = if keys.is_empty() { "PRIMARY KEY" } else { "ALL" };
+                problems.push(format!("table {}.{} (obj: {}) has no supplemental logging of key columns, run: ALTER TABLE {}.{} ADD SUPPLEMENTAL LOG DATA ({}) COLUMNS",
+                    owner, name, obj, owner, name, columns));
+            }
+        }
+
+        if problems.is_empty() {
+            return Ok(());
+        }
+
+        if self.context_ptr.disable_checks & constants::DISABLE_CHECKS_SUPPLEMENTAL_LOG != 0 {
+            for problem in problems {
+                warn!("Update and delete events can miss key columns: {}", problem);
+            }
+            return Ok(());
+        }
+
+        olr_err!(SupplementalLog, "Update and delete events can miss key columns: {}. Set flag {} in 'disable-checks' to continue anyway",
+            problems.join("; "), constants::DISABLE_CHECKS_SUPPLEMENTAL_LOG)
+    }
+
+    /// Adds tables created after start of replication and new partitions of tracked tables to filter
+    pub fn track_ddl(&self, ddl : &OpCode1801) -> Result<()> {
+        match ddl.ddl_type {
+            opcode1801::DDL_TYPE_CREATE_TABLE => self.track_table(ddl),
+            opcode1801::DDL_TYPE_ALTER_TABLE => self.track_partitions(ddl),
+            _ => Ok(()),
+        }
+    }
+
+    fn track_table(&self, ddl : &OpCode1801) -> Result<()> {
+        let objects = self.schema_objects.lock().unwrap();
+        if !objects.iter().any(|x| x.matches_owner(&ddl.owner)) {
+            return Ok(());
+        }
+
+        if let Some((data_obj, name)) = self.schema.lock().unwrap().get_object_info(ddl.obj)? {
+            if objects.iter().any(|x| x.matches(&ddl.owner, &name)) {
+                info!("Track new table: {}.{} obj: {}", ddl.owner, name, ddl.obj);
+                self.tracked_objects.write().unwrap().insert(ddl.obj, data_obj);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// ALTER TABLE ... ADD or SPLIT PARTITION creates partitions, which are read from dictionary again
+    fn track_partitions(&self, ddl : &OpCode1801) -> Result<()> {
+        if !self.tracked_objects.read().unwrap().objs.contains(&ddl.obj) || !ddl.sql_text.to_uppercase().contains("PARTITION") {
+            return Ok(());
+        }
+
+        let mut schema = self.schema.lock().unwrap();
+        schema.load_partitions()?;
+
+        let mut tracked = self.tracked_objects.write().unwrap();
+        for (obj, partition_obj, partition_data_obj) in schema.get_table_partitions() {
+            if obj == ddl.obj && tracked.insert(partition_obj, partition_data_obj) {
+                info!("Track new partition: {} data_obj: {:?} of table obj: {}", partition_obj, partition_data_obj, obj);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns row condition of the first filter element which matches table
+    pub fn get_condition(&self, owner : &str, name : &str) -> Option<Arc<Condition>> {
+        let objects = self.schema_objects.lock().unwrap();
+        objects.iter().find(|x| x.matches(owner, name)).and_then(|x| x.condition())
+    }
+
+    /// Returns key columns from the first filter element which matches table, if they are set
+    pub fn get_keys(&self, owner : &str, name : &str) -> Option<Vec<String>> {
+        let objects = self.schema_objects.lock().unwrap();
+        objects.iter().find(|x| x.matches(owner, name)).map(|x| x.keys().clone()).filter(|x| !x.is_empty())
+    }
+
+    /// Without filter all objects are tracked. DDL has no DATAOBJ#, it is checked by OBJ# only.
+    pub fn is_object_tracked(&self, obj : u32, data_obj : Option<u32>) -> bool {
+        let tracked = self.tracked_objects.read().unwrap();
+        !tracked.is_filtered || tracked.objs.contains(&obj) || data_obj.is_some_and(|x| tracked.data_objs.contains(&x))
+    }
+
+    /// Stores position in redo and table definitions history, so restarted replication continues from the position
+    /// and decodes old redo with correct schema. `offset` is position in file of redo `sequence`.
+    /// Checkpoint is written when `interval-s` seconds passed or `interval-mb` of redo was parsed since the previous one.
+    pub fn checkpoint(&self, sequence : TypeSeq, offset : u64, scn : TypeScn, parsed_size : u64) -> Result<()> {
+        let scn : u64 = scn.into();
+        let mut state = self.checkpoint_state.lock().unwrap();
+
+        state.bytes += parsed_size;
+        if state.time.elapsed() < Duration::from_secs(self.context_ptr.checkpoint_interval_s) 
+                && state.bytes < self.context_ptr.checkpoint_interval_mb * 1024 * 1024 {
+            return Ok(());
+        }
+
+        state.count += 1;
+        state.time = Instant::now();
+        state.bytes = 0;
+
+        // Dictionary is read without lock of schema, so parsers are not blocked by queries
+        if self.context_ptr.schema_force_interval > 0 && state.count.is_multiple_of(self.context_ptr.schema_force_interval) {
+            let snapshot = {
+                let schema = self.schema.lock().unwrap();
+                schema.detached().map(|x| (x, schema.snapshot_objects(scn)))
+            };
+
+            if let Some((dictionary, obj_ids)) = snapshot {
+                let tables = dictionary.read_tables(obj_ids, scn)?;
+                self.schema.lock().unwrap().apply_snapshot(scn, tables);
+            }
+        }
+
+        let schema = self.schema.lock().unwrap();
+
+        let directory_path = PathBuf::from(&self.context_ptr.state_path);
+        if let Err(err) = std::fs::create_dir_all(&directory_path) {
+            return olr_err!(CreateDir, "Can not create directory: {:?}. Error: {}", directory_path, err);
+        }
+
+        let file_path = directory_path.join(format!("{}-chkpt-{}.json", self.source_name, sequence));
+        let file = File::create(&file_path)
+            .map_err(|err| olr_err!(FileWriting, "Can not create checkpoint file: {:?}. Error: {}", file_path, err))?;
+
+        let checkpoint = Checkpoint { position : CheckpointPosition { sequence, offset, scn }, tables : schema.tables().clone() };
+        serde_json::to_writer(file, &checkpoint)
+            .map_err(|err| olr_err!(FileSerialization, "Can not write checkpoint file: {:?}. Error: {}", file_path, err))?;
+
+        debug!("Checkpoint sequence: {} offset: {} scn: {}", sequence, offset, scn);
+
+        let mut sequences = self.checkpoint_sequences()?;
+        while sequences.len() as u64 > self.context_ptr.checkpoint_keep {
+            let old_path = directory_path.join(format!("{}-chkpt-{}.json", self.source_name, sequences.remove(0)));
+            if let Err(err) = std::fs::remove_file(&old_path) {
+                warn!("Can not remove checkpoint file: {:?}. Error: {}", old_path, err);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Restores position in redo and table definitions history from the latest checkpoint
+    pub fn load_checkpoint(&self) -> Result<()> {
+        let sequence = match self.checkpoint_sequences()?.pop() {
+            Some(x) => x,
+            None => return Ok(()),
+        };
+
+        let file_path = PathBuf::from(&self.context_ptr.state_path).join(format!("{}-chkpt-{}.json", self.source_name, sequence));
+        let file = File::open(&file_path)
+            .map_err(|err| olr_err!(FileReading, "Can not open checkpoint file: {:?}. Error: {}", file_path, err))?;
+
+        let checkpoint : Checkpoint = serde_json::from_reader(BufReader::new(file))
+            .map_err(|err| olr_err!(FileDeserialization, "Can not read checkpoint file: {:?}. Error: {}", file_path, err))?;
+
+        let position = checkpoint.position;
+        info!("Load checkpoint sequence: {} offset: {} scn: {} tables: {}", position.sequence, position.offset, position.scn, checkpoint.tables.len());
+        self.schema.lock().unwrap().set_tables(checkpoint.tables);
+        *self.checkpoint_position.lock().unwrap() = Some(position);
+        Ok(())
+    }
+
+    /// Position of loaded checkpoint, replication continues after it
+    pub fn checkpoint_position(&self) -> Option<CheckpointPosition> {
+        *self.checkpoint_position.lock().unwrap()
+    }
+
+    fn checkpoint_sequences(&self) -> Result<Vec<TypeSeq>> {
+        let directory_path = PathBuf::from(&self.context_ptr.state_path);
+        if !directory_path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let prefix = format!("{}-chkpt-", self.source_name);
+        let directory = std::fs::read_dir(&directory_path)
+            .map_err(|err| olr_err!(MissingDir, "Can not read directory: {:?}. Error: {}", directory_path, err))?;
+
+        let mut sequences : Vec<TypeSeq> = directory
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| entry.file_name().to_str()
+                .and_then(|name| name.strip_prefix(&prefix))
+                .and_then(|name| name.strip_suffix(".json"))
+                .and_then(|name| name.parse::<TypeSeq>().ok()))
+            .collect();
+
+        sequences.sort();
+        Ok(sequences)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashMap, sync::Arc};
+
+    use super::CheckpointPosition;
+
+    use crate::{builder::formats, common::{constants, errors::Result, testing}, ctx::{Ctx, Dump}, oradefs::{oracle_schema::OracleSchemaResource, oracle_table::OracleTable}, parser::opcodes::{opcode1801, VectorData}};
+
+    fn table(owner : &str, name : &str, obj : u32, data_obj : u32, partition : Option<&str>) -> Arc<OracleTable> {
+        let mut table = OracleTable::new(owner.to_string(), name.to_string(), vec![testing::column(1, "ID", constants::SYS_COL_TYPE_NUMBER)], vec![]);
+        table.set_obj(obj);
+        table.set_data_obj(Some(data_obj));
+        table.set_partition(partition.map(|x| x.to_string()));
+        Arc::new(table)
+    }
+
+    #[test]
+    fn test_regexp_filter() -> Result<()> {
+        let metadata_ptr = testing::metadata(testing::context());
+
+        let tables = HashMap::from([
+            (100, table("SCOTT", "T1", 100, 101, None)),
+            (102, table("SCOTT", "T1", 100, 103, Some("P1"))),
+            (110, table("SCOTT", "T22", 110, 111, None)),
+            (120, table("SCOTT", "EMP", 120, 121, None)),
+            (130, table("HR", "T1", 130, 131, None)),
+        ]);
+        metadata_ptr.get_schema().set_resource(OracleSchemaResource::FromTables(tables));
+
+        // Without filter all objects are tracked
+        assert!(metadata_ptr.is_object_tracked(120, Some(121)));
+
+        drop(metadata_ptr.add_object("SCOTT".to_string(), "T[0-9]+".to_string(), 0)?);
+        metadata_ptr.resolve_filter()?;
+
+        assert!(metadata_ptr.is_object_tracked(100, Some(101)));
+        assert!(metadata_ptr.is_object_tracked(110, None));
+        assert!(metadata_ptr.is_object_tracked(102, Some(103)));
+        assert!(!metadata_ptr.is_object_tracked(120, Some(121)));
+        assert!(!metadata_ptr.is_object_tracked(130, Some(131)));
+
+        // OBJ# of tracked table is not DATAOBJ# of tracked segment
+        assert!(!metadata_ptr.is_object_tracked(999, Some(100)));
+        assert!(metadata_ptr.is_object_tracked(999, Some(101)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_objects_after_start() -> Result<()> {
+        let context_ptr = testing::context();
+        let metadata_ptr = testing::metadata(context_ptr.clone());
+        let (builder_ptr, _) = testing::builder(context_ptr.clone(), metadata_ptr.clone(), formats::MESSAGE_FORMAT_DEFAULT);
+        let mut parser = testing::parser(context_ptr, builder_ptr, metadata_ptr.clone());
+
+        let mut tables = HashMap::from([(100, table("SCOTT", "T1", 100, 101, None))]);
+        metadata_ptr.get_schema().set_resource(OracleSchemaResource::FromTables(tables.clone()));
+        drop(metadata_ptr.add_object("SCOTT".to_string(), "T[0-9]+".to_string(), 0)?);
+        metadata_ptr.resolve_filter()?;
+        assert!(!metadata_ptr.is_object_tracked(104, Some(105)));
+
+        // Partition and table are created in dictionary after start
+        tables.insert(104, table("SCOTT", "T1", 100, 105, Some("P2")));
+        tables.insert(140, table("SCOTT", "T2", 140, 141, None));
+        tables.insert(150, table("SCOTT", "EMP", 150, 151, None));
+        metadata_ptr.get_schema().set_resource(OracleSchemaResource::FromTables(tables));
+
+        let mut track = |ddl_type : u16, sql_text : &str, obj : u32| -> Result<()> {
+            let data = testing::ddl_vector_bytes(ddl_type, sql_text, obj);
+            match testing::parse_vector(&mut parser, &data).data() {
+                VectorData::OpCode1801(ddl) => metadata_ptr.track_ddl(ddl),
+                data => panic!("Unexpected vector: {}", data),
+            }
+        };
+
+        // Other change of table does not read partitions
+        track(opcode1801::DDL_TYPE_ALTER_TABLE, "alter table T1 add (NAME varchar2(10))", 100)?;
+        assert!(!metadata_ptr.is_object_tracked(104, Some(105)));
+
+        track(opcode1801::DDL_TYPE_ALTER_TABLE, "alter table T1 add partition P2 values less than (200)", 100)?;
+        assert!(metadata_ptr.is_object_tracked(104, Some(105)));
+        assert!(metadata_ptr.is_object_tracked(999, Some(105)));
+
+        track(opcode1801::DDL_TYPE_CREATE_TABLE, "create table T2 (ID number)", 140)?;
+        track(opcode1801::DDL_TYPE_CREATE_TABLE, "create table EMP (ID number)", 150)?;
+        assert!(metadata_ptr.is_object_tracked(140, Some(141)));
+        assert!(!metadata_ptr.is_object_tracked(150, Some(151)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_checkpoint_position() -> Result<()> {
+        let state_path = std::env::temp_dir().join(format!("olr-checkpoint-{}", std::process::id()));
+        let context_ptr = Arc::new(Ctx::new(Dump::default(), 0, 0, 0, 0, state_path.to_string_lossy().to_string(), 600, 1, 2, 0, 16, 64, 4)?);
+
+        let metadata_ptr = testing::metadata(context_ptr.clone());
+        metadata_ptr.get_schema().set_resource(OracleSchemaResource::FromTables(HashMap::from([(100, table("SCOTT", "T1", 100, 101, None))])));
+        assert!(metadata_ptr.get_schema().get_table(100, 1000)?.is_some());
+
+        // Interval is not reached, nothing is written
+        metadata_ptr.checkpoint(10, 4096, 1000.into(), 1024)?;
+        metadata_ptr.checkpoint(11, 8192, 1500.into(), 1024 * 1024)?;
+
+        let restored_ptr = testing::metadata(context_ptr);
+        restored_ptr.get_schema().set_resource(OracleSchemaResource::FromTables(HashMap::new()));
+        restored_ptr.load_checkpoint()?;
+        std::fs::remove_dir_all(&state_path).unwrap();
+
+        assert_eq!(restored_ptr.checkpoint_position(), Some(CheckpointPosition { sequence : 11, offset : 8192, scn : 1500 }));
+        assert!(restored_ptr.get_schema().get_table(100, 2000)?.is_some());
+        assert!(restored_ptr.get_schema().get_table(110, 2000)?.is_none());
+        Ok(())
+    }
 }
@@ -3,14 +3,17 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use log::trace;
 use log::info;
+use log::warn;
 
 use crate::builder;
 use crate::common::constants;
 use crate::common::errors::{Result, OLRErrorCode::*};
 use crate::common::thread::spawn;
+use crate::common::tde::Keystore;
 use crate::common::types;
 use crate::common::types::TypeScn;
 use crate::common::types::TypeSeq;
+use crate::common::types::TypeXid;
 use crate::ctx::Ctx;
 use crate::ctx::Dump;
 use crate::locales::Locales;
@@ -149,7 +152,7 @@ impl OracleLogicalReplicator {
             self.check_config_fields(&source_json, ["alias", "memory", "name", "reader", "flags", "skip-rollback", "state", "debug",
                                                     "transaction-max-mb", "metrics", "format", "redo-read-sleep-us", "arch-read-sleep-us",
                                                     "arch-read-tries", "redo-verify-delay-us", "refresh-interval-us", "arch",
-                                                    "filter"])?;
+                                                    "filter", "stream-uncommitted"])?;
 
             let alias = self.get_json_field_s(&source_json, "alias")?.expect("Field 'alias' must be defined for source");
 
@@ -196,7 +199,8 @@ impl OracleLogicalReplicator {
 
             self.check_config_fields(&reader_json, ["disable-checks", "start-scn", "start-seq", "start-time-rel", "start-time",
                                                     "con-id", "type", "redo-copy-path", "db-timezone", "host-timezone", "log-timezone",
-                                                    "user", "password", "server", "redo-log", "path-mapping", "log-archive-format"])?;
+                                                    "user", "password", "server", "redo-log", "path-mapping", "log-archive-format",
+                                                    "keystore"])?;
 
             let flags = self.get_json_field_u64(source_json, "flags")?.unwrap_or(0);
             let disable_checks = self.get_json_field_u64(reader_json, "disable-checks")?.unwrap_or(0);
@@ -205,8 +209,14 @@ impl OracleLogicalReplicator {
                 return olr_err!(NotValidField, "Field 'flags' ({}) expected: one of {{0 .. 524287}}", flags);
             }
 
-            if disable_checks > 15 {
-                return olr_err!(NotValidField, "Field 'disable-checks' ({}) expected: one of {{0 .. 15}}", flags);
+            if disable_checks > 63 {
+                return olr_err!(NotValidField, "Field 'disable-checks' ({}) expected: one of {{0 .. 63}}", disable_checks);
+            }
+
+            let stream_uncommitted = self.get_json_field_u64(source_json, "stream-uncommitted")?.unwrap_or(0);
+
+            if stream_uncommitted > 1 {
+                return olr_err!(NotValidField, "Field 'stream-uncommitted' ({}) expected: one of {{0, 1}}", stream_uncommitted);
             }
 
             let start_scn: TypeScn = self.get_json_field_u64(&reader_json, "start-scn")?.unwrap_or_default().into();
@@ -227,7 +237,7 @@ impl OracleLogicalReplicator {
                 }
             }
 
-            let mut _state_path: String = "checkpoint".to_string();
+            let mut state_path: String = "checkpoint".to_string();
             let mut checkpoint_interval_s: u64 = 600;
             let mut checkpoint_interval_mb: u64 = 500;
             let mut checkpoint_keep: u64 = 100;
@@ -238,7 +248,7 @@ impl OracleLogicalReplicator {
                 self.check_config_fields(&state_json, ["path", "interval-s", "interval-mb", "keep-checkpoints",
                                                        "schema-force-interval"])?;
 
-                _state_path = self.get_json_field_s(&state_json, "path")?.unwrap_or("checkpoint".into());
+                state_path = self.get_json_field_s(&state_json, "path")?.unwrap_or("checkpoint".into());
                 checkpoint_interval_s = self.get_json_field_u64(&state_json, "interval-s")?.unwrap_or(600);
                 checkpoint_interval_mb = self.get_json_field_u64(&state_json, "interval-mb")?.unwrap_or(500);
                 checkpoint_keep = self.get_json_field_u64(&state_json, "keep-checkpoints")?.unwrap_or(100);
@@ -250,7 +260,7 @@ impl OracleLogicalReplicator {
             // Context init
             let context_ptr = Arc::new(Ctx::new(
                 dump, log_level, trace, flags, disable_checks, 
-                checkpoint_interval_s, checkpoint_interval_mb, checkpoint_keep,
+                state_path, checkpoint_interval_s, checkpoint_interval_mb, checkpoint_keep,
                 schema_force_interval, memory_min_mb, memory_max_mb, read_buffer_max
             )?);
             
@@ -261,6 +271,10 @@ impl OracleLogicalReplicator {
                                         start_sequence, start_time, start_time_rel)
             );
 
+            if let Some(keystore_path) = self.get_json_field_s(reader_json, "keystore")? {
+                metadata_ptr.set_keystore(Keystore::from_file(&PathBuf::from(keystore_path))?);
+            }
+
             // Format
             let format_json = self.get_json_field_o(&source_json, "format")?.expect("Field 'format' must be defined");
 
@@ -307,6 +321,10 @@ impl OracleLogicalReplicator {
                 return olr_err!(NotValidField, "Field 'message' ({}) expected: BEGIN/COMMIT flag is unset ({}/{}) together with FULL mode ({})", message_format,
                                 builder::formats::MESSAGE_FORMAT_SKIP_BEGIN, builder::formats::MESSAGE_FORMAT_SKIP_COMMIT, builder::formats::MESSAGE_FORMAT_FULL)
             }
+            if (message_format & builder::formats::MESSAGE_FORMAT_FULL) != 0 && stream_uncommitted != 0 {
+                return olr_err!(NotValidField, "Field 'message' ({}) expected: FULL mode ({}) is unset when 'stream-uncommitted' is set", message_format,
+                                builder::formats::MESSAGE_FORMAT_FULL)
+            }
             if rid_format > 1 {
                 return olr_err!(NotValidField, "Field 'rid' ({}) expected: one of {{0, 1}}", rid_format)
             }
@@ -328,14 +346,14 @@ impl OracleLogicalReplicator {
             if scn_format > 3 {
                 return olr_err!(NotValidField, "Field 'scn' ({}) expected: one of {{0 .. 3}}", scn_format)
             }
-            if scn_all > 1 {
-                return olr_err!(NotValidField, "Field 'scn-all' ({}) expected: one of {{0, 1}}", scn_all)
+            if scn_all > 3 {
+                return olr_err!(NotValidField, "Field 'scn-all' ({}) expected: one of {{0 .. 3}}", scn_all)
             }
             if unknown_format > 1 {
                 return olr_err!(NotValidField, "Field 'unknown' ({}) expected: one of {{0, 1}}", unknown_format)
             }
-            if schema_format > 7 {
-                return olr_err!(NotValidField, "Field 'schema' ({}) expected: one of {{0 .. 7}}", schema_format)
+            if schema_format > 15 {
+                return olr_err!(NotValidField, "Field 'schema' ({}) expected: one of {{0 .. 15}}", schema_format)
             }
             if column_format > 2 {
                 return olr_err!(NotValidField, "Field 'column' ({}) expected: one of {{0 .. 2}}", column_format)
@@ -346,7 +364,8 @@ impl OracleLogicalReplicator {
             
             let builder_ptr = Arc::new(builder::JsonBuilder::new(context_ptr.clone(), locales_ptr.clone(), metadata_ptr.clone(), db_format, attributes_format,
                 interval_dts_format, interval_ytm_format, message_format, rid_format, xid_format, timestamp_format, timestamp_tz_format, 
-                timestamp_all, char_format, scn_format, scn_all, unknown_format, schema_format, column_format, unknown_type)?);
+                timestamp_all, char_format, scn_format, scn_all, unknown_format, schema_format, column_format, unknown_type,
+                Box::new(builder::writer::FileWriter::new(&PathBuf::from("out.txt"))?))?);
 
             let reader_type = self.get_json_field_s(&reader_json, "type")?.expect("Field 'type' must be defined");
             let log_archive_format = self.get_json_field_s(reader_json, "log-archive-format")?.unwrap_or("o1_mf_%t_%s_%h_.arc".into());
@@ -367,6 +386,7 @@ impl OracleLogicalReplicator {
                                 ArchiveDiggerOffline::new(
                                     context_ptr.clone(), 
                                     builder_ptr.clone(), 
+                                    metadata_ptr.clone(), 
                                     log_archive_format, 
                                     "".into(), 
                                     "".into(), 
@@ -383,6 +403,7 @@ impl OracleLogicalReplicator {
                                 ArchiveDiggerOffline::new(
                                     context_ptr.clone(), 
                                     builder_ptr.clone(), 
+                                    metadata_ptr.clone(), 
                                     log_archive_format, 
                                     "".into(), 
                                     "".into(), 
@@ -394,7 +415,7 @@ impl OracleLogicalReplicator {
                     };
 
                     let replicator = OnlineReplicator::new(context_ptr.clone(), builder_ptr.clone(), metadata_ptr.clone(), archive_digger,
-                                                        alias, source_name, user, password, server);
+                                                        alias, source_name, user, password, server, stream_uncommitted != 0);
                     replicator
                 },
                 _ => std::unimplemented!()
@@ -402,7 +423,34 @@ impl OracleLogicalReplicator {
 
             if let Some(filter_json) = self.get_json_field_o(&source_json, "filter")? {
 
-                self.check_config_fields(filter_json, ["table", "skip-xid", "dump-xid"])?;
+                self.check_config_fields(filter_json, ["table", "skip-xid", "dump-xid", "skip-user"])?;
+
+                if let Some(skip_xid_json) = self.get_json_field_a(filter_json, "skip-xid")? {
+                    for xid_json in skip_xid_json {
+                        match xid_json.as_str() {
+                            Some(xid) => metadata_ptr.add_skip_xid(xid.parse::<TypeXid>()?),
+                            None => return olr_err!(NotValidField, "Field 'skip-xid' expected: array of strings"),
+                        }
+                    }
+                }
+
+                if let Some(dump_xid_json) = self.get_json_field_a(filter_json, "dump-xid")? {
+                    for xid_json in dump_xid_json {
+                        match xid_json.as_str() {
+                            Some(xid) => metadata_ptr.add_dump_xid(xid.parse::<TypeXid>()?),
+                            None => return olr_err!(NotValidField, "Field 'dump-xid' expected: array of strings"),
+                        }
+                    }
+                }
+
+                if let Some(skip_user_json) = self.get_json_field_a(filter_json, "skip-user")? {
+                    for user_json in skip_user_json {
+                        match user_json.as_str() {
+                            Some(user) => metadata_ptr.add_skip_user(user.to_uppercase()),
+                            None => return olr_err!(NotValidField, "Field 'skip-user' expected: array of strings"),
+                        }
+                    }
+                }
 
                 if let Some(table_array_json) = self.get_json_field_a(&filter_json, "table")? {
                     
@@ -412,20 +460,26 @@ impl OracleLogicalReplicator {
                         let owner = self.get_json_field_s(&table_element_json, "owner")?.expect("Field 'owner' must be defined");
                         let table = self.get_json_field_s(&table_element_json, "table")?.expect("Field 'table' must be defined");
                         metadata_ptr.add_user(owner.clone());
-                        let mut guard = metadata_ptr.add_object(owner, table, 0);
+                        let mut guard = metadata_ptr.add_object(owner, table, 0)?;
                         let element = guard.last_mut().unwrap();
 
                         if let Some(keys_string) = self.get_json_field_s(&table_element_json, "key")? {
                             let columns : Vec<&str> = keys_string.split(',')
-                                                                .filter(|x| (*x)
-                                                                .trim_matches([' ', '\n', '\t'])
-                                                                .is_empty())
+                                                                .map(|x| x.trim_matches([' ', '\n', '\t']))
+                                                                .filter(|x| !x.is_empty())
                                                                 .collect();
                             
                             for key in columns {
-                                element.add_key(key.to_string());
+                                if key.as_bytes().iter().any(u8::is_ascii_lowercase) {
+                                    warn!("In table parameter Key: {} not all chars are uppercase. Try force rename.", key);
+                                }
+                                element.add_key(key.to_ascii_uppercase());
                             }
                         }
+
+                        if let Some(condition) = self.get_json_field_s(&table_element_json, "condition")? {
+                            element.set_condition(builder::condition::Condition::parse(condition.as_str())?);
+                        }
                     }
                 }
             }
@@ -1,4 +1,9 @@
-use crate::common::constants;
+use std::sync::Arc;
+
+use regex::Regex;
+
+use crate::{builder::condition::Condition, common::{constants, errors::Result}, olr_err};
+use crate::common::OLRErrorCode::*;
 
 #[derive(Debug)]
 pub struct DataBaseObject {
@@ -6,17 +11,40 @@ pub struct DataBaseObject {
     name    : String,
     options : u8,
     keys    : Vec<String>,
+    condition : Option<Arc<Condition>>,
+
+    schema_regexp : Regex,
+    name_regexp   : Regex,
 }
 
 impl DataBaseObject {
-    pub fn new(schema : String, name : String, options : u8) -> Self {
-        Self {schema, name, options, keys : Vec::new()}
+    pub fn new(schema : String, name : String, options : u8) -> Result<Self> {
+        let schema_regexp = Self::compile(&schema)?;
+        let name_regexp = Self::compile(&name)?;
+        Ok(Self {schema, name, options, keys : Vec::new(), condition : None, schema_regexp, name_regexp})
+    }
+
+    fn compile(pattern : &String) -> Result<Regex> {
+        Regex::new(format!("^(?:{})$", pattern).as_str())
+            .map_err(|err| olr_err!(NotValidField, "Not valid regexp: {}. Error: {}", pattern, err))
     }
 
     pub fn add_key(&mut self, key : String) {
         self.keys.push(key);
     }
 
+    pub fn keys(&self) -> &Vec<String> {
+        &self.keys
+    }
+
+    pub fn set_condition(&mut self, condition : Condition) {
+        self.condition = Some(Arc::new(condition));
+    }
+
+    pub fn condition(&self) -> Option<Arc<Condition>> {
+        self.condition.clone()
+    }
+
     pub fn schema(&self) -> &String {
         &self.schema
     }
@@ -28,4 +56,12 @@ impl DataBaseObject {
     pub fn is_system(&self) -> bool {
         self.options & constants::OPTIONS_SYSTEM_TABLE != 0
     } 
+
+    pub fn matches_owner(&self, owner : &str) -> bool {
+        self.schema_regexp.is_match(owner)
+    }
+
+    pub fn matches(&self, owner : &str, name : &str) -> bool {
+        self.matches_owner(owner) && self.name_regexp.is_match(name)
+    }
 }
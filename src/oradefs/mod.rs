@@ -4,4 +4,8 @@ pub mod oracle_schema;
 pub mod sys_obj;
 pub mod sys_user;
 pub mod sys_tab;
+pub mod sys_tabpart;
+pub mod sys_tabcompart;
+pub mod sys_tabsubpart;
 pub mod oracle_table;
+pub mod table_history;
@@ -1,18 +1,22 @@
-use std::{collections::HashMap, fs::OpenOptions, io::Write, sync::Arc};
+use std::{collections::{HashMap, HashSet}, fs::OpenOptions, io::Write, sync::Arc};
 
-use log::info;
+use log::{info, warn};
 use oracle::{sql_type::ToSql, Connection, ErrorKind, Statement};
 use serde::{ser::SerializeStruct, Serialize};
 use serde_json;
-use crate::{common::errors::Result, olr_err};
+use crate::{common::{constants, errors::Result}, olr_err};
 
-use super::{db_object::DataBaseObject, oracle_table::OracleTable, sys_obj::SysObjTable, sys_tab::SysTabTable, sys_user::SysUserTable};
+use super::{db_object::DataBaseObject, oracle_table::{OracleColumn, OracleTable}, sys_obj::SysObjTable, sys_tab::SysTabTable, sys_tabcompart::SysTabComPartTable, sys_tabpart::SysTabPartTable, sys_tabsubpart::SysTabSubPartTable, sys_user::SysUserTable, table_history::TableHistory};
 use crate::common::OLRErrorCode::*;
 
+pub type UserTableRow = (u32, Option<u32>, String, String);
+
 #[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
 pub enum OracleSchemaResource {
-    FromConnection (Connection),
+    FromConnection (Arc<Connection>),
     FromJson (String),
+    FromTables (HashMap<u32, Arc<OracleTable>>), // Current definitions of tables by OBJ#, without database
 }
 
 impl Default for OracleSchemaResource {
@@ -28,18 +32,26 @@ pub struct OracleSchema {
     sys_user_table : SysUserTable,
     sys_obj_table : SysObjTable,
     sys_tab_table : SysTabTable,
+    sys_tabpart_table : SysTabPartTable,
+    sys_tabcompart_table : SysTabComPartTable,
+    sys_tabsubpart_table : SysTabSubPartTable,
 
-    tables : HashMap<u32, Option<Arc<OracleTable>>>,
+    tables : HashMap<u32, TableHistory>,
+    checked_segments : HashSet<(u32, u32)>, // (OBJ#, DATAOBJ#) of segments which caused reload of definition
 }
 
 impl Serialize for OracleSchema {
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
         where
             S: serde::Serializer {
-        let mut st = serializer.serialize_struct("oracle_schema", 3)?;
+        let mut st = serializer.serialize_struct("oracle_schema", 7)?;
         st.serialize_field("sys_user", &self.sys_user_table)?;
         st.serialize_field("sys_obj", &self.sys_obj_table)?;
-        st.serialize_field("sys_user", &self.sys_user_table)?;
+        st.serialize_field("sys_tab", &self.sys_tab_table)?;
+        st.serialize_field("sys_tabpart", &self.sys_tabpart_table)?;
+        st.serialize_field("sys_tabcompart", &self.sys_tabcompart_table)?;
+        st.serialize_field("sys_tabsubpart", &self.sys_tabsubpart_table)?;
+        st.serialize_field("tables", &self.tables)?;
         st.end()
     }
 }
@@ -51,7 +63,11 @@ impl OracleSchema {
             sys_user_table : Default::default(),
             sys_obj_table : Default::default(),
             sys_tab_table : Default::default(),
+            sys_tabpart_table : Default::default(),
+            sys_tabcompart_table : Default::default(),
+            sys_tabsubpart_table : Default::default(),
             tables : Default::default(),
+            checked_segments : Default::default(),
         }
     }
 
@@ -59,38 +75,266 @@ impl OracleSchema {
         self.schema_resource = resource;
     }
 
-    pub fn get_table(&mut self, obj_id : u32) -> Result<Option<Arc<OracleTable>>> {
-        if let Some(x) = self.tables.get(&obj_id) {
-            return Ok(x.clone());
+    /// Returns definition of table which was valid at `scn`
+    pub fn get_table(&mut self, obj_id : u32, scn : u64) -> Result<Option<Arc<OracleTable>>> {
+        if let Some(version) = self.tables.get(&obj_id).and_then(|x| x.get(scn)) {
+            return Ok(version.table.clone());
         }
 
-        match self.schema_resource {
+        let table = match self.schema_resource {
             OracleSchemaResource::FromJson(_) => std::unimplemented!(),
+            OracleSchemaResource::FromTables(ref tables) => tables.get(&obj_id).cloned(),
             OracleSchemaResource::FromConnection(_) => {
-                self.create_table_from_connection(obj_id)?;
+                self.create_table_from_connection(obj_id, scn)?
+            }
+        };
+
+        self.tables.entry(obj_id).or_default().insert(scn, table.clone());
+        Ok(table)
+    }
+
+    /// Returns definition of table for change of segment `data_obj`. Other DATAOBJ# in definition means that
+    /// segment was replaced by TRUNCATE or MOVE after definition was loaded, then definition is reloaded at `scn`.
+    pub fn get_table_for_segment(&mut self, obj_id : u32, data_obj : u32, scn : u64) -> Result<Option<Arc<OracleTable>>> {
+        let table = self.get_table(obj_id, scn)?;
+
+        let is_changed = table.as_ref().and_then(|x| x.data_obj()).is_some_and(|x| x != data_obj);
+        if !is_changed || !self.checked_segments.insert((obj_id, data_obj)) {
+            return Ok(table);
+        }
+
+        info!("Segment of table with obj_id: {} changed to data_obj: {} at scn: {}", obj_id, data_obj, scn);
+        if self.tables.get_mut(&obj_id).is_some_and(|x| x.close(scn)) {
+            return self.get_table(obj_id, scn);
+        }
+        Ok(table)
+    }
+
+    /// Closes current definition of table at `scn` of DDL, so it will be reloaded from dictionary on next access
+    pub fn invalidate_table(&mut self, obj_id : u32, scn : u64) {
+        if let Some(history) = self.tables.get_mut(&obj_id) {
+            if history.close(scn) {
+                info!("Invalidate table definition with obj_id: {} at scn: {}", obj_id, scn);
             }
         }
+    }
+
+    /// OBJ# of tables with definition which is open at `scn`, they are reread by snapshot
+    pub fn snapshot_objects(&self, scn : u64) -> Vec<u32> {
+        self.tables
+            .iter()
+            .filter(|(_, history)| history.latest().is_some_and(|x| x.start_scn <= scn && x.is_open()))
+            .map(|(obj_id, _)| *obj_id)
+            .collect()
+    }
+
+    /// Schema with the same connection and without loaded data. It reads dictionary when
+    /// the shared schema is not locked, partitions are read by queries.
+    pub fn detached(&self) -> Option<Self> {
+        match self.schema_resource {
+            OracleSchemaResource::FromConnection(ref connection) => Some(Self::new(OracleSchemaResource::FromConnection(connection.clone()))),
+            OracleSchemaResource::FromJson(_) | OracleSchemaResource::FromTables(_) => None,
+        }
+    }
+
+    /// Reads definitions of `obj_ids` at `scn` from dictionary
+    pub fn read_tables(&self, obj_ids : Vec<u32>, scn : u64) -> Result<Vec<(u32, Option<Arc<OracleTable>>)>> {
+        obj_ids.into_iter()
+            .map(|obj_id| self.create_table_from_connection(obj_id, scn).map(|table| (obj_id, table)))
+            .collect()
+    }
+
+    /// Starts new versions of tables which definitions were changed without DDL in redo
+    pub fn apply_snapshot(&mut self, scn : u64, tables : Vec<(u32, Option<Arc<OracleTable>>)>) {
+        for (obj_id, table) in tables {
+            let history = match self.tables.get_mut(&obj_id) {
+                Some(history) => history,
+                None => continue,
+            };
+
+            if history.get(scn).is_some_and(|x| x.table != table) && history.close(scn) {
+                info!("Snapshot found changed definition of table with obj_id: {} at scn: {}", obj_id, scn);
+                history.insert(scn, table);
+            }
+        }
+    }
+
+    pub fn tables(&self) -> &HashMap<u32, TableHistory> {
+        &self.tables
+    }
 
-        Ok(self.tables.get(&obj_id).unwrap().clone())
+    pub fn set_tables(&mut self, tables : HashMap<u32, TableHistory>) {
+        self.tables = tables;
     }
 
-    fn create_table_from_connection(&mut self, obj_id : u32) -> Result<()> {
+    fn create_table_from_connection(&self, obj_id : u32, scn : u64) -> Result<Option<Arc<OracleTable>>> {
         if let OracleSchemaResource::FromConnection(ref connection) = self.schema_resource {
-            let mut stmt: Statement = Self::get_statement(&connection, GET_SYS_OBJ_BY_OBJ)?;
+            // Dictionary as of redo scn, current dictionary if flashback is not available
+            let mut as_of_scn = true;
+            let object = match Self::get_statement(connection, GET_SYS_OBJ_BY_OBJ_AS_OF_SCN)?.query_row_as::<(u32, Option<u32>, u32, String, u16, u64)>(&[&obj_id, &scn]) {
+                Err(err) if !matches!(err.kind(), ErrorKind::NoDataFound | ErrorKind::NullValue) => {
+                    warn!("Can not read dictionary as of scn: {}. Error: {}. Use current dictionary for obj_id: {}", scn, err, obj_id);
+                    as_of_scn = false;
+                    Self::get_statement(connection, GET_SYS_OBJ_BY_OBJ)?.query_row_as::<(u32, Option<u32>, u32, String, u16, u64)>(&[&obj_id])
+                },
+                result => result,
+            };
+
+            let (_, data_obj, owner_id, name, obj_type, _) = match object {
+                Ok(res) => res,
+                Err(err) if matches!(err.kind(), ErrorKind::NoDataFound | ErrorKind::NullValue) => return Ok(None),
+                Err(err) => return olr_err!(OracleQuery, "Problems with statement \"{}\" executing: {} with param: {}", GET_SYS_OBJ_BY_OBJ, err, obj_id),
+            };
+
+            // Rows of index-organized table are changed in its primary key index
+            if obj_type == constants::SYS_OBJ_TYPE_INDEX {
+                return self.create_iot_from_connection(connection, obj_id, data_obj, scn);
+            }
 
-            let object = stmt.query_row_as::<(u32, u32, u32, String, u16, u64)>(&[&obj_id]);
+            if obj_type == constants::SYS_OBJ_TYPE_TABLE_PARTITION || obj_type == constants::SYS_OBJ_TYPE_TABLE_SUBPARTITION {
+                return self.create_partition_from_connection(connection, obj_id, data_obj, obj_type, scn);
+            }
 
-            match object {
-                Ok(res) => {
-                    self.tables.insert(obj_id, Some(Arc::new(OracleTable::new(res.3))));
-                }
-                Err(err) if err.kind() == ErrorKind::NullValue => {
-                    self.tables.insert(obj_id, None);
+            let owner = Self::get_statement(connection, GET_SYS_USER_BY_USER)?.query_row_as::<String>(&[&owner_id])
+                .map_err(|err| olr_err!(OracleQuery, "Problems with statement \"{}\" executing: {} with param: {}", GET_SYS_USER_BY_USER, err, owner_id))?;
+
+            let params: (&'static str, &[&dyn ToSql]) = match as_of_scn {
+                true  => (GET_SYS_COL_BY_OBJ_AS_OF_SCN, &[&obj_id, &scn]),
+                false => (GET_SYS_COL_BY_OBJ, &[&obj_id]),
+            };
+
+            let mut stmt = Self::get_statement(connection, params.0)?;
+            let columns = stmt.query_as::<(u16, u16, String, u16, u32, Option<i32>, Option<i32>, u32, Option<u8>, u64)>(params.1)
+                .map_err(|err| olr_err!(OracleQuery, "Problems with statement \"{}\" executing: {} with param: {}", params.0, err, obj_id))?
+                .filter_map(|x| x.ok())
+                .map(|(col_no, seg_col_no, name, type_no, length, precision, scale, null, charset_form, property)| OracleColumn {
+                    col_no, seg_col_no, name, type_no, length, precision, scale, nullable : null == 0, charset_form : charset_form.unwrap_or(0), property
+                })
+                .collect();
+
+            let params: (&'static str, &[&dyn ToSql]) = match as_of_scn {
+                true  => (GET_PRIMARY_KEY_BY_OBJ_AS_OF_SCN, &[&obj_id, &scn]),
+                false => (GET_PRIMARY_KEY_BY_OBJ, &[&obj_id]),
+            };
+
+            let mut stmt = Self::get_statement(connection, params.0)?;
+            let primary_key = stmt.query_as::<String>(params.1)
+                .map_err(|err| olr_err!(OracleQuery, "Problems with statement \"{}\" executing: {} with param: {}", params.0, err, obj_id))?
+                .filter_map(|x| x.ok())
+                .collect();
+
+            let mut table = OracleTable::new(owner, name, columns, primary_key);
+            table.set_obj(obj_id);
+            table.set_data_obj(data_obj);
+            Ok(Some(Arc::new(table)))
+        } else {
+            olr_err!(SchemaReading, "Resource type is not FromConnection")
+        }
+    }
+
+    /// Table definition for OBJ# of primary key index of index-organized table, None for other indexes
+    fn create_iot_from_connection(&self, connection : &Connection, obj_id : u32, data_obj : Option<u32>, scn : u64) -> Result<Option<Arc<OracleTable>>> {
+        let (table_obj, property) = match Self::get_statement(connection, GET_SYS_IND_TABLE_BY_OBJ)?.query_row_as::<(u32, u64)>(&[&obj_id, &constants::SYS_IND_TYPE_IOT_TOP]) {
+            Ok(res) => res,
+            Err(err) if matches!(err.kind(), ErrorKind::NoDataFound | ErrorKind::NullValue) => return Ok(None),
+            Err(err) => return olr_err!(OracleQuery, "Problems with statement \"{}\" executing: {} with param: {}", GET_SYS_IND_TABLE_BY_OBJ, err, obj_id),
+        };
+
+        if property & (constants::SYS_TAB_PROPERTY_IOT | constants::SYS_TAB_PROPERTY_IOT2) == 0 {
+            return Ok(None);
+        }
+
+        let table = match self.create_table_from_connection(table_obj, scn)? {
+            Some(table) => table,
+            None => return Ok(None),
+        };
+
+        let mut table = Arc::unwrap_or_clone(table);
+        table.set_iot();
+        table.set_data_obj(data_obj);
+        Ok(Some(Arc::new(table)))
+    }
+
+    /// Definition of parent table for OBJ# of partition or subpartition, name of partition is added to it
+    fn create_partition_from_connection(&self, connection : &Connection, obj_id : u32, data_obj : Option<u32>, obj_type : u16, scn : u64) -> Result<Option<Arc<OracleTable>>> {
+        let parent = match obj_type {
+            constants::SYS_OBJ_TYPE_TABLE_SUBPARTITION => {
+                match self.get_partition_parent(connection, &self.sys_tabsubpart_table.get_parent(obj_id), GET_SYS_TABSUBPART_BY_OBJ, obj_id)? {
+                    Some(partition) => self.get_partition_parent(connection, &self.sys_tabcompart_table.get_parent(partition), GET_SYS_TABCOMPART_BY_OBJ, partition)?,
+                    None => None,
                 }
-                Err(err) => {
-                    return olr_err!(OracleQuery, "Problems with statement \"{}\" executing: {} with param: {}", GET_SYS_OBJ_BY_OBJ, err, obj_id);
+            },
+            _ => self.get_partition_parent(connection, &self.sys_tabpart_table.get_parent(obj_id), GET_SYS_TABPART_BY_OBJ, obj_id)?,
+        };
+
+        let parent = match parent {
+            Some(parent) => parent,
+            None => {
+                warn!("No parent table of partition with obj_id: {}", obj_id);
+                return Ok(None);
+            }
+        };
+
+        let table = match self.create_table_from_connection(parent, scn)? {
+            Some(table) => table,
+            None => return Ok(None),
+        };
+
+        let partition = Self::get_statement(connection, GET_SYS_OBJ_SUBNAME_BY_OBJ)?.query_row_as::<Option<String>>(&[&obj_id])
+            .map_err(|err| olr_err!(OracleQuery, "Problems with statement \"{}\" executing: {} with param: {}", GET_SYS_OBJ_SUBNAME_BY_OBJ, err, obj_id))?;
+
+        let mut table = Arc::unwrap_or_clone(table);
+        table.set_partition(partition);
+        table.set_data_obj(data_obj);
+        Ok(Some(Arc::new(table)))
+    }
+
+    /// Parent from loaded dictionary table, partitions created after load are read by `query`
+    fn get_partition_parent(&self, connection : &Connection, loaded : &Option<u32>, query : &'static str, obj_id : u32) -> Result<Option<u32>> {
+        if loaded.is_some() {
+            return Ok(*loaded);
+        }
+
+        match Self::get_statement(connection, query)?.query_row_as::<u32>(&[&obj_id]) {
+            Ok(parent) => Ok(Some(parent)),
+            Err(err) if matches!(err.kind(), ErrorKind::NoDataFound | ErrorKind::NullValue) => Ok(None),
+            Err(err) => olr_err!(OracleQuery, "Problems with statement \"{}\" executing: {} with param: {}", query, err, obj_id),
+        }
+    }
+
+    /// Reads all partitions and subpartitions of tables from SYS.TABPART$, SYS.TABCOMPART$ and SYS.TABSUBPART$
+    pub fn load_partitions(&mut self) -> Result<()> {
+        // Definition of partition refers to OBJ# of its table
+        if let OracleSchemaResource::FromTables(ref tables) = self.schema_resource {
+            for (obj, table) in tables.iter().filter(|(_, x)| x.partition().is_some()) {
+                if let Some(bo) = table.obj() {
+                    self.sys_tabpart_table.add_row(*obj, table.data_obj(), bo);
                 }
             }
+            return Ok(());
+        }
+
+        if let OracleSchemaResource::FromConnection(ref connection) = self.schema_resource {
+            let mut stmt = Self::get_statement(connection, GET_SYS_TABPART)?;
+            let rows = stmt.query_as::<(u32, Option<u32>, u32)>(&[])
+                .map_err(|err| olr_err!(OracleQuery, "Problems with statement \"{}\" executing: {}", GET_SYS_TABPART, err))?;
+            for (obj, data_obj, bo) in rows.filter_map(|x| x.ok()) {
+                self.sys_tabpart_table.add_row(obj, data_obj, bo);
+            }
+
+            let mut stmt = Self::get_statement(connection, GET_SYS_TABCOMPART)?;
+            let rows = stmt.query_as::<(u32, Option<u32>, u32)>(&[])
+                .map_err(|err| olr_err!(OracleQuery, "Problems with statement \"{}\" executing: {}", GET_SYS_TABCOMPART, err))?;
+            for (obj, data_obj, bo) in rows.filter_map(|x| x.ok()) {
+                self.sys_tabcompart_table.add_row(obj, data_obj, bo);
+            }
+
+            let mut stmt = Self::get_statement(connection, GET_SYS_TABSUBPART)?;
+            let rows = stmt.query_as::<(u32, Option<u32>, u32)>(&[])
+                .map_err(|err| olr_err!(OracleQuery, "Problems with statement \"{}\" executing: {}", GET_SYS_TABSUBPART, err))?;
+            for (obj, data_obj, pobj) in rows.filter_map(|x| x.ok()) {
+                self.sys_tabsubpart_table.add_row(obj, data_obj, pobj);
+            }
 
             Ok(())
         } else {
@@ -98,6 +342,188 @@ impl OracleSchema {
         }
     }
 
+    /// Returns (table OBJ#, partition OBJ#, partition DATAOBJ#) of loaded partitions and subpartitions
+    pub fn get_table_partitions(&self) -> Vec<(u32, u32, Option<u32>)> {
+        let partitions = self.sys_tabpart_table.rows()
+            .map(|(obj, data_obj, bo)| (bo, obj, data_obj));
+
+        let subpartitions = self.sys_tabsubpart_table.rows()
+            .filter_map(|(obj, data_obj, pobj)| self.sys_tabcompart_table.get_parent(pobj).map(|bo| (bo, obj, data_obj)));
+
+        partitions.chain(subpartitions).collect()
+    }
+
+    /// Returns (table OBJ#, index OBJ#, index DATAOBJ#) of primary key indexes of all index-organized tables
+    pub fn get_iot_indexes(&self) -> Result<Vec<(u32, u32, Option<u32>)>> {
+        if let OracleSchemaResource::FromTables(_) = self.schema_resource {
+            return Ok(Vec::new());
+        }
+
+        if let OracleSchemaResource::FromConnection(ref connection) = self.schema_resource {
+            let mut stmt = Self::get_statement(connection, GET_SYS_IOT_INDEXES)?;
+
+            let rows = stmt.query_as::<(u32, u32, Option<u32>, u64)>(&[&constants::SYS_IND_TYPE_IOT_TOP])
+                .map_err(|err| olr_err!(OracleQuery, "Problems with statement \"{}\" executing: {}", GET_SYS_IOT_INDEXES, err))?;
+
+            Ok(rows.filter_map(|x| x.ok())
+                .filter(|x| x.3 & (constants::SYS_TAB_PROPERTY_IOT | constants::SYS_TAB_PROPERTY_IOT2) != 0)
+                .map(|(obj, index_obj, index_data_obj, _)| (obj, index_obj, index_data_obj))
+                .collect())
+        } else {
+            olr_err!(SchemaReading, "Resource type is not FromConnection")
+        }
+    }
+
+    /// Returns (OBJ#, DATAOBJ#, owner, name) of all tables in dictionary
+    pub fn get_user_tables(&self) -> Result<Vec<UserTableRow>> {
+        if let OracleSchemaResource::FromTables(ref tables) = self.schema_resource {
+            return Ok(tables.iter()
+                .filter(|(_, x)| x.partition().is_none())
+                .map(|(obj, x)| (*obj, x.data_obj(), x.owner().clone(), x.name().clone()))
+                .collect());
+        }
+
+        if let OracleSchemaResource::FromConnection(ref connection) = self.schema_resource {
+            let mut stmt = Self::get_statement(connection, GET_SYS_OBJ_TABLES)?;
+
+            let rows = stmt.query_as::<UserTableRow>(&[])
+                .map_err(|err| olr_err!(OracleQuery, "Problems with statement \"{}\" executing: {}", GET_SYS_OBJ_TABLES, err))?;
+
+            Ok(rows.filter_map(|x| x.ok()).collect())
+        } else {
+            olr_err!(SchemaReading, "Resource type is not FromConnection")
+        }
+    }
+
+    /// Returns (table OBJ#, LOB segment OBJ#, LOB segment DATAOBJ#) of all LOB columns in dictionary
+    pub fn get_lob_segments(&self) -> Result<Vec<(u32, u32, Option<u32>)>> {
+        if let OracleSchemaResource::FromTables(_) = self.schema_resource {
+            return Ok(Vec::new());
+        }
+
+        if let OracleSchemaResource::FromConnection(ref connection) = self.schema_resource {
+            let mut stmt = Self::get_statement(connection, GET_SYS_LOB_SEGMENTS)?;
+
+            let rows = stmt.query_as::<(u32, u32, Option<u32>)>(&[])
+                .map_err(|err| olr_err!(OracleQuery, "Problems with statement \"{}\" executing: {}", GET_SYS_LOB_SEGMENTS, err))?;
+
+            Ok(rows.filter_map(|x| x.ok()).collect())
+        } else {
+            olr_err!(SchemaReading, "Resource type is not FromConnection")
+        }
+    }
+
+    /// Returns (DATAOBJ#, name) of object from current dictionary
+    pub fn get_object_info(&self, obj_id : u32) -> Result<Option<(Option<u32>, String)>> {
+        if let OracleSchemaResource::FromTables(ref tables) = self.schema_resource {
+            return Ok(tables.get(&obj_id).map(|x| (x.data_obj(), x.name().clone())));
+        }
+
+        if let OracleSchemaResource::FromConnection(ref connection) = self.schema_resource {
+            match Self::get_statement(connection, GET_SYS_OBJ_BY_OBJ)?.query_row_as::<(u32, Option<u32>, u32, String, u16, u64)>(&[&obj_id]) {
+                Ok(res) => Ok(Some((res.1, res.3))),
+                Err(err) if matches!(err.kind(), ErrorKind::NoDataFound | ErrorKind::NullValue) => Ok(None),
+                Err(err) => olr_err!(OracleQuery, "Problems with statement \"{}\" executing: {} with param: {}", GET_SYS_OBJ_BY_OBJ, err, obj_id),
+            }
+        } else {
+            olr_err!(SchemaReading, "Resource type is not FromConnection")
+        }
+    }
+
+    /// Switches session to container `con_id`, so dictionary of pluggable database is read. Returns name of container.
+    pub fn switch_container(&self, con_id : u32) -> Result<String> {
+        if let OracleSchemaResource::FromConnection(ref connection) = self.schema_resource {
+            let name = match Self::get_statement(connection, GET_CONTAINER_NAME_BY_CON_ID)?.query_row_as::<String>(&[&con_id]) {
+                Ok(name) => name,
+                Err(err) if matches!(err.kind(), ErrorKind::NoDataFound | ErrorKind::NullValue) => return olr_err!(SchemaReading, "No container with con_id: {}", con_id),
+                Err(err) => return olr_err!(OracleQuery, "Problems with statement \"{}\" executing: {} with param: {}", GET_CONTAINER_NAME_BY_CON_ID, err, con_id),
+            };
+
+            let sql = format!("ALTER SESSION SET CONTAINER = \"{}\"", name);
+            connection.execute(&sql, &[])
+                .map_err(|err| olr_err!(OracleQuery, "Problems with statement \"{}\" executing: {}", sql, err))?;
+
+            Ok(name)
+        } else {
+            olr_err!(SchemaReading, "Resource type is not FromConnection")
+        }
+    }
+
+    /// Returns (table OBJ#, master key id, encryption algorithm, integrity algorithm, wrapped table key) from SYS.ENC$
+    pub fn get_column_keys(&self) -> Result<Vec<(u32, String, u16, u16, Vec<u8>)>> {
+        if let OracleSchemaResource::FromConnection(ref connection) = self.schema_resource {
+            let mut stmt = Self::get_statement(connection, GET_SYS_ENC)?;
+
+            let rows = stmt.query_as::<(u32, String, u16, u16, Vec<u8>)>(&[])
+                .map_err(|err| olr_err!(OracleQuery, "Problems with statement \"{}\" executing: {}", GET_SYS_ENC, err))?;
+
+            Ok(rows.filter_map(|x| x.ok()).collect())
+        } else {
+            olr_err!(SchemaReading, "Resource type is not FromConnection")
+        }
+    }
+
+    /// Returns (absolute file number, TS#) of data files of encrypted tablespaces
+    pub fn get_encrypted_files(&self) -> Result<Vec<(u32, u32)>> {
+        if let OracleSchemaResource::FromConnection(ref connection) = self.schema_resource {
+            let mut stmt = Self::get_statement(connection, GET_ENCRYPTED_DATAFILES)?;
+
+            let rows = stmt.query_as::<(u32, u32)>(&[])
+                .map_err(|err| olr_err!(OracleQuery, "Problems with statement \"{}\" executing: {}", GET_ENCRYPTED_DATAFILES, err))?;
+
+            Ok(rows.filter_map(|x| x.ok()).collect())
+        } else {
+            olr_err!(SchemaReading, "Resource type is not FromConnection")
+        }
+    }
+
+    /// Returns SUPPLEMENTAL_LOG_DATA_MIN, SUPPLEMENTAL_LOG_DATA_PK and SUPPLEMENTAL_LOG_DATA_ALL of database
+    pub fn get_database_supplemental_log(&self) -> Result<(String, String, String)> {
+        if let OracleSchemaResource::FromConnection(ref connection) = self.schema_resource {
+            Self::get_statement(connection, GET_DATABASE_SUPPLEMENTAL_LOG)?.query_row_as::<(String, String, String)>(&[])
+                .map_err(|err| olr_err!(OracleQuery, "Problems with statement \"{}\" executing: {}", GET_DATABASE_SUPPLEMENTAL_LOG, err))
+        } else {
+            olr_err!(SchemaReading, "Resource type is not FromConnection")
+        }
+    }
+
+    /// Returns ids of character set and national character set of database
+    pub fn get_database_charsets(&self) -> Result<(u64, u64)> {
+        if let OracleSchemaResource::FromConnection(ref connection) = self.schema_resource {
+            Self::get_statement(connection, GET_DATABASE_CHARSETS)?.query_row_as::<(u64, u64)>(&[])
+                .map_err(|err| olr_err!(OracleQuery, "Problems with statement \"{}\" executing: {}", GET_DATABASE_CHARSETS, err))
+        } else {
+            olr_err!(SchemaReading, "Resource type is not FromConnection")
+        }
+    }
+
+    /// Returns SPARE1 flags of user
+    pub fn get_user_spare1(&self, name : &str) -> Result<Option<u64>> {
+        if let OracleSchemaResource::FromConnection(ref connection) = self.schema_resource {
+            match Self::get_statement(connection, GET_SYS_USER_SPARE1_BY_NAME)?.query_row_as::<Option<u64>>(&[&name]) {
+                Ok(res) => Ok(res),
+                Err(err) if matches!(err.kind(), ErrorKind::NoDataFound | ErrorKind::NullValue) => Ok(None),
+                Err(err) => olr_err!(OracleQuery, "Problems with statement \"{}\" executing: {} with param: {}", GET_SYS_USER_SPARE1_BY_NAME, err, name),
+            }
+        } else {
+            olr_err!(SchemaReading, "Resource type is not FromConnection")
+        }
+    }
+
+    /// Returns types of supplemental log groups (CDEF$ TYPE# 14 and 17) of table
+    pub fn get_supplemental_log_groups(&self, obj_id : u32) -> Result<Vec<u16>> {
+        if let OracleSchemaResource::FromConnection(ref connection) = self.schema_resource {
+            let mut stmt = Self::get_statement(connection, GET_SYS_CDEF_SUPPLEMENTAL_LOG_BY_OBJ)?;
+
+            let rows = stmt.query_as::<u16>(&[&obj_id])
+                .map_err(|err| olr_err!(OracleQuery, "Problems with statement \"{}\" executing: {} with param: {}", GET_SYS_CDEF_SUPPLEMENTAL_LOG_BY_OBJ, err, obj_id))?;
+
+            Ok(rows.filter_map(|x| x.ok()).collect())
+        } else {
+            olr_err!(SchemaReading, "Resource type is not FromConnection")
+        }
+    }
+
     pub fn from_connection(connection : Connection, schema_objects : &Vec<DataBaseObject>) -> Result<Self> {
         info!("Initialize Oracle Schema");
 
@@ -175,12 +601,155 @@ const GET_SYS_USER_BY_NAME : &'static str = "
     WHERE REGEXP_LIKE(NAME, :1)
 ";
 
+const GET_SYS_USER_SPARE1_BY_NAME : &'static str = "
+    SELECT SPARE1 
+    FROM SYS.USER$
+    WHERE NAME = :1
+";
+
+const GET_DATABASE_SUPPLEMENTAL_LOG : &'static str = "
+    SELECT SUPPLEMENTAL_LOG_DATA_MIN, SUPPLEMENTAL_LOG_DATA_PK, SUPPLEMENTAL_LOG_DATA_ALL 
+    FROM SYS.V_$DATABASE
+";
+
+const GET_DATABASE_CHARSETS : &'static str = "
+    SELECT NLS_CHARSET_ID(C.VALUE), NLS_CHARSET_ID(N.VALUE) 
+    FROM SYS.NLS_DATABASE_PARAMETERS C, SYS.NLS_DATABASE_PARAMETERS N 
+    WHERE C.PARAMETER = 'NLS_CHARACTERSET' AND N.PARAMETER = 'NLS_NCHAR_CHARACTERSET'
+";
+
+const GET_CONTAINER_NAME_BY_CON_ID : &'static str = "
+    SELECT NAME 
+    FROM SYS.V_$CONTAINERS 
+    WHERE CON_ID = :1
+";
+
+const GET_SYS_ENC : &'static str = "
+    SELECT OBJ#, MKEYID, ENCALG, INTALG, COLKLC 
+    FROM SYS.ENC$
+";
+
+const GET_ENCRYPTED_DATAFILES : &'static str = "
+    SELECT D.FILE#, D.TS# 
+    FROM SYS.V_$DATAFILE D, SYS.V_$ENCRYPTED_TABLESPACES E 
+    WHERE D.TS# = E.TS#
+";
+
+const GET_SYS_CDEF_SUPPLEMENTAL_LOG_BY_OBJ : &'static str = "
+    SELECT TYPE# 
+    FROM SYS.CDEF$ 
+    WHERE OBJ# = :1 AND TYPE# IN (14, 17)
+";
+
+const GET_SYS_USER_BY_USER : &'static str = "
+    SELECT NAME 
+    FROM SYS.USER$
+    WHERE USER# = :1
+";
+
+const GET_SYS_COL_BY_OBJ : &'static str = "
+    SELECT COL#, SEGCOL#, NAME, TYPE#, LENGTH, PRECISION#, SCALE, NULL$, CHARSETFORM, PROPERTY 
+    FROM SYS.COL$ 
+    WHERE OBJ# = :1 AND SEGCOL# > 0
+    ORDER BY SEGCOL#
+";
+
+const GET_SYS_COL_BY_OBJ_AS_OF_SCN : &'static str = "
+    SELECT COL#, SEGCOL#, NAME, TYPE#, LENGTH, PRECISION#, SCALE, NULL$, CHARSETFORM, PROPERTY 
+    FROM SYS.COL$ AS OF SCN :2
+    WHERE OBJ# = :1 AND SEGCOL# > 0
+    ORDER BY SEGCOL#
+";
+
+const GET_PRIMARY_KEY_BY_OBJ : &'static str = "
+    SELECT C.NAME 
+    FROM SYS.CDEF$ D, SYS.CCOL$ L, SYS.COL$ C 
+    WHERE D.OBJ# = :1 AND D.TYPE# = 2 AND L.CON# = D.CON# AND C.OBJ# = L.OBJ# AND C.INTCOL# = L.INTCOL#
+    ORDER BY L.POS#
+";
+
+const GET_PRIMARY_KEY_BY_OBJ_AS_OF_SCN : &'static str = "
+    SELECT C.NAME 
+    FROM SYS.CDEF$ AS OF SCN :2 D, SYS.CCOL$ AS OF SCN :2 L, SYS.COL$ AS OF SCN :2 C 
+    WHERE D.OBJ# = :1 AND D.TYPE# = 2 AND L.CON# = D.CON# AND C.OBJ# = L.OBJ# AND C.INTCOL# = L.INTCOL#
+    ORDER BY L.POS#
+";
+
 const GET_SYS_OBJ_BY_OBJ : &'static str = "
     SELECT OBJ#, DATAOBJ#, OWNER#, NAME, TYPE#, FLAGS 
     FROM SYS.OBJ$ 
     WHERE OBJ# = :1
 ";
 
+const GET_SYS_OBJ_BY_OBJ_AS_OF_SCN : &'static str = "
+    SELECT OBJ#, DATAOBJ#, OWNER#, NAME, TYPE#, FLAGS 
+    FROM SYS.OBJ$ AS OF SCN :2
+    WHERE OBJ# = :1
+";
+
+const GET_SYS_OBJ_TABLES : &'static str = "
+    SELECT O.OBJ#, O.DATAOBJ#, U.NAME, O.NAME 
+    FROM SYS.OBJ$ O, SYS.USER$ U 
+    WHERE O.OWNER# = U.USER# AND O.TYPE# = 2
+";
+
+const GET_SYS_LOB_SEGMENTS : &'static str = "
+    SELECT L.OBJ#, L.LOBJ#, O.DATAOBJ# 
+    FROM SYS.LOB$ L, SYS.OBJ$ O 
+    WHERE O.OBJ# = L.LOBJ#
+";
+
+const GET_SYS_OBJ_SUBNAME_BY_OBJ : &'static str = "
+    SELECT SUBNAME 
+    FROM SYS.OBJ$ 
+    WHERE OBJ# = :1
+";
+
+const GET_SYS_TABPART : &'static str = "
+    SELECT OBJ#, DATAOBJ#, BO# 
+    FROM SYS.TABPART$
+";
+
+const GET_SYS_TABPART_BY_OBJ : &'static str = "
+    SELECT BO# 
+    FROM SYS.TABPART$ 
+    WHERE OBJ# = :1
+";
+
+const GET_SYS_TABCOMPART : &'static str = "
+    SELECT OBJ#, DATAOBJ#, BO# 
+    FROM SYS.TABCOMPART$
+";
+
+const GET_SYS_TABCOMPART_BY_OBJ : &'static str = "
+    SELECT BO# 
+    FROM SYS.TABCOMPART$ 
+    WHERE OBJ# = :1
+";
+
+const GET_SYS_TABSUBPART : &'static str = "
+    SELECT OBJ#, DATAOBJ#, POBJ# 
+    FROM SYS.TABSUBPART$
+";
+
+const GET_SYS_TABSUBPART_BY_OBJ : &'static str = "
+    SELECT POBJ# 
+    FROM SYS.TABSUBPART$ 
+    WHERE OBJ# = :1
+";
+
+const GET_SYS_IND_TABLE_BY_OBJ : &'static str = "
+    SELECT I.BO#, T.PROPERTY 
+    FROM SYS.IND$ I, SYS.TAB$ T 
+    WHERE I.OBJ# = :1 AND I.TYPE# = :2 AND T.OBJ# = I.BO#
+";
+
+const GET_SYS_IOT_INDEXES : &'static str = "
+    SELECT I.BO#, I.OBJ#, O.DATAOBJ#, T.PROPERTY 
+    FROM SYS.IND$ I, SYS.OBJ$ O, SYS.TAB$ T 
+    WHERE I.TYPE# = :1 AND O.OBJ# = I.OBJ# AND T.OBJ# = I.BO#
+";
+
 const GET_SYS_OBJ_BY_USER : &'static str = "
     SELECT OBJ#, DATAOBJ#, OWNER#, NAME, TYPE#, FLAGS 
     FROM SYS.OBJ$ 
@@ -197,4 +766,33 @@ const GET_SYS_TAB_BY_OBJ : &'static str = "
     SELECT OBJ#, DATAOBJ#, TS#, CLUCOLS, FLAGS, PROPERTY 
     FROM SYS.TAB$ 
     WHERE OBJ# = :1"
-;
\ No newline at end of file
+;
+
+#[cfg(test)]
+mod test {
+    use crate::{common::{constants, testing}, oradefs::oracle_table::OracleTable};
+
+    #[test]
+    fn test_table_for_segment() {
+        let metadata_ptr = testing::metadata(testing::context());
+        let table = || OracleTable::new("SCOTT".to_string(), "T1".to_string(), vec![testing::column(1, "ID", constants::SYS_COL_TYPE_NUMBER)], vec![]);
+        testing::set_tables(&metadata_ptr, vec![(100, 101, table())]);
+        testing::set_dictionary(&metadata_ptr, vec![(100, 102, table())]);
+
+        let mut schema = metadata_ptr.get_schema();
+        let data_obj = |schema : &mut super::OracleSchema, data_obj, scn| schema.get_table_for_segment(100, data_obj, scn).unwrap().and_then(|x| x.data_obj());
+
+        // Segment of loaded definition
+        assert_eq!(data_obj(&mut schema, 101, 10), Some(101));
+
+        // New segment after truncate, definition is reloaded at scn of change
+        assert_eq!(data_obj(&mut schema, 102, 50), Some(102));
+        assert_eq!(data_obj(&mut schema, 101, 20), Some(101));
+
+        // Dictionary does not know segment yet, definition is reloaded only once
+        assert_eq!(data_obj(&mut schema, 103, 60), Some(102));
+        assert_eq!(schema.tables[&100].latest().unwrap().start_scn, 60);
+        assert_eq!(data_obj(&mut schema, 103, 70), Some(102));
+        assert_eq!(schema.tables[&100].latest().unwrap().start_scn, 60);
+    }
+}
@@ -1,17 +1,145 @@
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+use crate::common::constants;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OracleColumn {
+    pub col_no     : u16,
+    pub seg_col_no : u16,
+    pub name       : String,
+    pub type_no    : u16,
+    pub length     : u32,
+    pub precision  : Option<i32>,
+    pub scale      : Option<i32>,
+    pub nullable   : bool,
+    #[serde(default)]
+    pub charset_form : u8,
+    #[serde(default)]
+    pub property : u64,
+}
+
+impl OracleColumn {
+    pub fn is_lob(&self) -> bool {
+        self.type_no == constants::SYS_COL_TYPE_CLOB || self.type_no == constants::SYS_COL_TYPE_BLOB
+    }
+
+    /// Column of transparent data encryption, value in redo is cipher text
+    pub fn is_encrypted(&self) -> bool {
+        self.property & constants::SYS_COL_PROPERTY_ENCRYPTED != 0
+    }
+
+    pub fn has_salt(&self) -> bool {
+        self.property & constants::SYS_COL_PROPERTY_NO_SALT == 0
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        match self.type_no {
+            1 => "VARCHAR2",
+            2 => "NUMBER",
+            8 => "LONG",
+            12 => "DATE",
+            23 => "RAW",
+            24 => "LONG RAW",
+            69 => "ROWID",
+            96 => "CHAR",
+            100 => "BINARY_FLOAT",
+            101 => "BINARY_DOUBLE",
+            112 if self.charset_form == constants::SYS_COL_CHARSET_FORM_NCHAR => "NCLOB",
+            112 => "CLOB",
+            113 => "BLOB",
+            180 => "TIMESTAMP",
+            181 => "TIMESTAMP WITH TIME ZONE",
+            182 => "INTERVAL YEAR TO MONTH",
+            183 => "INTERVAL DAY TO SECOND",
+            208 => "UROWID",
+            231 => "TIMESTAMP WITH LOCAL TIME ZONE",
+            _ => "UNKNOWN",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct OracleTable {
+    owner : String,
     name : String,
+    columns : Vec<OracleColumn>, // Sorted by seg_col_no
+    primary_key : Vec<String>,
+    #[serde(default)]
+    iot_columns : Vec<usize>, // Segment column index of each column of index leaf row, empty for heap table
+    #[serde(default)]
+    obj : Option<u32>, // OBJ# of table, parent table for partitions and index-organized tables
+    #[serde(default)]
+    data_obj : Option<u32>, // DATAOBJ# of segment when definition was loaded
+    #[serde(default)]
+    partition : Option<String>, // Name of partition for definition loaded by OBJ# of partition
 }
 
 impl OracleTable {
-    pub fn new(name : String) -> Self {
+    pub fn new(owner : String, name : String, columns : Vec<OracleColumn>, primary_key : Vec<String>) -> Self {
         Self {
-            name
+            owner, name, columns, primary_key, iot_columns : Vec::new(), obj : None, data_obj : None, partition : None
         }
     }
 
+    pub fn owner(&self) -> &String {
+        &self.owner
+    }
+
     pub fn name(&self) -> &String {
         &self.name
     }
-}
\ No newline at end of file
+
+    pub fn columns(&self) -> &Vec<OracleColumn> {
+        &self.columns
+    }
+
+    /// Names of primary key columns in constraint order
+    pub fn primary_key(&self) -> &Vec<String> {
+        &self.primary_key
+    }
+
+    pub fn obj(&self) -> Option<u32> {
+        self.obj
+    }
+
+    pub fn set_obj(&mut self, obj : u32) {
+        self.obj = Some(obj);
+    }
+
+    pub fn data_obj(&self) -> Option<u32> {
+        self.data_obj
+    }
+
+    pub fn set_data_obj(&mut self, data_obj : Option<u32>) {
+        self.data_obj = data_obj;
+    }
+
+    pub fn partition(&self) -> Option<&String> {
+        self.partition.as_ref()
+    }
+
+    pub fn set_partition(&mut self, partition : Option<String>) {
+        self.partition = partition;
+    }
+
+    /// Index-organized table keeps primary key columns in key of leaf row and other columns in key data
+    pub fn set_iot(&mut self) {
+        let key = self.primary_key.iter().filter_map(|name| self.columns.iter().find(|x| &x.name == name));
+        let rest = self.columns.iter().filter(|x| !self.primary_key.contains(&x.name));
+        self.iot_columns = key.chain(rest).map(|x| x.seg_col_no as usize - 1).collect();
+    }
+
+    pub fn is_iot(&self) -> bool {
+        !self.iot_columns.is_empty()
+    }
+
+    /// Segment column index of column at position `idx` of index leaf row
+    pub fn iot_column(&self, idx : usize) -> Option<usize> {
+        self.iot_columns.get(idx).cloned()
+    }
+
+    /// Column stored in row at position `idx` of redo
+    pub fn segment_column(&self, idx : usize) -> Option<&OracleColumn> {
+        self.columns.iter().find(|x| x.seg_col_no as usize == idx + 1)
+    }
+}
@@ -0,0 +1,33 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+/// Row of SYS.TABCOMPART$: composite partition, parent is table
+#[derive(Debug, Serialize)]
+pub struct SysTabComPart {
+    obj : u32,
+    data_obj : Option<u32>,
+    bo : u32,
+}
+
+impl SysTabComPart {
+    pub fn new(obj : u32, data_obj : Option<u32>, bo : u32) -> Self {
+        Self {obj, data_obj, bo}
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct SysTabComPartTable {
+    rows : HashMap<u32, SysTabComPart>,
+}
+
+impl SysTabComPartTable {
+    pub fn add_row(&mut self, obj : u32, data_obj : Option<u32>, bo : u32) {
+        let _ = self.rows.insert(obj, SysTabComPart::new(obj, data_obj, bo));
+    }
+
+    pub fn get_parent(&self, obj : u32) -> Option<u32> {
+        self.rows.get(&obj).map(|x| x.bo)
+    }
+
+}
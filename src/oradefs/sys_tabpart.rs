@@ -0,0 +1,37 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+/// Row of SYS.TABPART$: table partition, parent is table
+#[derive(Debug, Serialize)]
+pub struct SysTabPart {
+    obj : u32,
+    data_obj : Option<u32>,
+    bo : u32,
+}
+
+impl SysTabPart {
+    pub fn new(obj : u32, data_obj : Option<u32>, bo : u32) -> Self {
+        Self {obj, data_obj, bo}
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct SysTabPartTable {
+    rows : HashMap<u32, SysTabPart>,
+}
+
+impl SysTabPartTable {
+    pub fn add_row(&mut self, obj : u32, data_obj : Option<u32>, bo : u32) {
+        let _ = self.rows.insert(obj, SysTabPart::new(obj, data_obj, bo));
+    }
+
+    pub fn get_parent(&self, obj : u32) -> Option<u32> {
+        self.rows.get(&obj).map(|x| x.bo)
+    }
+
+    /// Returns (OBJ#, DATAOBJ#, parent OBJ#) of all rows
+    pub fn rows(&self) -> impl Iterator<Item = (u32, Option<u32>, u32)> + '_ {
+        self.rows.values().map(|x| (x.obj, x.data_obj, x.bo))
+    }
+}
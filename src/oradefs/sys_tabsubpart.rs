@@ -0,0 +1,37 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+/// Row of SYS.TABSUBPART$: table subpartition, parent is composite partition
+#[derive(Debug, Serialize)]
+pub struct SysTabSubPart {
+    obj : u32,
+    data_obj : Option<u32>,
+    pobj : u32,
+}
+
+impl SysTabSubPart {
+    pub fn new(obj : u32, data_obj : Option<u32>, pobj : u32) -> Self {
+        Self {obj, data_obj, pobj}
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct SysTabSubPartTable {
+    rows : HashMap<u32, SysTabSubPart>,
+}
+
+impl SysTabSubPartTable {
+    pub fn add_row(&mut self, obj : u32, data_obj : Option<u32>, pobj : u32) {
+        let _ = self.rows.insert(obj, SysTabSubPart::new(obj, data_obj, pobj));
+    }
+
+    pub fn get_parent(&self, obj : u32) -> Option<u32> {
+        self.rows.get(&obj).map(|x| x.pobj)
+    }
+
+    /// Returns (OBJ#, DATAOBJ#, parent OBJ#) of all rows
+    pub fn rows(&self) -> impl Iterator<Item = (u32, Option<u32>, u32)> + '_ {
+        self.rows.values().map(|x| (x.obj, x.data_obj, x.pobj))
+    }
+}
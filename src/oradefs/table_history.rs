@@ -0,0 +1,131 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use super::oracle_table::OracleTable;
+
+/// Definition of table which is valid for SCN range [start_scn; end_scn)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableVersion {
+    pub start_scn : u64,
+    pub end_scn : u64,
+    pub table : Option<Arc<OracleTable>>,
+}
+
+impl TableVersion {
+    pub fn contains(&self, scn : u64) -> bool {
+        self.start_scn <= scn && scn < self.end_scn
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.end_scn == u64::MAX
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct TableHistory {
+    versions : Vec<TableVersion>, // Sorted by start_scn, ranges do not overlap
+}
+
+impl TableHistory {
+    pub fn get(&self, scn : u64) -> Option<&TableVersion> {
+        let idx = self.versions.partition_point(|x| x.start_scn <= scn);
+
+        match idx {
+            0 => None,
+            idx => Some(&self.versions[idx - 1]).filter(|x| x.contains(scn)),
+        }
+    }
+
+    pub fn latest(&self) -> Option<&TableVersion> {
+        self.versions.last()
+    }
+
+    pub fn versions(&self) -> &Vec<TableVersion> {
+        &self.versions
+    }
+
+    /// Adds definition loaded for `scn`. It fills the whole gap between known versions around `scn`.
+    pub fn insert(&mut self, scn : u64, table : Option<Arc<OracleTable>>) {
+        let idx = self.versions.partition_point(|x| x.start_scn <= scn);
+
+        let start_scn = match idx {
+            0 => 0,
+            idx => self.versions[idx - 1].end_scn,
+        };
+        let end_scn = self.versions.get(idx).map_or(u64::MAX, |x| x.start_scn);
+
+        debug_assert!(start_scn <= scn && scn < end_scn, "Version for scn {} already exists", scn);
+
+        self.versions.insert(idx, TableVersion { start_scn, end_scn, table });
+    }
+
+    /// Closes version which contains `scn`, so the next access at `scn` or later loads a new definition.
+    /// Returns false if the change at `scn` is already known.
+    pub fn close(&mut self, scn : u64) -> bool {
+        let idx = self.versions.partition_point(|x| x.start_scn <= scn);
+
+        if idx == 0 {
+            return false;
+        }
+
+        let version = &mut self.versions[idx - 1];
+        if version.start_scn == scn || !version.contains(scn) {
+            return false;
+        }
+
+        version.end_scn = scn;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::oradefs::oracle_table::OracleTable;
+
+    use super::TableHistory;
+
+    fn table(name : &str) -> Option<Arc<OracleTable>> {
+        Some(Arc::new(OracleTable::new("OWNER".to_string(), name.to_string(), Vec::new(), Vec::new())))
+    }
+
+    #[test]
+    fn test_lazy_versions() {
+        let mut history = TableHistory::default();
+        assert!(history.get(100).is_none());
+
+        history.insert(100, table("T1"));
+        assert_eq!(history.get(0).unwrap().table.as_ref().unwrap().name(), "T1");
+        assert_eq!(history.get(u64::MAX - 1).unwrap().table.as_ref().unwrap().name(), "T1");
+
+        assert!(history.close(200));
+        assert!(!history.close(200));
+        assert!(history.get(150).is_some());
+        assert!(history.get(200).is_none());
+
+        history.insert(250, table("T2"));
+        assert_eq!(history.get(199).unwrap().table.as_ref().unwrap().name(), "T1");
+        assert_eq!(history.get(200).unwrap().table.as_ref().unwrap().name(), "T2");
+        assert!(history.latest().unwrap().is_open());
+    }
+
+    #[test]
+    fn test_gap_between_versions() {
+        let mut history = TableHistory::default();
+
+        history.insert(500, table("T1"));
+        history.close(300);
+        history.insert(1000, None);
+        assert_eq!(history.versions().len(), 2);
+        assert!(history.get(400).unwrap().table.is_none());
+
+        history.close(700);
+        history.insert(800, table("T2"));
+        assert_eq!(history.versions().len(), 3);
+        assert_eq!(history.get(100).unwrap().table.as_ref().unwrap().name(), "T1");
+        assert_eq!(history.get(700).unwrap().start_scn, 700);
+        assert_eq!(history.get(700).unwrap().end_scn, u64::MAX);
+    }
+}
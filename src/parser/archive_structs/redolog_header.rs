@@ -1,4 +1,4 @@
-use crate::common::types::{TypeScn, TypeTimestamp};
+use crate::common::{constants, types::{TypeScn, TypeTimestamp}};
 
 use super::block_header::BlockHeader;
 
@@ -48,3 +48,43 @@ pub struct RedoLogHeader {
     pub redo_log_key            : [u8; 16],
     pub redo_log_key_flag       : u16,
 }
+
+impl RedoLogHeader {
+    /// Release of redo format. Layouts of records and vectors are known only for these releases:
+    /// up to 12.2 release is defined by major and minor number, since 18 by major number.
+    pub fn release(&self) -> Option<&'static str> {
+        match self.oracle_version & 0xFFF00000 {
+            constants::REDO_VERSION_11_2 => return Some("11.2"),
+            constants::REDO_VERSION_12_1 => return Some("12.1"),
+            constants::REDO_VERSION_12_2 => return Some("12.2"),
+            _ => (),
+        }
+
+        match self.oracle_version & 0xFF000000 {
+            constants::REDO_VERSION_18_0 => Some("18"),
+            constants::REDO_VERSION_19_0 => Some("19"),
+            constants::REDO_VERSION_21_0 => Some("21"),
+            constants::REDO_VERSION_23_0 => Some("23"),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::RedoLogHeader;
+
+    #[test]
+    fn test_release() {
+        let release = |oracle_version| RedoLogHeader { oracle_version, ..Default::default() }.release();
+
+        assert_eq!(release(0x0B200400), Some("11.2"));
+        assert_eq!(release(0x0C100200), Some("12.1"));
+        assert_eq!(release(0x0C200100), Some("12.2"));
+        assert_eq!(release(0x13120000), Some("19"));
+        assert_eq!(release(0x17030000), Some("23"));
+        assert_eq!(release(0x0B100700), None);
+        assert_eq!(release(0x0C300000), None);
+        assert_eq!(release(0x14000000), None);
+    }
+}
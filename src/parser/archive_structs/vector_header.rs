@@ -32,6 +32,7 @@ impl std::fmt::Display for VectorHeader {
             (5, 11) => "Rollback DBA in transaction table entry",
             (5, 19) | (5, 20) => "Session info",
             (10, 2) => "Insert leaf row",
+            (10, 4) => "Mark leaf row deleted",
             (10, 8) => "Initialize new leaf block",
             (10, 18) => "Update keydata in row",
             (11, 2) => "Insert row piece",
@@ -0,0 +1,131 @@
+use crate::{common::{constants, errors::Result, types::TypeLobId}, olr_perr, parser::{byte_reader::ByteReader, parser_impl::Parser, record_reader::VectorReader}};
+
+use super::VectorField;
+
+const KDLI_CODE_NAMES : [&str; 18] = ["000", "info", "load common", "003", "load data", "zero", "fill", "lmap", "lmapx", "suplog",
+                                      "gmap", "fpload", "load lhb", "almap", "almapx", "load itree", "imap", "imapx"];
+
+/// One of LOB (kdli) structures of 26.x vectors
+#[derive(Default)]
+pub struct Kdli {
+    pub code : u8,
+    pub lob_id : Option<TypeLobId>,
+    pub page_no : Option<u32>,
+    pub has_data : bool, // Next field is data of LOB page
+}
+
+impl Kdli {
+    fn kdli_info(result : &mut Kdli, parser : &mut Parser, reader : &mut ByteReader, field_num : usize) -> Result<()> {
+        if reader.data().len() < 17 {
+            return olr_perr!("Size of field {} < 17", reader.data().len());
+        }
+
+        result.lob_id = TypeLobId::from_bytes(&reader.data()[1 ..]);
+        reader.skip_bytes(11);
+        let block = reader.read_u32()?;
+
+        if parser.can_dump(1) {
+            parser.write_dump(format_args!("\n[Change {}; KDLI info - {}] LOB ID: {} block: {}\n", field_num, reader.data().len(), result.lob_id.unwrap(), block))?;
+        }
+
+        Ok(())
+    }
+
+    fn kdli_load_common(_result : &mut Kdli, parser : &mut Parser, reader : &mut ByteReader, field_num : usize) -> Result<()> {
+        if reader.data().len() < 20 {
+            return olr_perr!("Size of field {} < 20", reader.data().len());
+        }
+
+        reader.skip_bytes(4);
+        let typ = reader.read_u8()?;
+        let flg0 = reader.read_u8()?;
+        let flg1 = reader.read_u8()?;
+        let flg2 = reader.read_u8()?;
+
+        if parser.can_dump(1) {
+            parser.write_dump(format_args!("\n[Change {}; KDLI load common - {}] type: {} flags: {} {} {}\n", field_num, reader.data().len(), typ, flg0, flg1, flg2))?;
+        }
+
+        Ok(())
+    }
+
+    fn kdli_load_data(result : &mut Kdli, parser : &mut Parser, reader : &mut ByteReader, field_num : usize) -> Result<()> {
+        if reader.data().len() < 56 {
+            return olr_perr!("Size of field {} < 56", reader.data().len());
+        }
+
+        result.lob_id = TypeLobId::from_bytes(&reader.data()[12 ..]);
+        reader.skip_bytes(24);
+        result.page_no = Some(reader.read_u32()?);
+        result.has_data = true;
+
+        if parser.can_dump(1) {
+            parser.write_dump(format_args!("\n[Change {}; KDLI load data - {}] LOB ID: {} page: {}\n", field_num, reader.data().len(), result.lob_id.unwrap(), result.page_no.unwrap()))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl VectorField for Kdli {
+    fn parse_from_reader(parser : &mut Parser, _vec_reader : &mut VectorReader, reader : &mut ByteReader, field_num : usize) -> Result<Self> {
+        if reader.data().is_empty() {
+            return olr_perr!("Empty kdli field");
+        }
+
+        let mut result = Kdli { code : reader.data()[0], ..Default::default() };
+
+        match result.code {
+            constants::KDLI_CODE_INFO => Self::kdli_info(&mut result, parser, reader, field_num)?,
+            constants::KDLI_CODE_LOAD_COMMON => Self::kdli_load_common(&mut result, parser, reader, field_num)?,
+            constants::KDLI_CODE_LOAD_DATA => Self::kdli_load_data(&mut result, parser, reader, field_num)?,
+            code => {
+                if parser.can_dump(1) {
+                    parser.write_dump(format_args!("\n[Change {}; KDLI {} - {}]\n", field_num, KDLI_CODE_NAMES.get(code as usize).unwrap_or(&"unknown"), reader.data().len()))?;
+                }
+            },
+        }
+
+        Ok(result)
+    }
+}
+
+/// Data of LOB page from 26.x vector
+#[derive(Debug, Default)]
+pub struct LobPage<'a> {
+    pub lob_id : Option<TypeLobId>,
+    pub page_no : u32,
+    pub data : Option<&'a [u8]>,
+}
+
+impl Kdli {
+    /// Parses remaining fields of vector as kdli structures, data field follows "load data" structure
+    pub fn parse_fields<'a>(parser : &mut Parser, vec_reader : &mut VectorReader<'a>, mut field_num : usize) -> Result<LobPage<'a>> {
+        let mut result = LobPage::default();
+
+        while let Some(mut field_reader) = vec_reader.next() {
+            let kdli = Kdli::parse_from_reader(parser, vec_reader, &mut field_reader, field_num)?;
+            field_num += 1;
+
+            if result.lob_id.is_none() {
+                result.lob_id = kdli.lob_id;
+            }
+
+            if kdli.has_data {
+                let data_reader = vec_reader.next()
+                    .ok_or(olr_perr!("Expected LOB data field after kdli load data"))?;
+
+                if parser.can_dump(2) {
+                    parser.write_dump(format_args!("\n[Change {}; LOB data - {}]\n{}", field_num, data_reader.data().len(), data_reader.to_hex_dump()))?;
+                }
+                field_num += 1;
+
+                result.lob_id = kdli.lob_id;
+                result.page_no = kdli.page_no.unwrap_or_default();
+                result.data = Some(data_reader.data());
+            }
+        }
+
+        Ok(result)
+    }
+}
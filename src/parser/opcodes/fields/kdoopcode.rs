@@ -1,4 +1,4 @@
-use crate::{common::{constants, errors::Result, types::TypeFb}, parser::{byte_reader::ByteReader, parser_impl::Parser, record_reader::VectorReader}};
+use crate::{common::{constants, errors::Result, types::TypeFb}, olr_perr, parser::{byte_reader::ByteReader, opcodes::RowColumn, parser_impl::Parser, record_reader::VectorReader}};
 
 use super::VectorField;
 
@@ -15,21 +15,137 @@ pub struct Kdoopcode {
     pub nulls_field : Option<usize>,
     pub nulls_offset : Option<usize>,
     pub slots_offset : Option<usize>,
+    pub nrid : Option<(u32, u16)>, // Next piece of chained row
 
     pub nrow : Option<u8>,
 }
 
+/// Row of multi-row insert (QMI)
+#[derive(Debug)]
+pub struct KdoRow<'a> {
+    pub slot : u16,
+    pub columns : Vec<RowColumn<'a>>,
+}
+
 impl Kdoopcode {
+    /// Reads rows of QMI from row sizes field and data field, `slots_reader` is KDO opcode field at slots offset
+    pub fn parse_rows<'a>(parser : &mut Parser, vec_reader : &mut VectorReader<'a>, slots_reader : &mut ByteReader, op : u8, nrow : u8) -> Result<Vec<KdoRow<'a>>> {
+        let mut sizes_reader = vec_reader.next()
+            .ok_or(olr_perr!("Expected row sizes field"))?;
+        let mut data_reader = vec_reader.next()
+            .ok_or(olr_perr!("Expected rows data field"))?;
+
+        let mut result = Vec::with_capacity(nrow as usize);
+        let mut row_pos = 0;
+        for _ in 0 .. nrow {
+            data_reader.set_cursor(row_pos)?;
+            let slot = slots_reader.read_u16()?;
+            let fb: TypeFb = data_reader.read_u8()?.into();
+            let lb = data_reader.read_u8()?;
+            let jcc = data_reader.read_u8()?;
+            let tl = sizes_reader.read_u16()?;
+
+            if parser.can_dump(1) {
+                parser.write_dump(format_args!("slot: {} FB: {} LB: {} TL: {} JCC: {}\n", slot, fb, lb, tl, jcc))?;
+            }
+
+            if op & constants::OP_ROWDEPENDENCIES != 0 {
+                if parser.version().unwrap() < constants::REDO_VERSION_12_2 {
+                    data_reader.skip_bytes(6);
+                } else {
+                    data_reader.skip_bytes(8);
+                }
+            }
+
+            let mut columns = Vec::with_capacity(jcc as usize);
+            for j in 0 .. jcc as usize {
+                let mut size: u16 = data_reader.read_u8()? as u16;
+                let is_null: bool = size == 0xFF;
+
+                if size == 0xFE {
+                    size = data_reader.read_u16()?;
+                }
+
+                let start = data_reader.cursor();
+                if !is_null && start + size as usize > data_reader.data().len() {
+                    return olr_perr!("Column {} of row in slot {} is out of data field: {} + {} > {}", j, slot, start, size, data_reader.data().len());
+                }
+
+                if parser.can_dump(1) {
+                    parser.dump_column(&data_reader.data()[start ..], j, size as usize, is_null)?;
+                }
+
+                if is_null {
+                    columns.push((j, None));
+                } else {
+                    columns.push((j, Some(&data_reader.data()[start .. start + size as usize])));
+                    data_reader.skip_bytes(size as usize);
+                }
+            }
+
+            result.push(KdoRow { slot, columns });
+            row_pos += tl as usize;
+        }
+
+        Ok(result)
+    }
+
+    /// Reads `cc` columns of URP packed in one field (flag KDOM2), numbers field keeps first column number,
+    /// columns go in sequence with length byte before each of them
+    pub fn parse_columns_vector<'a>(parser : &mut Parser, vec_reader : &mut VectorReader<'a>, cc : u8) -> Result<Vec<RowColumn<'a>>> {
+        let mut numbers_reader = vec_reader.next()
+            .ok_or(olr_perr!("Expected column numbers field"))?;
+        let mut data_reader = vec_reader.next()
+            .ok_or(olr_perr!("Expected columns vector field"))?;
+
+        let first_col_num = numbers_reader.read_u16()? as usize;
+        let mut columns = Vec::with_capacity(cc as usize);
+        for i in 0 .. cc as usize {
+            let mut size: u16 = data_reader.read_u8()? as u16;
+            let is_null: bool = size == 0xFF;
+
+            if size == 0xFE {
+                size = data_reader.read_u16()?;
+            }
+
+            let start = data_reader.cursor();
+            if !is_null && start + size as usize > data_reader.data().len() {
+                return olr_perr!("Column {} of vector is out of data field: {} + {} > {}", i, start, size, data_reader.data().len());
+            }
+
+            if parser.can_dump(1) {
+                parser.dump_column(&data_reader.data()[start ..], first_col_num + i, size as usize, is_null)?;
+            }
+
+            if is_null {
+                columns.push((first_col_num + i, None));
+            } else {
+                columns.push((first_col_num + i, Some(&data_reader.data()[start .. start + size as usize])));
+                data_reader.skip_bytes(size as usize);
+            }
+        }
+
+        Ok(columns)
+    }
+
     fn kdo_opcode_irp(result : &mut Kdoopcode, parser : &mut Parser, reader : &mut ByteReader, field_num : usize) -> Result<()> {
-        assert!(reader.data().len() >= 48, "Size of field {} < 48", reader.data().len());
+        if reader.data().len() < 48 {
+            return olr_perr!("Size of field {} < 48", reader.data().len());
+        }
 
         result.fb = Some(reader.read_u8()?.into());
         let lb = reader.read_u8()?;
         result.cc = Some(reader.read_u8()?);
         let cki = reader.read_u8()?;
 
+        let pos = reader.cursor();
+        if !result.fb.unwrap().is_last() {
+            reader.set_cursor(pos + 8)?;
+            result.nrid = Some((reader.read_u32()?, reader.read_u16()?));
+            reader.set_cursor(pos)?;
+        }
+
         if parser.can_dump(1) {
-            let pos = reader.cursor();
             parser.write_dump(format_args!("FB: {} CC: {} lb: {} cki: {}\n", result.fb.unwrap(), result.cc.unwrap(), lb, cki))?;
 
             if result.fb.unwrap().is_first() && !result.fb.unwrap().is_head() {
@@ -38,10 +154,7 @@ impl Kdoopcode {
                 parser.write_dump(format_args!("hrid: {}.{}\n", hrid1, hrid2))?;
             }
             
-            if !result.fb.unwrap().is_last() {
-                reader.set_cursor(pos + 8)?;
-                let nrid_bdba = reader.read_u32()?;
-                let nrid_slot = reader.read_u16()?;
+            if let Some((nrid_bdba, nrid_slot)) = result.nrid {
                 parser.write_dump(format_args!("next bdba: {} next slot: {}\n", nrid_bdba, nrid_slot))?;
             }
 
@@ -85,13 +198,17 @@ impl Kdoopcode {
             reader.set_cursor(result.nulls_offset.unwrap())?;
         }
 
-        assert!(reader.data().len() >= 45 + ((result.cc.unwrap() as usize + 7) / 8), "Size of field {} < 26 + (cc + 7) / 8", reader.data().len());
+        if reader.data().len() < 45 + ((result.cc.unwrap() as usize + 7) / 8) {
+            return olr_perr!("Size of field {} < 26 + (cc + 7) / 8", reader.data().len());
+        }
 
         Ok(())
     }
 
     fn kdo_opcode_drp(result : &mut Kdoopcode, parser : &mut Parser, reader : &mut ByteReader) -> Result<()> {
-        assert!(reader.data().len() >= 20, "Size of field {} < 20", reader.data().len());
+        if reader.data().len() < 20 {
+            return olr_perr!("Size of field {} < 20", reader.data().len());
+        }
 
         result.slot = Some(reader.read_u16()?);
         let tabn = reader.read_u8()?;
@@ -104,7 +221,9 @@ impl Kdoopcode {
     }
 
     fn kdo_opcode_lkr(result : &mut Kdoopcode, parser : &mut Parser, reader : &mut ByteReader) -> Result<()> {
-        assert!(reader.data().len() >= 20, "Size of field {} < 20", reader.data().len());
+        if reader.data().len() < 20 {
+            return olr_perr!("Size of field {} < 20", reader.data().len());
+        }
 
         result.slot = Some(reader.read_u16()?);
         let tabn = reader.read_u8()?;
@@ -118,7 +237,9 @@ impl Kdoopcode {
     }
 
     fn kdo_opcode_urp(result : &mut Kdoopcode, parser : &mut Parser, reader : &mut ByteReader) -> Result<()> {
-        assert!(reader.data().len() >= 28, "Size of field {} < 28", reader.data().len());
+        if reader.data().len() < 28 {
+            return olr_perr!("Size of field {} < 28", reader.data().len());
+        }
 
         result.fb = Some(reader.read_u8()?.into());
         let lock = reader.read_u8()?;
@@ -136,18 +257,30 @@ impl Kdoopcode {
             parser.write_dump(format_args!("lock: {} ckix: {} tabn: {} ncol: {} size: {}\n", lock, ckix, tabn, ncol, size))?;
         }
 
-        assert!(reader.data().len() >= 26 + ((result.cc.unwrap() as usize + 7) / 8), "Size of field {} < 26 + (cc + 7) / 8", reader.data().len());
+        if reader.data().len() < 26 + ((result.cc.unwrap() as usize + 7) / 8) {
+            return olr_perr!("Size of field {} < 26 + (cc + 7) / 8", reader.data().len());
+        }
 
         Ok(())
     }
 
     fn kdo_opcode_orp(result : &mut Kdoopcode, parser : &mut Parser, reader : &mut ByteReader) -> Result<()> {
-        assert!(reader.data().len() >= 48, "Size of field {} < 48", reader.data().len());
+        if reader.data().len() < 48 {
+            return olr_perr!("Size of field {} < 48", reader.data().len());
+        }
 
         result.fb = Some(reader.read_u8()?.into());
         reader.skip_bytes(1);
         result.cc = Some(reader.read_u8()?);
-        reader.skip_bytes(23);
+        reader.skip_bytes(1);
+
+        let pos = reader.cursor();
+        if !result.fb.unwrap().is_last() {
+            reader.set_cursor(pos + 8)?;
+            result.nrid = Some((reader.read_u32()?, reader.read_u16()?));
+        }
+
+        reader.set_cursor(pos + 22)?;
         result.slot = Some(reader.read_u16()?);
         reader.skip_bytes(1);
 
@@ -157,13 +290,17 @@ impl Kdoopcode {
             parser.write_dump(format_args!("FB: {} SLOT: {} CC: {}\n", result.fb.unwrap(), result.slot.unwrap(), result.cc.unwrap()))?;
         }
 
-        assert!(reader.data().len() >= 45 + ((result.cc.unwrap() as usize + 7) / 8), "Size of field {} < 45 + (cc + 7) / 8", reader.data().len());
+        if reader.data().len() < 45 + ((result.cc.unwrap() as usize + 7) / 8) {
+            return olr_perr!("Size of field {} < 45 + (cc + 7) / 8", reader.data().len());
+        }
 
         Ok(())
     }
 
     fn kdo_opcode_cfa(result : &mut Kdoopcode, parser : &mut Parser, reader : &mut ByteReader) -> Result<()> {
-        assert!(reader.data().len() >= 32, "Size of field {} < 32", reader.data().len());
+        if reader.data().len() < 32 {
+            return olr_perr!("Size of field {} < 32", reader.data().len());
+        }
 
         let nrid_bdba = reader.read_u32()?;
         let nrid_slot = reader.read_u16()?;
@@ -183,7 +320,9 @@ impl Kdoopcode {
     }
 
     fn kdo_opcode_cki(result : &mut Kdoopcode, parser : &mut Parser, reader : &mut ByteReader) -> Result<()> {
-        assert!(reader.data().len() >= 20, "Size of field {} < 20", reader.data().len());
+        if reader.data().len() < 20 {
+            return olr_perr!("Size of field {} < 20", reader.data().len());
+        }
 
         reader.skip_bytes(11);
         result.slot = Some(reader.read_u8()? as u16);
@@ -196,7 +335,9 @@ impl Kdoopcode {
     }
 
     fn kdo_opcode_qm(result : &mut Kdoopcode, parser : &mut Parser, reader : &mut ByteReader) -> Result<()> {
-        assert!(reader.data().len() >= 24, "Size of field {} < 24", reader.data().len());
+        if reader.data().len() < 24 {
+            return olr_perr!("Size of field {} < 24", reader.data().len());
+        }
 
         let tabn = reader.read_u8()?;
         let lock = reader.read_u8()?;
@@ -215,7 +356,9 @@ impl Kdoopcode {
 
 impl VectorField for Kdoopcode {
     fn parse_from_reader(parser : &mut Parser, _vec_reader : &mut VectorReader, reader : &mut ByteReader, field_num : usize) -> Result<Self> {
-        assert!(reader.data().len() >= 16, "Size of field {} < 16", reader.data().len());
+        if reader.data().len() < 16 {
+            return olr_perr!("Size of field {} < 16", reader.data().len());
+        }
 
         let mut result = Kdoopcode::default();
 
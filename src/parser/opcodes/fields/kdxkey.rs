@@ -0,0 +1,66 @@
+use crate::{common::{errors::Result, types::TypeFb}, olr_perr, parser::{byte_reader::ByteReader, parser_impl::Parser}};
+
+/// Columns of index leaf row. For index-organized table key holds primary key columns
+/// and key data holds other columns of row.
+pub struct Kdxkey;
+
+impl Kdxkey {
+    /// Key is a list of columns with length prefix
+    pub fn parse_key<'a>(parser : &mut Parser, reader : &mut ByteReader<'a>, field_num : usize) -> Result<Vec<Option<&'a [u8]>>> {
+        if parser.can_dump(1) {
+            parser.write_dump(format_args!("\n[Change {}; key - {}]\n", field_num, reader.data().len()))?;
+        }
+
+        let mut result = Vec::new();
+        while !reader.eof() {
+            result.push(Self::read_column(parser, reader, result.len())?);
+        }
+        Ok(result)
+    }
+
+    /// Key data has header of row (FB, LB, CC) before columns
+    pub fn parse_keydata<'a>(parser : &mut Parser, reader : &mut ByteReader<'a>, field_num : usize) -> Result<Vec<Option<&'a [u8]>>> {
+        if reader.data().len() < 3 {
+            return olr_perr!("Size of key data field {} < 3", reader.data().len());
+        }
+
+        let fb : TypeFb = reader.read_u8()?.into();
+        let lb = reader.read_u8()?;
+        let cc = reader.read_u8()?;
+
+        if parser.can_dump(1) {
+            parser.write_dump(format_args!("\n[Change {}; key data - {}] FB: {} LB: {} CC: {}\n", field_num, reader.data().len(), fb, lb, cc))?;
+        }
+
+        (0 .. cc as usize).map(|i| Self::read_column(parser, reader, i)).collect()
+    }
+
+    fn read_column<'a>(parser : &mut Parser, reader : &mut ByteReader<'a>, col_num : usize) -> Result<Option<&'a [u8]>> {
+        let mut size : u16 = reader.read_u8()? as u16;
+        let is_null = size == 0xFF;
+
+        if size == 0xFE {
+            size = reader.read_u16()?;
+        }
+
+        let start = reader.cursor();
+        if is_null {
+            if parser.can_dump(1) {
+                parser.dump_column(&[], col_num, 0, true)?;
+            }
+            return Ok(None);
+        }
+
+        if start + size as usize > reader.data().len() {
+            return olr_perr!("Column {} of index row is out of field: {} + {} > {}", col_num, start, size, reader.data().len());
+        }
+
+        let data = &reader.data()[start .. start + size as usize];
+        if parser.can_dump(1) {
+            parser.dump_column(data, col_num, size as usize, false)?;
+        }
+        reader.skip_bytes(size as usize);
+
+        Ok(Some(data))
+    }
+}
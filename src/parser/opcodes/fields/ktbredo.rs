@@ -9,7 +9,9 @@ pub struct Ktbredo {
 
 impl VectorField for Ktbredo {
     fn parse_from_reader(parser : &mut Parser, _vec_reader : &mut VectorReader, reader : &mut ByteReader, field_num : usize) -> Result<Self> {
-        assert!(reader.data().len() >= 8, "Size of field {} < 8", reader.data().len());
+        if reader.data().len() < 8 {
+            return olr_perr!("Size of field {} < 8", reader.data().len());
+        }
 
         let mut result = Ktbredo { xid : Default::default() };
 
@@ -27,7 +29,9 @@ impl VectorField for Ktbredo {
 
         match ktb_op & 0x0F {
             constants::KTBOP_F => {
-                assert!(reader.data().len() - reader.cursor() >= 16, "Size of field {} < 16", reader.data().len());
+                if reader.data().len() - reader.cursor() < 16 {
+                    return olr_perr!("Size of field {} < 16", reader.data().len());
+                }
 
                 let usn = reader.read_u16()?;
                 let slt = reader.read_u16()?;
@@ -40,7 +44,9 @@ impl VectorField for Ktbredo {
                 }
             },
             constants::KTBOP_C => {
-                assert!(reader.data().len() - reader.cursor() >= 8, "Size of field {} < 8", reader.data().len());
+                if reader.data().len() - reader.cursor() < 8 {
+                    return olr_perr!("Size of field {} < 8", reader.data().len());
+                }
 
                 if parser.can_dump(1) {
                     let uba = reader.read_uba()?;
@@ -53,7 +59,9 @@ impl VectorField for Ktbredo {
                 }
             },
             constants::KTBOP_L => {
-                assert!(reader.data().len() - reader.cursor() >= 24, "Size of field {} < 24", reader.data().len());
+                if reader.data().len() - reader.cursor() < 24 {
+                    return olr_perr!("Size of field {} < 24", reader.data().len());
+                }
 
                 if parser.can_dump(1) {
                     let usn = reader.read_u16()?;
@@ -77,7 +85,9 @@ impl VectorField for Ktbredo {
 
                     parser.write_dump(format_args!("Op: R\nITC: {}\n", itc))?;
 
-                    assert!(reader.data().len() - reader.cursor() >= 12 + itc * 24, "Size of field {} < 12 * itc * 24", reader.data().len());
+                    if reader.data().len() - reader.cursor() < 12 + itc * 24 {
+                        return olr_perr!("Size of field {} < 12 + itc * 24", reader.data().len());
+                    }
 
                     for i in 0 .. itc {
                         let usn = reader.read_u16()?;
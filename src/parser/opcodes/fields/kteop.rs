@@ -1,4 +1,4 @@
-use crate::{common::errors::Result, parser::{byte_reader::ByteReader, parser_impl::Parser, record_reader::VectorReader}};
+use crate::{common::errors::Result, olr_perr, parser::{byte_reader::ByteReader, parser_impl::Parser, record_reader::VectorReader}};
 
 use super::VectorField;
 
@@ -8,7 +8,9 @@ pub struct Kteop {
 
 impl VectorField for Kteop {
     fn parse_from_reader(parser : &mut Parser, _vec_reader : &mut VectorReader, reader : &mut ByteReader, field_num : usize) -> Result<Self> {
-        assert!(reader.data().len() == 36, "Size of field {} != 36", reader.data().len());
+        if reader.data().len() != 36 {
+            return olr_perr!("Size of field {} != 36", reader.data().len());
+        }
 
         if parser.can_dump(1) {
             reader.skip_bytes(4);
@@ -1,4 +1,4 @@
-use crate::{common::{constants, errors::Result}, parser::{byte_reader::ByteReader, parser_impl::Parser, record_reader::VectorReader}};
+use crate::{common::{constants, errors::Result}, olr_perr, parser::{byte_reader::ByteReader, parser_impl::Parser, record_reader::VectorReader}};
 
 use super::VectorField;
 
@@ -13,7 +13,9 @@ pub struct Ktub {
 
 impl VectorField for Ktub {
     fn parse_from_reader(parser : &mut Parser, _vec_reader : &mut VectorReader, reader : &mut ByteReader, field_num : usize) -> Result<Self> {
-        assert!(reader.data().len() >= 24, "Size of field {} < 24", reader.data().len());
+        if reader.data().len() < 24 {
+            return olr_perr!("Size of field {} < 24", reader.data().len());
+        }
 
         let obj        = reader.read_u32()?;
         let data_obj   = reader.read_u32()?;
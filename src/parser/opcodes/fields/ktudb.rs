@@ -1,4 +1,4 @@
-use crate::{common::{errors::Result, types::TypeXid}, parser::{byte_reader::ByteReader, parser_impl::Parser, record_reader::VectorReader}};
+use crate::{common::{errors::Result, types::TypeXid}, olr_perr, parser::{byte_reader::ByteReader, parser_impl::Parser, record_reader::VectorReader}};
 
 use super::VectorField;
 
@@ -9,7 +9,9 @@ pub struct Ktudb {
 
 impl VectorField for Ktudb {
     fn parse_from_reader(parser : &mut Parser, _vec_reader : &mut VectorReader, reader : &mut ByteReader, field_num : usize) -> Result<Self> {
-        assert!(reader.data().len() >= 20, "Size of field {} < 20", reader.data().len());
+        if reader.data().len() < 20 {
+            return olr_perr!("Size of field {} < 20", reader.data().len());
+        }
 
         reader.skip_bytes(8);
         let usn = reader.read_u16()?;
@@ -1,4 +1,4 @@
-use crate::{common::{errors::Result, types::TypeXid}, parser::{byte_reader::ByteReader, parser_impl::Parser, record_reader::VectorReader}};
+use crate::{common::{errors::Result, types::TypeXid}, olr_perr, parser::{byte_reader::ByteReader, parser_impl::Parser, record_reader::VectorReader}};
 
 use super::VectorField;
 
@@ -9,7 +9,9 @@ pub struct Ktudh {
 
 impl VectorField for Ktudh {
     fn parse_from_reader(parser : &mut Parser, vec_reader : &mut VectorReader, reader : &mut ByteReader, field_num : usize) -> Result<Self> {
-        assert!(reader.data().len() == 32, "Size of field {} != 32", reader.data().len());
+        if reader.data().len() != 32 {
+            return olr_perr!("Size of field {} != 32", reader.data().len());
+        }
 
         let xid_usn = (vec_reader.header.class - 15) / 2;
         let xid_slot = reader.read_u16()?;
@@ -10,6 +10,8 @@ pub mod ktucm;
 pub mod ktucf;
 pub mod ktudb;
 pub mod ktub;
+pub mod kdli;
+pub mod kdxkey;
 
 pub trait VectorField {
     fn parse_from_reader(
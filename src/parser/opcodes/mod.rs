@@ -5,9 +5,24 @@ use opcode0501::OpCode0501;
 use opcode0502::OpCode0502;
 use opcode0504::OpCode0504;
 use opcode0520::OpCode0520;
+use opcode1002::OpCode1002;
+use opcode1004::OpCode1004;
+use opcode1018::OpCode1018;
 use opcode1102::OpCode1102;
+use opcode1103::OpCode1103;
+use opcode1105::OpCode1105;
+use opcode1106::OpCode1106;
+use opcode1108::OpCode1108;
+use opcode1111::OpCode1111;
+use opcode1112::OpCode1112;
+use opcode1122::OpCode1122;
+use opcode1301::OpCode1301;
+use opcode1a02::OpCode1A02;
+use opcode1a06::OpCode1A06;
+use opcode1801::OpCode1801;
 
 use crate::common::{errors::Result, types::TypeXid};
+use fields::kdli::LobPage;
 
 use super::{archive_structs::vector_header::VectorHeader, byte_reader::ByteReader, parser_impl::Parser, record_reader::VectorReader};
 pub mod fields;
@@ -15,7 +30,24 @@ pub mod opcode0502;
 pub mod opcode0520;
 pub mod opcode0504;
 pub mod opcode0501;
+pub mod opcode1002;
+pub mod opcode1004;
+pub mod opcode1018;
 pub mod opcode1102;
+pub mod opcode1103;
+pub mod opcode1105;
+pub mod opcode1106;
+pub mod opcode1108;
+pub mod opcode1111;
+pub mod opcode1112;
+pub mod opcode1122;
+pub mod opcode1301;
+pub mod opcode1a02;
+pub mod opcode1a06;
+pub mod opcode1801;
+
+/// Column of row image: index of column in segment (from 0) and its data, `None` for null
+pub type RowColumn<'a> = (usize, Option<&'a [u8]>);
 
 #[derive(Debug)]
 pub struct Vector<'a> {
@@ -29,7 +61,7 @@ pub struct Vector<'a> {
 impl<'a> Vector<'a> {
     pub fn parse(parser : &mut Parser, reader : &mut ByteReader<'a>, version : u32) -> Result<Self> {
         let start_position = reader.cursor();
-        let data_ptr = reader.data().as_ptr();
+        let data_ptr = reader.data()[start_position ..].as_ptr();
 
         let header: VectorHeader = reader.read_redo_vector_header(version)?;
         trace!("Analize vector: {:?} offset: {}", header.op_code, reader.cursor());
@@ -46,7 +78,8 @@ impl<'a> Vector<'a> {
 
         let vec_reader = VectorReader::new(
             header.clone(), 
-            &reader.data()[reader.cursor() .. reader.cursor() + body_size]
+            &reader.data()[reader.cursor() .. reader.cursor() + body_size],
+            reader.endian()
         );
 
         reader.skip_bytes(body_size);
@@ -58,12 +91,23 @@ impl<'a> Vector<'a> {
             (5, 2) => OpCode0502::parse(parser, vec_reader)?,
             (5, 4) => OpCode0504::parse(parser, vec_reader)?,
             (5, 20) => OpCode0520::parse(parser, vec_reader)?,
+            (10, 2) => OpCode1002::parse(parser, vec_reader)?,
+            (10, 4) => OpCode1004::parse(parser, vec_reader)?,
+            (10, 18) => OpCode1018::parse(parser, vec_reader)?,
             (11, 2) => OpCode1102::parse(parser, vec_reader)?,
-            (5, 6) | (5, 11) | (5, 19) | (10, 2) |
-            (10, 8) | (10, 18) | (11, 3) | (11, 4) |
-            (11, 5) | (11, 6) | (11, 8) | (11, 11) |
-            (11, 12) | (11, 16) | (11, 22) | (19, 1) |
-            (26, 2) | (26, 6) | (24, 1) => {
+            (11, 3) => OpCode1103::parse(parser, vec_reader)?,
+            (11, 5) => OpCode1105::parse(parser, vec_reader)?,
+            (11, 6) => OpCode1106::parse(parser, vec_reader)?,
+            (11, 8) => OpCode1108::parse(parser, vec_reader)?,
+            (11, 11) => OpCode1111::parse(parser, vec_reader)?,
+            (11, 12) => OpCode1112::parse(parser, vec_reader)?,
+            (11, 22) => OpCode1122::parse(parser, vec_reader)?,
+            (19, 1) => OpCode1301::parse(parser, vec_reader)?,
+            (24, 1) => OpCode1801::parse(parser, vec_reader)?,
+            (26, 2) => OpCode1A02::parse(parser, vec_reader)?,
+            (26, 6) => OpCode1A06::parse(parser, vec_reader)?,
+            (5, 6) | (5, 11) | (5, 19) | (10, 8) |
+            (11, 4) | (11, 16) => {
                 warn!("Opcode: {}.{} not implemented", header.op_code.0, header.op_code.1); 
                 VectorData::UnknownOpcode
             },
@@ -87,7 +131,21 @@ pub enum VectorKind {
     OpCode0502,
     OpCode0504,
     OpCode0520,
+    OpCode1002,
+    OpCode1004,
+    OpCode1018,
     OpCode1102,
+    OpCode1103,
+    OpCode1105,
+    OpCode1106,
+    OpCode1108,
+    OpCode1111,
+    OpCode1112,
+    OpCode1122,
+    OpCode1301,
+    OpCode1801,
+    OpCode1A02,
+    OpCode1A06,
     UnknownOpcode,
 }
 
@@ -97,7 +155,21 @@ pub enum VectorData<'a> {
     OpCode0502(opcode0502::OpCode0502<'a>),
     OpCode0504(opcode0504::OpCode0504<'a>),
     OpCode0520(opcode0520::OpCode0520<'a>),
+    OpCode1002(opcode1002::OpCode1002<'a>),
+    OpCode1004(opcode1004::OpCode1004<'a>),
+    OpCode1018(opcode1018::OpCode1018<'a>),
     OpCode1102(opcode1102::OpCode1102<'a>),
+    OpCode1103(opcode1103::OpCode1103<'a>),
+    OpCode1105(opcode1105::OpCode1105<'a>),
+    OpCode1106(opcode1106::OpCode1106<'a>),
+    OpCode1108(opcode1108::OpCode1108<'a>),
+    OpCode1111(opcode1111::OpCode1111<'a>),
+    OpCode1112(opcode1112::OpCode1112<'a>),
+    OpCode1122(opcode1122::OpCode1122<'a>),
+    OpCode1301(opcode1301::OpCode1301<'a>),
+    OpCode1801(opcode1801::OpCode1801<'a>),
+    OpCode1A02(opcode1a02::OpCode1A02<'a>),
+    OpCode1A06(opcode1a06::OpCode1A06<'a>),
     UnknownOpcode,
 }
 
@@ -110,8 +182,22 @@ impl<'a> Vector<'a> {
         ((self.header.op_code.0 as u16) << 8) | (self.header.op_code.1 as u16)
     }
 
-    pub fn data(&self) -> *const u8 {
-        self.data_ptr
+    /// Absolute file number of changed block
+    pub fn afn(&self) -> u16 {
+        self.header.afn
+    }
+
+    /// Raw bytes of vector with its header
+    pub fn raw_data(&self) -> &'a [u8] {
+        unsafe { std::slice::from_raw_parts(self.data_ptr, self.size) }
+    }
+
+    pub fn data(&self) -> &VectorData<'a> {
+        &self.data
+    }
+
+    pub fn into_data(self) -> VectorData<'a> {
+        self.data
     }
     
     pub fn kind(&self) -> VectorKind {
@@ -120,7 +206,21 @@ impl<'a> Vector<'a> {
             VectorData::OpCode0502(_) => VectorKind::OpCode0502,
             VectorData::OpCode0504(_) => VectorKind::OpCode0504,
             VectorData::OpCode0520(_) => VectorKind::OpCode0520,
+            VectorData::OpCode1002(_) => VectorKind::OpCode1002,
+            VectorData::OpCode1004(_) => VectorKind::OpCode1004,
+            VectorData::OpCode1018(_) => VectorKind::OpCode1018,
             VectorData::OpCode1102(_) => VectorKind::OpCode1102,
+            VectorData::OpCode1103(_) => VectorKind::OpCode1103,
+            VectorData::OpCode1105(_) => VectorKind::OpCode1105,
+            VectorData::OpCode1106(_) => VectorKind::OpCode1106,
+            VectorData::OpCode1108(_) => VectorKind::OpCode1108,
+            VectorData::OpCode1111(_) => VectorKind::OpCode1111,
+            VectorData::OpCode1112(_) => VectorKind::OpCode1112,
+            VectorData::OpCode1122(_) => VectorKind::OpCode1122,
+            VectorData::OpCode1301(_) => VectorKind::OpCode1301,
+            VectorData::OpCode1801(_) => VectorKind::OpCode1801,
+            VectorData::OpCode1A02(_) => VectorKind::OpCode1A02,
+            VectorData::OpCode1A06(_) => VectorKind::OpCode1A06,
             VectorData::UnknownOpcode => VectorKind::UnknownOpcode,
         }
     }
@@ -131,7 +231,21 @@ impl<'a> Vector<'a> {
             VectorData::OpCode0502(inside) => Some(inside.xid),
             VectorData::OpCode0504(inside) => Some(inside.xid),
             VectorData::OpCode0520(_) => None,
-            VectorData::OpCode1102(inside) => Some(inside.xid),
+            VectorData::OpCode1002(inside) => inside.xid,
+            VectorData::OpCode1004(inside) => inside.xid,
+            VectorData::OpCode1018(inside) => inside.xid,
+            VectorData::OpCode1102(inside) => inside.xid,
+            VectorData::OpCode1103(inside) => inside.xid,
+            VectorData::OpCode1105(inside) => inside.xid,
+            VectorData::OpCode1106(inside) => inside.xid,
+            VectorData::OpCode1108(inside) => inside.xid,
+            VectorData::OpCode1111(inside) => inside.xid,
+            VectorData::OpCode1112(inside) => inside.xid,
+            VectorData::OpCode1122(inside) => inside.xid,
+            VectorData::OpCode1301(_) => None,
+            VectorData::OpCode1801(inside) => Some(inside.xid),
+            VectorData::OpCode1A02(inside) => inside.xid,
+            VectorData::OpCode1A06(_) => None,
             VectorData::UnknownOpcode => None,
         }
     }
@@ -142,10 +256,41 @@ impl<'a> Vector<'a> {
             VectorData::OpCode0502(_) => None,
             VectorData::OpCode0504(_) => None,
             VectorData::OpCode0520(_) => None,
+            VectorData::OpCode1002(_) => None,
+            VectorData::OpCode1004(_) => None,
+            VectorData::OpCode1018(_) => None,
             VectorData::OpCode1102(_) => None,
+            VectorData::OpCode1103(_) => None,
+            VectorData::OpCode1105(_) => None,
+            VectorData::OpCode1106(_) => None,
+            VectorData::OpCode1108(_) => None,
+            VectorData::OpCode1111(_) => None,
+            VectorData::OpCode1112(_) => None,
+            VectorData::OpCode1122(_) => None,
+            VectorData::OpCode1301(_) => None,
+            VectorData::OpCode1801(inside) => Some(inside.obj),
+            VectorData::OpCode1A02(_) => None,
+            VectorData::OpCode1A06(_) => None,
             VectorData::UnknownOpcode => None,
         }
     }
+
+    /// Page of LOB data carried by 26.x and 19.1 vectors
+    pub fn lob_page(&self) -> Option<&LobPage<'a>> {
+        match &self.data {
+            VectorData::OpCode1301(inside) => Some(&inside.page),
+            VectorData::OpCode1A02(inside) => Some(&inside.page),
+            VectorData::OpCode1A06(inside) => Some(&inside.page),
+            _ => None,
+        }
+    }
+
+    pub fn data_obj(&self) -> Option<u32> {
+        match &self.data {
+            VectorData::OpCode0501(inside) => Some(inside.data_obj),
+            _ => None,
+        }
+    }
 }
 
 impl<'a> Display for VectorData<'a> {
@@ -155,7 +300,21 @@ impl<'a> Display for VectorData<'a> {
             VectorData::OpCode0502(_) => "opcode0502",
             VectorData::OpCode0504(_) => "opcode0504",
             VectorData::OpCode0520(_) => "opcode0520",
+            VectorData::OpCode1002(_) => "opcode1002",
+            VectorData::OpCode1004(_) => "opcode1004",
+            VectorData::OpCode1018(_) => "opcode1018",
             VectorData::OpCode1102(_) => "opcode1102",
+            VectorData::OpCode1103(_) => "opcode1103",
+            VectorData::OpCode1105(_) => "opcode1105",
+            VectorData::OpCode1106(_) => "opcode1106",
+            VectorData::OpCode1108(_) => "opcode1108",
+            VectorData::OpCode1111(_) => "opcode1111",
+            VectorData::OpCode1112(_) => "opcode1112",
+            VectorData::OpCode1122(_) => "opcode1122",
+            VectorData::OpCode1301(_) => "opcode1301",
+            VectorData::OpCode1801(_) => "opcode1801",
+            VectorData::OpCode1A02(_) => "opcode1a02",
+            VectorData::OpCode1A06(_) => "opcode1a06",
             VectorData::UnknownOpcode => "unknown opcode",
         })
     }
@@ -1,4 +1,6 @@
-use super::{fields::{kdoopcode::Kdoopcode, ktbredo::Ktbredo, ktub::Ktub, ktudb::Ktudb, VectorField}, VectorData, VectorParser};
+use log::warn;
+
+use super::{fields::{kdoopcode::{KdoRow, Kdoopcode}, kdxkey::Kdxkey, ktbredo::Ktbredo, ktub::Ktub, ktudb::Ktudb, VectorField}, RowColumn, VectorData, VectorParser};
 use crate::{common::{constants, errors::Result, types::{TypeFb, TypeXid}}, olr_perr, parser::{byte_reader::ByteReader, parser_impl::Parser, record_reader::VectorReader}};
 
 #[derive(Debug)]
@@ -23,6 +25,24 @@ pub struct OpCode0501<'a> {
     pub slots_offset : usize,
 
     pub nrow : u8,
+    pub nrid : Option<(u32, u16)>,
+
+    pub columns : Vec<RowColumn<'a>>, // Undo image of row
+    pub compressed : Option<&'a [u8]>, // Undo image of row in compressed block
+    pub rows : Vec<KdoRow<'a>>, // Undo image of multi-row delete
+
+    pub index_code : u8,
+    pub key : Vec<Option<&'a [u8]>>, // Columns of index leaf row
+    pub keydata : Vec<Option<&'a [u8]>>,
+
+    pub supplog_type : u8,
+    pub supplog_fb : TypeFb,
+    pub supplog_cc : u16,
+    pub supplog_before : u16,
+    pub supplog_after : u16,
+    pub supplog_bdba : u32,
+    pub supplog_slot : u16,
+    pub supplog_columns : Vec<RowColumn<'a>>,
     
     reader : VectorReader<'a>,
 }
@@ -46,6 +66,21 @@ impl<'a> OpCode0501<'a> {
             nulls_offset : Default::default(),
             slots_offset : Default::default(),
             nrow : Default::default(),
+            nrid : None,
+            columns : Vec::new(),
+            compressed : None,
+            rows : Vec::new(),
+            index_code : Default::default(),
+            key : Vec::new(),
+            keydata : Vec::new(),
+            supplog_type : Default::default(),
+            supplog_fb : Default::default(),
+            supplog_cc : Default::default(),
+            supplog_before : Default::default(),
+            supplog_after : Default::default(),
+            supplog_bdba : Default::default(),
+            supplog_slot : Default::default(),
+            supplog_columns : Vec::new(),
             reader,
         };
         res.init(parser)?;
@@ -85,7 +120,7 @@ impl<'a> OpCode0501<'a> {
 
                 self.opc0b01(parser, 3)?;
             },
-            (26, 1) => std::unimplemented!(),
+            (26, 1) => warn!("Undo of LOB (26.1) is not parsed, skip it, xid: {}", self.xid),
             (14, 8) => () /* kteoputrn field */,
             (_, _) => (),
         }
@@ -122,6 +157,7 @@ impl<'a> OpCode0501<'a> {
         self.bdba = kdoopcode.bdba;
         self.op = kdoopcode.op;
         self.flags = kdoopcode.flags;
+        self.nrid = kdoopcode.nrid;
 
         if let Some(fb) = kdoopcode.fb { self.fb = fb; }
         if let Some(cc) = kdoopcode.cc { self.cc = cc; }
@@ -134,34 +170,35 @@ impl<'a> OpCode0501<'a> {
         Ok(())
     }
 
+    /// Supplemental log block after KDO opcode: header, column numbers, column lengths and data of each column
     fn supp_log(&mut self, parser : &mut Parser, mut field_num : usize) -> Result<()> {
         field_num = field_num + self.reader.skip_empty() + 1;
 
-        let supplog_cc;
-
         match self.reader.next() {
             None => return Ok(()),
             Some(mut field_reader) => {
-                assert!(field_reader.data().len() >= 20, "Size of field {} < 20", field_reader.data().len());
+                if field_reader.data().len() < 20 {
+                    return olr_perr!("Size of field {} < 20", field_reader.data().len());
+                }
 
-                let supplog_type: u8 = field_reader.read_u8()?;
-                let supplog_fb: TypeFb = field_reader.read_u8()?.into();
-                supplog_cc = field_reader.read_u16()?;
+                self.supplog_type = field_reader.read_u8()?;
+                self.supplog_fb = field_reader.read_u8()?.into();
+                self.supplog_cc = field_reader.read_u16()?;
                 field_reader.skip_bytes(2);
-                let supplog_before = field_reader.read_u16()?;
-                let supplog_after = field_reader.read_u16()?;
+                self.supplog_before = field_reader.read_u16()?;
+                self.supplog_after = field_reader.read_u16()?;
 
                 if parser.can_dump(2) {
                     parser.write_dump(format_args!("\n[Change {}; SuppLog - {}] Type: {} FB : {} CC: {}\nBefore: {} After: {}\n", 
-                        field_num, field_reader.data().len(), supplog_type, supplog_fb, supplog_cc, supplog_before, supplog_after))?;
+                        field_num, field_reader.data().len(), self.supplog_type, self.supplog_fb, self.supplog_cc, self.supplog_before, self.supplog_after))?;
                 }
 
                 if field_reader.data().len() >= 26 {
                     field_reader.skip_bytes(10);
-                    let supplog_bdba = field_reader.read_u32()?;
-                    let supplog_slot = field_reader.read_u16()?;
+                    self.supplog_bdba = field_reader.read_u32()?;
+                    self.supplog_slot = field_reader.read_u16()?;
                     if parser.can_dump(2) {
-                        parser.write_dump(format_args!("Bdba: {} Slot: {}\n", supplog_bdba, supplog_slot))?;
+                        parser.write_dump(format_args!("Bdba: {} Slot: {}\n", self.supplog_bdba, self.supplog_slot))?;
                     }
                 }
             }
@@ -171,9 +208,13 @@ impl<'a> OpCode0501<'a> {
         let mut supplog_numbers = match self.reader.next() {
             None => return Ok(()),
             Some(mut field_reader) => {
+                if field_reader.data().len() < self.supplog_cc as usize * 2 {
+                    return olr_perr!("Size of supplemental log column numbers {} < 2 * {}", field_reader.data().len(), self.supplog_cc);
+                }
+
                 if parser.can_dump(2) {
                     parser.write_dump(format_args!("\n[Change {}; SuppLog - {}] Column numbers: {}\n", field_num, field_reader.data().len(),
-                        (0 .. supplog_cc).map(|_| format!("{} ", field_reader.read_u16().unwrap()) ).collect::<String>() ))?;
+                        (0 .. self.supplog_cc).map(|_| format!("{} ", field_reader.read_u16().unwrap()) ).collect::<String>() ))?;
                 }
                 field_reader.reset_cursor();
                 field_reader
@@ -182,40 +223,73 @@ impl<'a> OpCode0501<'a> {
 
         field_num += 1;
         let mut supplog_lengths = match self.reader.next() {
-            None => return Ok(()),
+            None => return olr_perr!("Expected supplemental log column lengths field"),
             Some(mut field_reader) => {
+                if field_reader.data().len() < self.supplog_cc as usize * 2 {
+                    return olr_perr!("Size of supplemental log column lengths {} < 2 * {}", field_reader.data().len(), self.supplog_cc);
+                }
+
                 if parser.can_dump(2) {
                     parser.write_dump(format_args!("\n[Change {}; SuppLog - {}] Column lengths: {}\n", field_num, field_reader.data().len(),
-                        (0 .. supplog_cc).map(|_| format!("{} ", field_reader.read_u16().unwrap()) ).collect::<String>() ))?;
+                        (0 .. self.supplog_cc).map(|_| format!("{} ", field_reader.read_u16().unwrap()) ).collect::<String>() ))?;
                 }
                 field_reader.reset_cursor();
                 field_reader
             }
         };
 
-        if parser.can_dump(2) {
-            for _ in 0 .. supplog_cc {
-                let field_reader = self.reader.next().unwrap();
+        self.supplog_columns.reserve(self.supplog_cc as usize);
+        for _ in 0 .. self.supplog_cc {
+            let field_reader = self.reader.next()
+                .ok_or(olr_perr!("Expected supplemental log column data field"))?;
+
+            // Column numbers are counted from 1, length 0xFFFF marks null
+            let col_num = supplog_numbers.read_u16()? as usize;
+            let col_len = supplog_lengths.read_u16()? as usize;
+            let is_null = col_len == 0xFFFF;
+
+            if parser.can_dump(2) {
+                parser.dump_column(field_reader.data(), col_num, col_len % 0xFFFF, is_null)?;
+            }
 
-                let col_num = supplog_numbers.read_u16()? as usize;
-                let col_len = supplog_lengths.read_u16()? as usize;
-                parser.dump_column(&field_reader.data(), col_num, col_len % 65535, col_len == 65535)?;
+            if col_num == 0 {
+                return olr_perr!("Supplemental log column number can not be 0");
             }
+
+            self.supplog_columns.push((col_num - 1, if is_null { None } else { Some(field_reader.data()) }));
         }
 
         Ok(())
     }
 
-    fn kdilk(&mut self, parser : &mut Parser, reader : &mut ByteReader, _field_num : usize) -> Result<()> {
-        assert!(reader.data().len() >= 20, "Size of field {} < 20", reader.data().len());
+    fn kdilk(&mut self, parser : &mut Parser, reader : &mut ByteReader, field_num : usize) -> Result<()> {
+        if reader.data().len() < 20 {
+            return olr_perr!("Size of field {} < 20", reader.data().len());
+        }
 
-        // if parser.can_dump(1) {
-        //     parser.write_dump(format_args!("{}", reader.to_hex_dump()))?;
-        // }
+        self.index_code = reader.read_u8()?;
+        let itl = reader.read_u8()?;
+        let flag = reader.read_u8()?;
+        reader.skip_bytes(1);
+        let index_id = reader.read_u32()?;
+        let block = reader.read_u32()?;
+        self.bdba = block;
+
+        if parser.can_dump(1) {
+            let code_name = match self.index_code {
+                constants::KDILK_CODE_PURGE => "(kdxlpu): purge leaf row",
+                constants::KDILK_CODE_RESTORE => "(kdxlre): restore leaf row (clear leaf delete flags)",
+                constants::KDILK_CODE_UPDATE => "(kdxlup): update keydata in row",
+                _ => "unknown",
+            };
+            parser.write_dump(format_args!("\n[Change {}; kdilk - {}] code: {} itl: {} flag: {} index id: {} block: {}\n", 
+                field_num, reader.data().len(), code_name, itl, flag, index_id, block))?;
+        }
 
         Ok(())
     }
 
+    /// Undo of index leaf operation: kdilk, key and key data of row for restore and update
     fn opc0a16(&mut self, parser : &mut Parser, field_num : usize) -> Result<()> {
         if let Some(mut field_reader) = self.reader.next()  {
             self.kdilk(parser, &mut field_reader, field_num)?;
@@ -223,6 +297,16 @@ impl<'a> OpCode0501<'a> {
             return olr_perr!("expect kdilk opcode field");
         }
 
+        if let Some(mut field_reader) = self.reader.next() {
+            self.key = Kdxkey::parse_key(parser, &mut field_reader, field_num + 1)?;
+        }
+
+        if matches!(self.index_code, constants::KDILK_CODE_RESTORE | constants::KDILK_CODE_UPDATE) {
+            if let Some(mut field_reader) = self.reader.next() {
+                self.keydata = Kdxkey::parse_keydata(parser, &mut field_reader, field_num + 2)?;
+            }
+        }
+
         Ok(())
     }
 
@@ -235,23 +319,30 @@ impl<'a> OpCode0501<'a> {
 
         match self.op & 0x1F {
             constants::OP_IRP | constants::OP_ORP => {
-                if self.cc > 0 {
-                    if parser.can_dump(1) {
-                        let mut nulls: u8 = 0;
-                        for (i, mask) in (0 .. self.cc).map(|i| (i, 1u8 << (i & 0b111))) {
-                            if mask == 1 {
-                                nulls = ktb_opcode_reader.read_u8()?;
-                            }
+                let mut nulls: u8 = 0;
+                self.columns.reserve(self.cc as usize);
+                for (i, mask) in (0 .. self.cc).map(|i| (i, 1u8 << (i & 0b111))) {
+                    if mask == 1 {
+                        nulls = ktb_opcode_reader.read_u8()?;
+                    }
 
-                            let column_reader = self.reader.next().unwrap();
+                    let column_reader = self.reader.next()
+                        .ok_or(olr_perr!("Expected column {} data field", i))?;
 
-                            parser.dump_column(&column_reader.data(), i as usize, column_reader.data().len(), nulls & mask != 0)?;
-                        }
-                    } else {
-                        for _ in 0 .. self.cc {
-                            let _ = self.reader.next().unwrap();
+                    // Row of compressed block is kept in one field with size of row
+                    if i == 0 && self.cc != 1 && column_reader.data().len() == self.size_delt as usize {
+                        if parser.can_dump(1) {
+                            parser.write_dump(format_args!("Compressed row - {}\n{}", column_reader.data().len(), column_reader.to_hex_dump()))?;
                         }
+                        self.compressed = Some(column_reader.data());
+                        break;
+                    }
+
+                    if parser.can_dump(1) {
+                        parser.dump_column(column_reader.data(), i as usize, column_reader.data().len(), nulls & mask != 0)?;
                     }
+
+                    self.columns.push((i as usize, if nulls & mask != 0 { None } else { Some(column_reader.data()) }));
                 }
 
                 if self.op & 64 != 0 {
@@ -269,23 +360,27 @@ impl<'a> OpCode0501<'a> {
             },
             constants::OP_URP => {
                 if self.flags & 128 != 0 {
-                    std::unimplemented!();
+                    self.columns = Kdoopcode::parse_columns_vector(parser, &mut self.reader, self.cc)?;
                 } else {
-                    let _ = self.reader.next().unwrap(); // 4 bytes size
-
-                    let mut bits : u8 = 1;
-                    let mut nulls: u8 = ktb_opcode_reader.read_u8()?;
+                    let mut numbers_reader = self.reader.next()
+                        .ok_or(olr_perr!("Expected column numbers field"))?;
 
-                    for i in 0 .. self.cc {
-                        let column_reader = self.reader.next().unwrap();
+                    let mut nulls: u8 = 0;
+                    self.columns.reserve(self.cc as usize);
+                    for (i, mask) in (0 .. self.cc).map(|i| (i, 1u8 << (i & 0b111))) {
+                        if mask == 1 {
+                            nulls = ktb_opcode_reader.read_u8()?;
+                        }
 
-                        parser.dump_column(&column_reader.data(), i as usize, column_reader.data().len(), nulls & bits != 0)?;
+                        let column_reader = self.reader.next()
+                            .ok_or(olr_perr!("Expected column {} data field", i))?;
+                        let col_num = numbers_reader.read_u16()? as usize;
 
-                        bits <<= 1;
-                        if bits == 0 {
-                            bits = 1;
-                            nulls = ktb_opcode_reader.read_u8()?;
+                        if parser.can_dump(1) {
+                            parser.dump_column(column_reader.data(), col_num, column_reader.data().len(), nulls & mask != 0)?;
                         }
+
+                        self.columns.push((col_num, if nulls & mask != 0 { None } else { Some(column_reader.data()) }));
                     }
                 }
 
@@ -296,46 +391,13 @@ impl<'a> OpCode0501<'a> {
                 self.supp_log(parser, field_num)?;
             },
             constants::OP_QMI => {
-                let mut sizes_reader = self.reader.next().unwrap();
-                let mut data_reader = self.reader.next().unwrap();
-
-                if parser.can_dump(1) {
-                    for _ in 0 .. self.nrow {
-                        let fb: TypeFb = data_reader.read_u8()?.into();
-                        let lb = data_reader.read_u8()?;
-                        let jcc = data_reader.read_u8()?;
-                        let tl = sizes_reader.read_u16()?;
-
-                        parser.write_dump(format_args!("FB: {} LB: {} TL: {} JCC: {}\n", fb, lb, tl, jcc))?;
-
-                        if self.op & 64 != 0 {
-                            if parser.version().unwrap() < constants::REDO_VERSION_12_2 {
-                                data_reader.skip_bytes(6);
-                            } else {
-                                data_reader.skip_bytes(8);
-                            }
-                        }
-
-                        for j in 0 .. jcc {
-                            let mut size: u16 = data_reader.read_u8()? as u16;
-                            let is_null: bool = size == 0xFF;
-
-                            if size == 0xFE {
-                                size = data_reader.read_u16()?;
-                            }
-
-                            parser.dump_column(&data_reader.data()[data_reader.cursor() ..], j as usize, size as usize, is_null)?;
-                            if !is_null {
-                                data_reader.skip_bytes(size as usize);
-                            }
-                        }
-                    }
-                }
+                ktb_opcode_reader.set_cursor(self.slots_offset)?;
+                self.rows = Kdoopcode::parse_rows(parser, &mut self.reader, &mut ktb_opcode_reader, self.op, self.nrow)?;
             },
             constants::OP_LKR | constants::OP_LMN | constants::OP_CFA => {
                 self.supp_log(parser, field_num)?;
             },
-            constants::OP_SKL | constants::OP_QMD => {},
+            constants::OP_SKL | constants::OP_QMD | constants::OP_CMP => {},
             _ => std::unimplemented!("{}", self.op & 0x1F),
         }
 
@@ -5,6 +5,7 @@ use crate::{common::{constants, errors::Result, types::TypeXid}, olr_perr, parse
 pub struct OpCode0502<'a> {
     pub xid : TypeXid,
     pub flg : u16,
+    pub pdb_id : Option<u32>,
 
     reader : VectorReader<'a>,
 }
@@ -14,6 +15,7 @@ impl<'a> OpCode0502<'a> {
         let mut res = Self {
             xid : Default::default(),
             flg : Default::default(),
+            pdb_id : None,
             reader,
         };
         res.init(parser)?;
@@ -21,8 +23,10 @@ impl<'a> OpCode0502<'a> {
     }
 
     fn init(&mut self, parser : &mut Parser) -> Result<()> {
-        if self.reader.header.fields_count > 3 {
-            return olr_perr!("Opcode: 5.2 Count of field > 3. Dump: {}", self.reader.by_ref().map(|x| {x.to_hex_dump()}).collect::<String>());
+        // Since 12.1 PDB field follows ktudh and optional kteop
+        let max_fields = if parser.version().unwrap() >= constants::REDO_VERSION_12_1 { 3 } else { 2 };
+        if self.reader.header.fields_count > max_fields {
+            return olr_perr!("Opcode: 5.2 Count of field > {}. Dump: {}", max_fields, self.reader.by_ref().map(|x| {x.to_hex_dump()}).collect::<String>());
         }
 
         match self.reader.next() {
@@ -30,16 +34,14 @@ impl<'a> OpCode0502<'a> {
             None => olr_perr!("Expect ktudh field")
         }?;
 
-        if parser.version().unwrap() >= constants::REDO_VERSION_12_1 && parser.can_dump(1) {
-            if let Some(mut field_reader) = self.reader.next() {
-                if field_reader.data().len() == 4 {
-                    self.pdb(parser, &mut field_reader, 1)?;
-                } else {
-                    self.kteop(parser, &mut field_reader, 1)?;
+        if let Some(mut field_reader) = self.reader.next() {
+            if parser.version().unwrap() >= constants::REDO_VERSION_12_1 && field_reader.data().len() == 4 {
+                self.pdb(parser, &mut field_reader, 1)?;
+            } else {
+                self.kteop(parser, &mut field_reader, 1)?;
 
-                    if let Some(mut field_reader) = self.reader.next() {
-                        self.pdb(parser, &mut field_reader, 2)?;
-                    }
+                if let Some(mut field_reader) = self.reader.next() {
+                    self.pdb(parser, &mut field_reader, 2)?;
                 }
             }
         }
@@ -55,7 +57,8 @@ impl<'a> OpCode0502<'a> {
     }
 
     fn pdb(&mut self, parser : &mut Parser, reader : &mut ByteReader, field_num : usize) -> Result<()> {
-        Pdb::parse_from_reader(parser, &mut self.reader, reader, field_num)?;
+        let pdb = Pdb::parse_from_reader(parser, &mut self.reader, reader, field_num)?;
+        self.pdb_id = Some(pdb.pdb_id);
         Ok(())
     }
 
@@ -1,5 +1,5 @@
 use super::{VectorData, VectorParser};
-use crate::{common::{constants, errors::Result}, parser::{byte_reader::ByteReader, parser_impl::Parser, record_reader::VectorReader}};
+use crate::{common::{constants, errors::Result}, olr_perr, parser::{byte_reader::ByteReader, parser_impl::Parser, record_reader::VectorReader}};
 
 #[derive(Debug)]
 pub struct OpCode0520<'a> {
@@ -27,7 +27,11 @@ impl<'a> OpCode0520<'a> {
     }
 
     fn init(&mut self, parser : &mut Parser) -> Result<()> {
-        assert!(self.reader.header.fields_count == 8, "Opcode: 5.4 Count of field != 8. Dump: {}", self.reader.by_ref().map(|x| {x.to_hex_dump()}).collect::<String>());
+        // Fields after the 8th one are skipped
+        let fields_count = self.reader.header.fields_count;
+        if fields_count < 8 {
+            return olr_perr!("Opcode: 5.20 Count of field {} < 8. Dump: {}", fields_count, self.reader.by_ref().map(|x| {x.to_hex_dump()}).collect::<String>());
+        }
 
         if let Some(mut field_reader) = self.reader.next() {
             self.session_attribute_1(parser, &mut field_reader, 0)?;
@@ -65,7 +69,9 @@ impl<'a> OpCode0520<'a> {
     }
     
     fn session_attribute_1(&mut self, parser : &mut Parser, reader : &mut ByteReader, field_num : usize) -> Result<()> {
-        assert!(reader.data().len() >= 4, "Size of field {} < 4", reader.data().len());
+        if reader.data().len() < 4 {
+            return olr_perr!("Size of field {} < 4", reader.data().len());
+        }
 
         if parser.version().unwrap() < constants::REDO_VERSION_19_0 {
             self.session_number = reader.read_u16()? as u32;
@@ -73,7 +79,9 @@ impl<'a> OpCode0520<'a> {
 
             reader.skip_bytes(reader.data().len() - 4);
         } else {
-            assert!(reader.data().len() >= 8, "Size of field {} < 8", reader.data().len());
+            if reader.data().len() < 8 {
+                return olr_perr!("Size of field {} < 8", reader.data().len());
+            }
             reader.skip_bytes(2);
             self.serial_number = reader.read_u16()?;
             self.session_number = reader.read_u32()?;
@@ -99,7 +107,9 @@ impl<'a> OpCode0520<'a> {
     }
 
     fn session_attribute_3(&mut self, parser : &mut Parser, reader : &mut ByteReader, field_num : usize) -> Result<()> {
-        assert!(reader.data().len() >= 6, "Size of field {} < 6", reader.data().len());
+        if reader.data().len() < 6 {
+            return olr_perr!("Size of field {} < 6", reader.data().len());
+        }
 
         if parser.can_dump(1) {
             let flg1 = reader.read_u16()?;
@@ -114,7 +124,9 @@ impl<'a> OpCode0520<'a> {
     }
 
     fn session_attribute_4(&mut self, parser : &mut Parser, reader : &mut ByteReader, field_num : usize) -> Result<()> {
-        assert!(reader.data().len() >= 4, "Size of field {} < 4", reader.data().len());
+        if reader.data().len() < 4 {
+            return olr_perr!("Size of field {} < 4", reader.data().len());
+        }
 
         self.version = reader.read_u32()?;
 
@@ -126,7 +138,9 @@ impl<'a> OpCode0520<'a> {
     }
 
     fn session_attribute_5(&mut self, parser : &mut Parser, reader : &mut ByteReader, field_num : usize) -> Result<()> {
-        assert!(reader.data().len() >= 4, "Size of field {} < 4", reader.data().len());
+        if reader.data().len() < 4 {
+            return olr_perr!("Size of field {} < 4", reader.data().len());
+        }
 
         self.audit_session_id = reader.read_u32()?;
 
@@ -0,0 +1,138 @@
+use super::{fields::{kdxkey::Kdxkey, ktbredo::Ktbredo, VectorField}, VectorData, VectorParser};
+use crate::{common::{errors::Result, types::TypeXid}, olr_perr, parser::{byte_reader::ByteReader, parser_impl::Parser, record_reader::VectorReader}};
+
+/// Insert leaf row of index. For index-organized table it is insert of row.
+#[derive(Debug)]
+pub struct OpCode1002<'a> {
+    pub xid : Option<TypeXid>, // None when KTB redo has no XID
+    pub bdba : u32,
+    pub itl : u8,
+    pub slot : u16,
+
+    pub key : Vec<Option<&'a [u8]>>,
+    pub keydata : Vec<Option<&'a [u8]>>,
+    
+    reader : VectorReader<'a>,
+}
+
+impl<'a> OpCode1002<'a> {
+    pub fn new(parser : &mut Parser, reader : VectorReader<'a>) -> Result<Self> {
+        let mut res = Self {
+            xid : Default::default(),
+            bdba : reader.header.dba,
+            itl : Default::default(),
+            slot : Default::default(),
+            key : Vec::new(),
+            keydata : Vec::new(),
+            reader,
+        };
+        res.init(parser)?;
+        Ok(res)
+    }
+
+    fn init(&mut self, parser : &mut Parser) -> Result<()> {
+
+        match self.reader.next() {
+            Some(mut field_reader) => self.ktbredo(parser, &mut field_reader, 0),
+            None => olr_perr!("Expect ktb_redo field")
+        }?;
+        
+        match self.reader.next() {
+            Some(mut field_reader) => self.kdxlin(parser, &mut field_reader, 1)?,
+            None => return Ok(()),
+        }
+
+        if let Some(mut field_reader) = self.reader.next() {
+            self.key = Kdxkey::parse_key(parser, &mut field_reader, 2)?;
+        }
+
+        if let Some(mut field_reader) = self.reader.next() {
+            self.keydata = Kdxkey::parse_keydata(parser, &mut field_reader, 3)?;
+        }
+
+        Ok(())
+    }
+
+    fn ktbredo(&mut self, parser : &mut Parser, reader : &mut ByteReader, field_num : usize) -> Result<()> {
+        let ktbredo = Ktbredo::parse_from_reader(parser, &mut self.reader, reader, field_num)?;
+        self.xid = ktbredo.xid;
+        Ok(())
+    }
+
+    fn kdxlin(&mut self, parser : &mut Parser, reader : &mut ByteReader, field_num : usize) -> Result<()> {
+        if reader.data().len() < 4 {
+            return olr_perr!("Size of field {} < 4", reader.data().len());
+        }
+
+        self.itl = reader.read_u8()?;
+        reader.skip_bytes(1);
+        self.slot = reader.read_u16()?;
+
+        if parser.can_dump(1) {
+            parser.write_dump(format_args!("\n[Change {}; kdxlin - {}] itl: {} sno: {}\n", field_num, reader.data().len(), self.itl, self.slot))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> VectorParser<'a> for OpCode1002<'a> {
+    fn parse(parser : &mut Parser, reader : VectorReader<'a>) -> Result<VectorData<'a>> {
+        Ok(
+            VectorData::OpCode1002(
+                OpCode1002::new(parser, reader)?
+            )
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use crate::{builder::{formats, lobs::LobContext}, common::{constants, errors::Result, testing, types::TypeXid}, oradefs::oracle_table::OracleTable, parser::opcodes::VectorData};
+
+    #[test]
+    fn test_iot_insert() -> Result<()> {
+        let context_ptr = testing::context();
+        let metadata_ptr = testing::metadata(context_ptr.clone());
+        let (builder_ptr, output) = testing::builder(context_ptr.clone(), metadata_ptr.clone(), formats::MESSAGE_FORMAT_DEFAULT);
+        let mut parser = testing::parser(context_ptr, builder_ptr.clone(), metadata_ptr.clone());
+
+        // Primary key columns are in other order than in segment, leaf row has key columns first
+        let columns = vec![testing::column(1, "ID", constants::SYS_COL_TYPE_NUMBER), testing::column(2, "NAME", constants::SYS_COL_TYPE_VARCHAR),
+                           testing::column(3, "CODE", constants::SYS_COL_TYPE_VARCHAR)];
+        let mut table = OracleTable::new("SCOTT".to_string(), "T1".to_string(), columns, vec!["CODE".to_string(), "ID".to_string()]);
+        table.set_iot();
+        assert_eq!((table.iot_column(0), table.iot_column(1), table.iot_column(2), table.iot_column(3)), (Some(2), Some(0), Some(1), None));
+        testing::set_tables(&metadata_ptr, vec![(100, 101, table)]);
+
+        let xid = TypeXid::new(10, 27, 12109);
+        let ktbredo = [0x01, 0x00, 0x00, 0x00, 0x0A, 0x00, 0x1B, 0x00, 0x4D, 0x2F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+        let key = [0x01, b'X', 0x02, 0xC1, 0x02];
+        let keydata = [0x2C, 0x00, 0x01, 0x03, b'a', b'b', b'c'];
+        let redo_data = testing::vector_bytes((10, 2), &[&ktbredo, &[0x02, 0x00, 0x05, 0x00], &key, &keydata]);
+        let undo_data = testing::index_undo_bytes(100, 101, xid, constants::KDILK_CODE_PURGE, &key, &[]);
+
+        let redo = testing::parse_vector(&mut parser, &redo_data);
+        match redo.data() {
+            VectorData::OpCode1002(redo) => {
+                assert_eq!(redo.xid, Some(xid));
+                assert_eq!((redo.itl, redo.slot), (2, 5));
+                assert_eq!(redo.key, vec![Some(b"X".as_slice()), Some([0xC1, 0x02].as_slice())]);
+                assert_eq!(redo.keydata, vec![Some(b"abc".as_slice())]);
+            },
+            data => panic!("Unexpected vector: {}", data),
+        };
+
+        let undo = testing::parse_vector(&mut parser, &undo_data);
+        builder_ptr.process_dml(10.into(), 1.into(), Default::default(), undo, redo, None, &LobContext::default())?;
+
+        let messages = output.take();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0]["OP"], "insert");
+        assert_eq!(messages[0]["DATA"], json!({"0": "Col 0: 1", "1": "Col 1: abc", "2": "Col 2: X"}));
+        assert_eq!(messages[0]["KEY"], json!({"CODE": "X", "ID": "1"}));
+        Ok(())
+    }
+}
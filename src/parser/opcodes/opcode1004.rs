@@ -0,0 +1,74 @@
+use super::{fields::{ktbredo::Ktbredo, VectorField}, VectorData, VectorParser};
+use crate::{common::{errors::Result, types::TypeXid}, olr_perr, parser::{byte_reader::ByteReader, parser_impl::Parser, record_reader::VectorReader}};
+
+/// Mark leaf row of index deleted. For index-organized table it is delete of row, values are in the undo vector.
+#[derive(Debug)]
+pub struct OpCode1004<'a> {
+    pub xid : Option<TypeXid>, // None when KTB redo has no XID
+    pub bdba : u32,
+    pub itl : u8,
+    pub slot : u16,
+    
+    reader : VectorReader<'a>,
+}
+
+impl<'a> OpCode1004<'a> {
+    pub fn new(parser : &mut Parser, reader : VectorReader<'a>) -> Result<Self> {
+        let mut res = Self {
+            xid : Default::default(),
+            bdba : reader.header.dba,
+            itl : Default::default(),
+            slot : Default::default(),
+            reader,
+        };
+        res.init(parser)?;
+        Ok(res)
+    }
+
+    fn init(&mut self, parser : &mut Parser) -> Result<()> {
+
+        match self.reader.next() {
+            Some(mut field_reader) => self.ktbredo(parser, &mut field_reader, 0),
+            None => olr_perr!("Expect ktb_redo field")
+        }?;
+        
+        match self.reader.next() {
+            Some(mut field_reader) => self.kdxlde(parser, &mut field_reader, 1)?,
+            None => return Ok(()),
+        }
+
+        Ok(())
+    }
+
+    fn ktbredo(&mut self, parser : &mut Parser, reader : &mut ByteReader, field_num : usize) -> Result<()> {
+        let ktbredo = Ktbredo::parse_from_reader(parser, &mut self.reader, reader, field_num)?;
+        self.xid = ktbredo.xid;
+        Ok(())
+    }
+
+    fn kdxlde(&mut self, parser : &mut Parser, reader : &mut ByteReader, field_num : usize) -> Result<()> {
+        if reader.data().len() < 4 {
+            return olr_perr!("Size of field {} < 4", reader.data().len());
+        }
+
+        self.itl = reader.read_u8()?;
+        reader.skip_bytes(1);
+        self.slot = reader.read_u16()?;
+
+        if parser.can_dump(1) {
+            parser.write_dump(format_args!("\n[Change {}; kdxlde - {}] itl: {} sno: {}\n", field_num, reader.data().len(), self.itl, self.slot))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> VectorParser<'a> for OpCode1004<'a> {
+    fn parse(parser : &mut Parser, reader : VectorReader<'a>) -> Result<VectorData<'a>> {
+        Ok(
+            VectorData::OpCode1004(
+                OpCode1004::new(parser, reader)?
+            )
+        )
+    }
+}
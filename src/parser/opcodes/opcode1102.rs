@@ -3,7 +3,7 @@ use crate::{common::{errors::Result, types::{TypeFb, TypeXid}}, olr_perr, parser
 
 #[derive(Debug)]
 pub struct OpCode1102<'a> {
-    pub xid : TypeXid,
+    pub xid : Option<TypeXid>, // None when KTB redo has no XID
     pub fb : TypeFb,
     pub cc : u8,
     pub size_delt : u16,
@@ -17,6 +17,8 @@ pub struct OpCode1102<'a> {
     pub bdba : u32,
     pub op : u8,
     pub flags : u8,
+    pub nrid : Option<(u32, u16)>,
+    pub compressed : Option<&'a [u8]>,
     
     reader : VectorReader<'a>,
 }
@@ -35,6 +37,8 @@ impl<'a> OpCode1102<'a> {
             bdba : Default::default(),
             op : Default::default(),
             flags : Default::default(),
+            nrid : None,
+            compressed : None,
             reader,
         };
         res.init(parser)?;
@@ -57,8 +61,12 @@ impl<'a> OpCode1102<'a> {
 
         self.data_field = 2;
         if let Some(mut field_reader) = self.reader.next() {
+            // Row of compressed block is kept in one field with size of row
             if field_reader.data().len() == self.size_delt as usize && self.cc != 1 {
-                std::unimplemented!("Compressed data");
+                if parser.can_dump(1) {
+                    parser.write_dump(format_args!("Compressed row - {}\n{}", field_reader.data().len(), field_reader.to_hex_dump()))?;
+                }
+                self.compressed = Some(field_reader.data());
             } else {
                 let mut nulls: u8 = 0;
                 ktb_opcode_reader.set_cursor(self.nulls_offset)?;
@@ -84,7 +92,7 @@ impl<'a> OpCode1102<'a> {
 
     fn ktbredo(&mut self, parser : &mut Parser, reader : &mut ByteReader, field_num : usize) -> Result<()> {
         let ktbredo = Ktbredo::parse_from_reader(parser, &mut self.reader, reader, field_num)?;
-        if let Some(xid) = ktbredo.xid { self.xid = xid; } 
+        self.xid = ktbredo.xid;
         Ok(())
     }
 
@@ -93,6 +101,7 @@ impl<'a> OpCode1102<'a> {
         self.bdba = kdoopcode.bdba;
         self.op = kdoopcode.op;
         self.flags = kdoopcode.flags;
+        self.nrid = kdoopcode.nrid;
 
         if let Some(fb) = kdoopcode.fb { self.fb = fb; }
         if let Some(cc) = kdoopcode.cc { self.cc = cc; }
@@ -124,3 +133,55 @@ impl<'a> VectorParser<'a> for OpCode1102<'a> {
         )
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{builder::{formats::{self, BuilderFormats}, lobs::LobContext}, common::{constants, errors::Result, testing, types::TypeXid}, oradefs::oracle_table::OracleTable, parser::opcodes::VectorData};
+
+    #[test]
+    fn test_compressed_row() -> Result<()> {
+        let xid = TypeXid::new(10, 27, 12109);
+        let (undo_data, _) = testing::insert_vectors_bytes(100, 101, xid, &[]);
+
+        // IRP of 3 columns with one field of size of row
+        let ktbredo = [0x01, 0x00, 0x00, 0x00, 0x0A, 0x00, 0x1B, 0x00, 0x4D, 0x2F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+        let mut irp = [0u8; 48];
+        irp[0 .. 4].copy_from_slice(&0x01000010u32.to_le_bytes());
+        irp[10] = constants::OP_IRP;
+        irp[16] = 0x2C;
+        irp[18] = 3;
+        irp[40] = 5;
+        let redo_data = testing::vector_bytes((11, 2), &[&ktbredo, &irp, &[0x2C, 0x01, 0x02, 0x10, 0x11]]);
+
+        for unknown_type in [formats::UNKNOWN_TYPE_SHOW, formats::UNKNOWN_TYPE_HIDE] {
+            let context_ptr = testing::context();
+            let metadata_ptr = testing::metadata(context_ptr.clone());
+            let (builder_ptr, output) = testing::builder_with_formats(context_ptr.clone(), metadata_ptr.clone(), BuilderFormats { unknown_type, ..testing::formats() });
+            let mut parser = testing::parser(context_ptr, builder_ptr.clone(), metadata_ptr.clone());
+            let columns = vec![testing::column(1, "ID", constants::SYS_COL_TYPE_NUMBER)];
+            testing::set_tables(&metadata_ptr, vec![(100, 101, OracleTable::new("SCOTT".to_string(), "T1".to_string(), columns, vec![]))]);
+
+            let redo = testing::parse_vector(&mut parser, &redo_data);
+            match redo.data() {
+                VectorData::OpCode1102(redo) => assert_eq!(redo.compressed, Some([0x2C, 0x01, 0x02, 0x10, 0x11].as_slice())),
+                data => panic!("Unexpected vector: {}", data),
+            };
+
+            let undo = testing::parse_vector(&mut parser, &undo_data);
+            builder_ptr.process_dml(10.into(), 1.into(), Default::default(), undo, redo, None, &LobContext::default())?;
+
+            // Values of compressed row are unknown, raw row is shown only on request
+            let messages = output.take();
+            if unknown_type == formats::UNKNOWN_TYPE_SHOW {
+                assert_eq!(messages.len(), 1);
+                assert_eq!(messages[0]["OP"], "unsupported");
+                assert_eq!(messages[0]["DML"], "insert");
+                assert_eq!(messages[0]["RAW"], "2C01021011");
+                assert_eq!(messages[0]["XID"], "0x000a.01b.00002f4d");
+            } else {
+                assert!(messages.is_empty());
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,146 @@
+use super::{fields::{kdoopcode::Kdoopcode, ktbredo::Ktbredo, VectorField}, RowColumn, VectorData, VectorParser};
+use crate::{common::{errors::Result, types::{TypeFb, TypeXid}}, olr_perr, parser::{byte_reader::ByteReader, parser_impl::Parser, record_reader::VectorReader}};
+
+/// Update row piece. Contains new values of changed columns only.
+#[derive(Debug)]
+pub struct OpCode1105<'a> {
+    pub xid : Option<TypeXid>, // None when KTB redo has no XID
+    pub fb : TypeFb,
+    pub cc : u8,
+    pub slot : u16,
+    pub nulls_offset : usize,
+
+    pub bdba : u32,
+    pub op : u8,
+    pub flags : u8,
+
+    pub columns : Vec<RowColumn<'a>>,
+    
+    reader : VectorReader<'a>,
+}
+
+impl<'a> OpCode1105<'a> {
+    pub fn new(parser : &mut Parser, reader : VectorReader<'a>) -> Result<Self> {
+        let mut res = Self {
+            xid : Default::default(),
+            fb : Default::default(),
+            cc : Default::default(),
+            slot : Default::default(),
+            nulls_offset : Default::default(),
+            bdba : Default::default(),
+            op : Default::default(),
+            flags : Default::default(),
+            columns : Vec::new(),
+            reader,
+        };
+        res.init(parser)?;
+        Ok(res)
+    }
+
+    fn init(&mut self, parser : &mut Parser) -> Result<()> {
+
+        match self.reader.next() {
+            Some(mut field_reader) => self.ktbredo(parser, &mut field_reader, 0),
+            None => olr_perr!("Expect ktb_redo field")
+        }?;
+        
+        let mut ktb_opcode_reader = if let Some(mut field_reader) = self.reader.next() {
+            self.kdo_opcode(parser, &mut field_reader, 1)?;
+            field_reader
+        } else {
+            return Ok(());
+        };
+
+        if self.flags & 128 != 0 {
+            self.columns = Kdoopcode::parse_columns_vector(parser, &mut self.reader, self.cc)?;
+            return Ok(());
+        }
+
+        let mut numbers_reader = match self.reader.next() {
+            Some(field_reader) => field_reader,
+            None => return Ok(()),
+        };
+
+        let mut nulls: u8 = 0;
+        ktb_opcode_reader.set_cursor(self.nulls_offset)?;
+        self.columns.reserve(self.cc as usize);
+        for (i, mask) in (0 .. self.cc).map(|i| (i, 1u8 << (i & 0b111))) {
+            if mask == 1 {
+                nulls = ktb_opcode_reader.read_u8()?;
+            }
+
+            let column_reader = self.reader.next()
+                .ok_or(olr_perr!("Expected column {} data field", i))?;
+            let col_num = numbers_reader.read_u16()? as usize;
+
+            if parser.can_dump(1) {
+                parser.dump_column(column_reader.data(), col_num, column_reader.data().len(), nulls & mask != 0)?;
+            }
+
+            self.columns.push((col_num, if nulls & mask != 0 { None } else { Some(column_reader.data()) }));
+        }
+
+        Ok(())
+    }
+
+    fn ktbredo(&mut self, parser : &mut Parser, reader : &mut ByteReader, field_num : usize) -> Result<()> {
+        let ktbredo = Ktbredo::parse_from_reader(parser, &mut self.reader, reader, field_num)?;
+        self.xid = ktbredo.xid;
+        Ok(())
+    }
+
+    fn kdo_opcode(&mut self, parser : &mut Parser, reader : &mut ByteReader, field_num : usize) -> Result<()> {
+        let kdoopcode = Kdoopcode::parse_from_reader(parser, &mut self.reader, reader, field_num)?;
+        self.bdba = kdoopcode.bdba;
+        self.op = kdoopcode.op;
+        self.flags = kdoopcode.flags;
+
+        if let Some(fb) = kdoopcode.fb { self.fb = fb; }
+        if let Some(cc) = kdoopcode.cc { self.cc = cc; }
+        if let Some(slot) = kdoopcode.slot { self.slot = slot; }
+        if let Some(nulls_offset) = kdoopcode.nulls_offset { self.nulls_offset = nulls_offset; }
+
+        Ok(())
+    }
+}
+
+impl<'a> VectorParser<'a> for OpCode1105<'a> {
+    fn parse(parser : &mut Parser, reader : VectorReader<'a>) -> Result<VectorData<'a>> {
+        Ok(
+            VectorData::OpCode1105(
+                OpCode1105::new(parser, reader)?
+            )
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{builder::formats, common::{testing, types::TypeXid}, parser::opcodes::VectorData};
+
+    #[test]
+    fn test_columns_vector() {
+        let context_ptr = testing::context();
+        let metadata_ptr = testing::metadata(context_ptr.clone());
+        let (builder_ptr, _) = testing::builder(context_ptr.clone(), metadata_ptr.clone(), formats::MESSAGE_FORMAT_DEFAULT);
+        let mut parser = testing::parser(context_ptr, builder_ptr, metadata_ptr);
+
+        // KTB redo with XID, URP with flag KDOM2 of 3 columns, first column number and columns vector
+        let ktbredo = [0x01, 0x00, 0x00, 0x00, 0x0A, 0x00, 0x1B, 0x00, 0x4D, 0x2F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+        let mut kdoopcode = [0u8; 28];
+        kdoopcode[10] = 0x05;
+        kdoopcode[11] = 0x80;
+        kdoopcode[23] = 3;
+        let data = testing::vector_bytes((11, 5), &[
+            &ktbredo, &kdoopcode, &[0x02, 0x00], &[0x02, 0xC1, 0x02, 0xFF, 0x01, 0x41],
+        ]);
+
+        let redo = match testing::parse_vector(&mut parser, &data).into_data() {
+            VectorData::OpCode1105(redo) => redo,
+            data => panic!("Unexpected vector: {}", data),
+        };
+
+        assert_eq!(redo.xid, Some(TypeXid::new(10, 27, 12109)));
+        assert_eq!(redo.columns, vec![(2, Some([0xC1, 0x02].as_slice())), (3, None), (4, Some(b"A".as_slice()))]);
+    }
+}
@@ -0,0 +1,107 @@
+use super::{fields::{kdoopcode::Kdoopcode, ktbredo::Ktbredo, VectorField}, RowColumn, VectorData, VectorParser};
+use crate::{common::{errors::Result, types::{TypeFb, TypeXid}}, olr_perr, parser::{byte_reader::ByteReader, parser_impl::Parser, record_reader::VectorReader}};
+
+/// Overwrite row piece. Contains all columns of piece, it is written when row is migrated by update.
+#[derive(Debug)]
+pub struct OpCode1106<'a> {
+    pub xid : Option<TypeXid>, // None when KTB redo has no XID
+    pub fb : TypeFb,
+    pub cc : u8,
+    pub slot : u16,
+    pub nulls_offset : usize,
+
+    pub bdba : u32,
+    pub op : u8,
+    pub flags : u8,
+    pub nrid : Option<(u32, u16)>,
+
+    pub columns : Vec<RowColumn<'a>>,
+    
+    reader : VectorReader<'a>,
+}
+
+impl<'a> OpCode1106<'a> {
+    pub fn new(parser : &mut Parser, reader : VectorReader<'a>) -> Result<Self> {
+        let mut res = Self {
+            xid : Default::default(),
+            fb : Default::default(),
+            cc : Default::default(),
+            slot : Default::default(),
+            nulls_offset : Default::default(),
+            bdba : Default::default(),
+            op : Default::default(),
+            flags : Default::default(),
+            nrid : None,
+            columns : Vec::new(),
+            reader,
+        };
+        res.init(parser)?;
+        Ok(res)
+    }
+
+    fn init(&mut self, parser : &mut Parser) -> Result<()> {
+
+        match self.reader.next() {
+            Some(mut field_reader) => self.ktbredo(parser, &mut field_reader, 0),
+            None => olr_perr!("Expect ktb_redo field")
+        }?;
+        
+        let mut ktb_opcode_reader = if let Some(mut field_reader) = self.reader.next() {
+            self.kdo_opcode(parser, &mut field_reader, 1)?;
+            field_reader
+        } else {
+            return Ok(());
+        };
+
+        let mut nulls: u8 = 0;
+        ktb_opcode_reader.set_cursor(self.nulls_offset)?;
+        self.columns.reserve(self.cc as usize);
+        for (i, mask) in (0 .. self.cc).map(|i| (i, 1u8 << (i & 0b111))) {
+            if mask == 1 {
+                nulls = ktb_opcode_reader.read_u8()?;
+            }
+
+            let column_reader = self.reader.next()
+                .ok_or(olr_perr!("Expected column {} data field", i))?;
+
+            if parser.can_dump(1) {
+                parser.dump_column(column_reader.data(), i as usize, column_reader.data().len(), nulls & mask != 0)?;
+            }
+
+            self.columns.push((i as usize, if nulls & mask != 0 { None } else { Some(column_reader.data()) }));
+        }
+
+        Ok(())
+    }
+
+    fn ktbredo(&mut self, parser : &mut Parser, reader : &mut ByteReader, field_num : usize) -> Result<()> {
+        let ktbredo = Ktbredo::parse_from_reader(parser, &mut self.reader, reader, field_num)?;
+        self.xid = ktbredo.xid;
+        Ok(())
+    }
+
+    fn kdo_opcode(&mut self, parser : &mut Parser, reader : &mut ByteReader, field_num : usize) -> Result<()> {
+        let kdoopcode = Kdoopcode::parse_from_reader(parser, &mut self.reader, reader, field_num)?;
+        self.bdba = kdoopcode.bdba;
+        self.op = kdoopcode.op;
+        self.flags = kdoopcode.flags;
+        self.nrid = kdoopcode.nrid;
+
+        if let Some(fb) = kdoopcode.fb { self.fb = fb; }
+        if let Some(cc) = kdoopcode.cc { self.cc = cc; }
+        if let Some(slot) = kdoopcode.slot { self.slot = slot; }
+        if let Some(nulls_offset) = kdoopcode.nulls_offset { self.nulls_offset = nulls_offset; }
+
+        Ok(())
+    }
+}
+
+impl<'a> VectorParser<'a> for OpCode1106<'a> {
+    fn parse(parser : &mut Parser, reader : VectorReader<'a>) -> Result<VectorData<'a>> {
+        Ok(
+            VectorData::OpCode1106(
+                OpCode1106::new(parser, reader)?
+            )
+        )
+    }
+}
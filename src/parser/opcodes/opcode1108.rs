@@ -0,0 +1,71 @@
+use super::{fields::{kdoopcode::Kdoopcode, ktbredo::Ktbredo, VectorField}, VectorData, VectorParser};
+use crate::{common::{errors::Result, types::TypeXid}, olr_perr, parser::{byte_reader::ByteReader, parser_impl::Parser, record_reader::VectorReader}};
+
+/// Change forwarding address of migrated row. Row data is not changed.
+#[derive(Debug)]
+pub struct OpCode1108<'a> {
+    pub xid : Option<TypeXid>, // None when KTB redo has no XID
+    pub slot : u16,
+
+    pub bdba : u32,
+    pub op : u8,
+    pub flags : u8,
+    
+    reader : VectorReader<'a>,
+}
+
+impl<'a> OpCode1108<'a> {
+    pub fn new(parser : &mut Parser, reader : VectorReader<'a>) -> Result<Self> {
+        let mut res = Self {
+            xid : Default::default(),
+            slot : Default::default(),
+            bdba : Default::default(),
+            op : Default::default(),
+            flags : Default::default(),
+            reader,
+        };
+        res.init(parser)?;
+        Ok(res)
+    }
+
+    fn init(&mut self, parser : &mut Parser) -> Result<()> {
+
+        match self.reader.next() {
+            Some(mut field_reader) => self.ktbredo(parser, &mut field_reader, 0),
+            None => olr_perr!("Expect ktb_redo field")
+        }?;
+        
+        if let Some(mut field_reader) = self.reader.next() {
+            self.kdo_opcode(parser, &mut field_reader, 1)?;
+        }
+
+        Ok(())
+    }
+
+    fn ktbredo(&mut self, parser : &mut Parser, reader : &mut ByteReader, field_num : usize) -> Result<()> {
+        let ktbredo = Ktbredo::parse_from_reader(parser, &mut self.reader, reader, field_num)?;
+        self.xid = ktbredo.xid;
+        Ok(())
+    }
+
+    fn kdo_opcode(&mut self, parser : &mut Parser, reader : &mut ByteReader, field_num : usize) -> Result<()> {
+        let kdoopcode = Kdoopcode::parse_from_reader(parser, &mut self.reader, reader, field_num)?;
+        self.bdba = kdoopcode.bdba;
+        self.op = kdoopcode.op;
+        self.flags = kdoopcode.flags;
+
+        if let Some(slot) = kdoopcode.slot { self.slot = slot; }
+
+        Ok(())
+    }
+}
+
+impl<'a> VectorParser<'a> for OpCode1108<'a> {
+    fn parse(parser : &mut Parser, reader : VectorReader<'a>) -> Result<VectorData<'a>> {
+        Ok(
+            VectorData::OpCode1108(
+                OpCode1108::new(parser, reader)?
+            )
+        )
+    }
+}
@@ -0,0 +1,125 @@
+use super::{fields::{kdoopcode::{KdoRow, Kdoopcode}, ktbredo::Ktbredo, VectorField}, VectorData, VectorParser};
+use crate::{common::{errors::Result, types::TypeXid}, olr_perr, parser::{byte_reader::ByteReader, parser_impl::Parser, record_reader::VectorReader}};
+
+/// Insert multiple rows. It is written by array inserts and inserts into compressed tables.
+#[derive(Debug)]
+pub struct OpCode1111<'a> {
+    pub xid : Option<TypeXid>, // None when KTB redo has no XID
+    pub nrow : u8,
+    pub slots_offset : usize,
+
+    pub bdba : u32,
+    pub op : u8,
+    pub flags : u8,
+
+    pub rows : Vec<KdoRow<'a>>,
+    
+    reader : VectorReader<'a>,
+}
+
+impl<'a> OpCode1111<'a> {
+    pub fn new(parser : &mut Parser, reader : VectorReader<'a>) -> Result<Self> {
+        let mut res = Self {
+            xid : Default::default(),
+            nrow : Default::default(),
+            slots_offset : Default::default(),
+            bdba : Default::default(),
+            op : Default::default(),
+            flags : Default::default(),
+            rows : Vec::new(),
+            reader,
+        };
+        res.init(parser)?;
+        Ok(res)
+    }
+
+    fn init(&mut self, parser : &mut Parser) -> Result<()> {
+
+        match self.reader.next() {
+            Some(mut field_reader) => self.ktbredo(parser, &mut field_reader, 0),
+            None => olr_perr!("Expect ktb_redo field")
+        }?;
+        
+        let mut ktb_opcode_reader = if let Some(mut field_reader) = self.reader.next() {
+            self.kdo_opcode(parser, &mut field_reader, 1)?;
+            field_reader
+        } else {
+            return Ok(());
+        };
+
+        ktb_opcode_reader.set_cursor(self.slots_offset)?;
+        self.rows = Kdoopcode::parse_rows(parser, &mut self.reader, &mut ktb_opcode_reader, self.op, self.nrow)?;
+
+        Ok(())
+    }
+
+    fn ktbredo(&mut self, parser : &mut Parser, reader : &mut ByteReader, field_num : usize) -> Result<()> {
+        let ktbredo = Ktbredo::parse_from_reader(parser, &mut self.reader, reader, field_num)?;
+        self.xid = ktbredo.xid;
+        Ok(())
+    }
+
+    fn kdo_opcode(&mut self, parser : &mut Parser, reader : &mut ByteReader, field_num : usize) -> Result<()> {
+        let kdoopcode = Kdoopcode::parse_from_reader(parser, &mut self.reader, reader, field_num)?;
+        self.bdba = kdoopcode.bdba;
+        self.op = kdoopcode.op;
+        self.flags = kdoopcode.flags;
+
+        if let Some(nrow) = kdoopcode.nrow { self.nrow = nrow; }
+        if let Some(slots_offset) = kdoopcode.slots_offset { self.slots_offset = slots_offset; }
+
+        Ok(())
+    }
+}
+
+impl<'a> VectorParser<'a> for OpCode1111<'a> {
+    fn parse(parser : &mut Parser, reader : VectorReader<'a>) -> Result<VectorData<'a>> {
+        Ok(
+            VectorData::OpCode1111(
+                OpCode1111::new(parser, reader)?
+            )
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{builder::formats, common::{constants, testing, types::TypeXid}, parser::{byte_reader::ByteReader, opcodes::{Vector, VectorData}}};
+
+    #[test]
+    fn test_multi_insert() {
+        let context_ptr = testing::context();
+        let metadata_ptr = testing::metadata(context_ptr.clone());
+        let (builder_ptr, _) = testing::builder(context_ptr.clone(), metadata_ptr.clone(), formats::MESSAGE_FORMAT_DEFAULT);
+        let mut parser = testing::parser(context_ptr, builder_ptr, metadata_ptr);
+
+        // KTB redo with XID, QMI of 2 rows with their slots, sizes of rows and rows data
+        let ktbredo = [0x01, 0x00, 0x00, 0x00, 0x0A, 0x00, 0x1B, 0x00, 0x4D, 0x2F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+        let mut kdoopcode = [0u8; 24];
+        kdoopcode[0 .. 4].copy_from_slice(&0x01000010u32.to_le_bytes());
+        kdoopcode[10] = constants::OP_QMI;
+        kdoopcode[18] = 2;
+        kdoopcode[20 .. 24].copy_from_slice(&[0x03, 0x00, 0x07, 0x00]);
+        let rows = [0x2C, 0x00, 0x02, 0x02, 0xC1, 0x02, 0xFF, 
+                    0x2C, 0x00, 0x02, 0x02, 0xC1, 0x03, 0x01, 0x41];
+        let data = testing::vector_bytes((11, 11), &[&ktbredo, &kdoopcode, &[0x07, 0x00, 0x08, 0x00], &rows]);
+
+        let redo = match testing::parse_vector(&mut parser, &data).into_data() {
+            VectorData::OpCode1111(redo) => redo,
+            data => panic!("Unexpected vector: {}", data),
+        };
+
+        assert_eq!(redo.xid, Some(TypeXid::new(10, 27, 12109)));
+        assert_eq!(redo.bdba, 0x01000010);
+        assert_eq!(redo.rows.len(), 2);
+        assert_eq!(redo.rows[0].slot, 3);
+        assert_eq!(redo.rows[0].columns, vec![(0, Some([0xC1, 0x02].as_slice())), (1, None)]);
+        assert_eq!(redo.rows[1].slot, 7);
+        assert_eq!(redo.rows[1].columns, vec![(0, Some([0xC1, 0x03].as_slice())), (1, Some(b"A".as_slice()))]);
+
+        // Sizes of rows do not match data
+        let data = testing::vector_bytes((11, 11), &[&ktbredo, &kdoopcode, &[0x07, 0x00, 0x08, 0x00], &rows[.. 12]]);
+        let mut reader = ByteReader::from_bytes(&data);
+        assert!(Vector::parse(&mut parser, &mut reader, constants::REDO_VERSION_19_0).is_err());
+    }
+}
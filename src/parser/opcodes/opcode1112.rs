@@ -0,0 +1,128 @@
+use super::{fields::{kdoopcode::Kdoopcode, ktbredo::Ktbredo, VectorField}, VectorData, VectorParser};
+use crate::{common::{errors::Result, types::TypeXid}, olr_perr, parser::{byte_reader::ByteReader, parser_impl::Parser, record_reader::VectorReader}};
+
+/// Delete multiple rows. Deleted values are in the undo vector.
+#[derive(Debug)]
+pub struct OpCode1112<'a> {
+    pub xid : Option<TypeXid>, // None when KTB redo has no XID
+    pub nrow : u8,
+
+    pub bdba : u32,
+    pub op : u8,
+    pub flags : u8,
+    
+    reader : VectorReader<'a>,
+}
+
+impl<'a> OpCode1112<'a> {
+    pub fn new(parser : &mut Parser, reader : VectorReader<'a>) -> Result<Self> {
+        let mut res = Self {
+            xid : Default::default(),
+            nrow : Default::default(),
+            bdba : Default::default(),
+            op : Default::default(),
+            flags : Default::default(),
+            reader,
+        };
+        res.init(parser)?;
+        Ok(res)
+    }
+
+    fn init(&mut self, parser : &mut Parser) -> Result<()> {
+
+        match self.reader.next() {
+            Some(mut field_reader) => self.ktbredo(parser, &mut field_reader, 0),
+            None => olr_perr!("Expect ktb_redo field")
+        }?;
+        
+        if let Some(mut field_reader) = self.reader.next() {
+            self.kdo_opcode(parser, &mut field_reader, 1)?;
+        }
+
+        Ok(())
+    }
+
+    fn ktbredo(&mut self, parser : &mut Parser, reader : &mut ByteReader, field_num : usize) -> Result<()> {
+        let ktbredo = Ktbredo::parse_from_reader(parser, &mut self.reader, reader, field_num)?;
+        self.xid = ktbredo.xid;
+        Ok(())
+    }
+
+    fn kdo_opcode(&mut self, parser : &mut Parser, reader : &mut ByteReader, field_num : usize) -> Result<()> {
+        let kdoopcode = Kdoopcode::parse_from_reader(parser, &mut self.reader, reader, field_num)?;
+        self.bdba = kdoopcode.bdba;
+        self.op = kdoopcode.op;
+        self.flags = kdoopcode.flags;
+
+        if let Some(nrow) = kdoopcode.nrow { self.nrow = nrow; }
+
+        Ok(())
+    }
+}
+
+impl<'a> VectorParser<'a> for OpCode1112<'a> {
+    fn parse(parser : &mut Parser, reader : VectorReader<'a>) -> Result<VectorData<'a>> {
+        Ok(
+            VectorData::OpCode1112(
+                OpCode1112::new(parser, reader)?
+            )
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use crate::{builder::{formats, lobs::LobContext}, common::{constants, errors::Result, testing, types::TypeXid}, oradefs::oracle_table::OracleTable, parser::opcodes::VectorData};
+
+    #[test]
+    fn test_multi_delete() -> Result<()> {
+        let context_ptr = testing::context();
+        let metadata_ptr = testing::metadata(context_ptr.clone());
+        let (builder_ptr, output) = testing::builder(context_ptr.clone(), metadata_ptr.clone(), formats::MESSAGE_FORMAT_DEFAULT);
+        let mut parser = testing::parser(context_ptr, builder_ptr.clone(), metadata_ptr.clone());
+
+        let columns = vec![testing::column(1, "ID", constants::SYS_COL_TYPE_NUMBER), testing::column(2, "A", constants::SYS_COL_TYPE_VARCHAR)];
+        testing::set_tables(&metadata_ptr, vec![(100, 101, OracleTable::new("SCOTT".to_string(), "T1".to_string(), columns, vec![]))]);
+
+        // QMD of 2 rows with their slots, undo has QMI with deleted rows
+        let mut qm = [0u8; 24];
+        qm[0 .. 4].copy_from_slice(&0x01000010u32.to_le_bytes());
+        qm[10] = constants::OP_QMD;
+        qm[18] = 2;
+        qm[20 .. 24].copy_from_slice(&[0x03, 0x00, 0x07, 0x00]);
+
+        let ktbredo = [0x01, 0x00, 0x00, 0x00, 0x0A, 0x00, 0x1B, 0x00, 0x4D, 0x2F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+        let redo_data = testing::vector_bytes((11, 12), &[&ktbredo, &qm]);
+
+        let mut ktudb = [0u8; 20];
+        ktudb[8 .. 16].copy_from_slice(&ktbredo[4 .. 12]);
+        let mut ktub = [0u8; 24];
+        ktub[0 .. 8].copy_from_slice(&[100, 0, 0, 0, 101, 0, 0, 0]);
+        ktub[16 .. 18].copy_from_slice(&[11, 1]);
+        qm[10] = constants::OP_QMI;
+        let rows = [0x2C, 0x00, 0x02, 0x02, 0xC1, 0x02, 0xFF, 
+                    0x2C, 0x00, 0x02, 0x02, 0xC1, 0x03, 0x01, 0x41];
+        let undo_data = testing::vector_bytes((5, 1), &[&ktudb, &ktub, &[constants::KTBOP_Z, 0, 0, 0, 0, 0, 0, 0], &qm, &[0x07, 0x00, 0x08, 0x00], &rows]);
+
+        let redo = testing::parse_vector(&mut parser, &redo_data);
+        match redo.data() {
+            VectorData::OpCode1112(redo) => {
+                assert_eq!(redo.xid, Some(TypeXid::new(10, 27, 12109)));
+                assert_eq!((redo.bdba, redo.nrow), (0x01000010, 2));
+            },
+            data => panic!("Unexpected vector: {}", data),
+        };
+
+        let undo = testing::parse_vector(&mut parser, &undo_data);
+        builder_ptr.process_dml(10.into(), 1.into(), Default::default(), undo, redo, None, &LobContext::default())?;
+
+        let messages = output.take();
+        assert_eq!(messages.len(), 2);
+        assert!(messages.iter().all(|x| x["OP"] == "delete" && x["XID"] == "0x000a.01b.00002f4d"));
+        assert_eq!(messages[0]["BEFORE"], json!({"0": "Col 0: 1", "1": "Col 1: Null"}));
+        assert_eq!(messages[1]["BEFORE"], json!({"0": "Col 0: 2", "1": "Col 1: A"}));
+        Ok(())
+    }
+}
@@ -0,0 +1,61 @@
+use super::{fields::kdli::LobPage, VectorData, VectorParser};
+use crate::{common::{errors::Result, types::TypeLobId}, parser::{byte_reader::ByteReader, parser_impl::Parser, record_reader::VectorReader}};
+
+const BLOCK_HEADER_SIZE : usize = 20;
+const LOB_HEADER_SIZE : usize = 56;
+
+/// Direct block logging. Image of LOB page written bypassing buffer cache: cache header is followed
+/// by LOB data header with the same layout as kdli "load data" structure and data of page.
+#[derive(Debug)]
+pub struct OpCode1301<'a> {
+    pub page : LobPage<'a>,
+    
+    reader : VectorReader<'a>,
+}
+
+impl<'a> OpCode1301<'a> {
+    pub fn new(parser : &mut Parser, reader : VectorReader<'a>) -> Result<Self> {
+        let mut res = Self {
+            page : Default::default(),
+            reader,
+        };
+        res.init(parser)?;
+        Ok(res)
+    }
+
+    fn init(&mut self, parser : &mut Parser) -> Result<()> {
+        if let Some(mut field_reader) = self.reader.next() {
+            self.block(parser, &mut field_reader, 0)?;
+        }
+
+        Ok(())
+    }
+
+    fn block(&mut self, parser : &mut Parser, reader : &mut ByteReader<'a>, field_num : usize) -> Result<()> {
+        let data = reader.data();
+        if data.len() < BLOCK_HEADER_SIZE + LOB_HEADER_SIZE {
+            return Ok(());
+        }
+
+        reader.set_cursor(BLOCK_HEADER_SIZE + 24)?;
+        self.page.lob_id = TypeLobId::from_bytes(&data[BLOCK_HEADER_SIZE + 12 ..]);
+        self.page.page_no = reader.read_u32()?;
+        self.page.data = Some(&data[BLOCK_HEADER_SIZE + LOB_HEADER_SIZE ..]);
+
+        if parser.can_dump(1) {
+            parser.write_dump(format_args!("\n[Change {}; Block - {}] LOB ID: {} page: {}\n", field_num, data.len(), self.page.lob_id.unwrap(), self.page.page_no))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> VectorParser<'a> for OpCode1301<'a> {
+    fn parse(parser : &mut Parser, reader : VectorReader<'a>) -> Result<VectorData<'a>> {
+        Ok(
+            VectorData::OpCode1301(
+                OpCode1301::new(parser, reader)?
+            )
+        )
+    }
+}
@@ -0,0 +1,183 @@
+use super::{VectorData, VectorParser};
+use crate::{common::{errors::Result, types::TypeXid}, olr_perr, parser::{byte_reader::ByteReader, parser_impl::Parser, record_reader::VectorReader}};
+
+pub const DDL_TYPE_CREATE_TABLE : u16 = 1;
+pub const DDL_TYPE_DROP_TABLE : u16 = 12;
+pub const DDL_TYPE_ALTER_TABLE : u16 = 15;
+pub const DDL_TYPE_TRUNCATE_TABLE : u16 = 85;
+pub const DDL_TYPE_TRUNCATE_PARTITION : u16 = 86;
+
+#[derive(Debug)]
+pub struct OpCode1801<'a> {
+    pub xid : TypeXid,
+    pub ddl_type : u16,
+    pub tmp : u16,
+    pub owner : String,
+    pub sql_text : String,
+    pub obj : u32,
+
+    reader : VectorReader<'a>,
+}
+
+impl<'a> OpCode1801<'a> {
+    pub fn new(parser : &mut Parser, reader : VectorReader<'a>) -> Result<Self> {
+        let mut res = Self {
+            xid : Default::default(),
+            ddl_type : Default::default(),
+            tmp : Default::default(),
+            owner : Default::default(),
+            sql_text : Default::default(),
+            obj : Default::default(),
+            reader,
+        };
+        res.init(parser)?;
+        Ok(res)
+    }
+
+    /// DDL which changes layout or content of a table
+    pub fn is_table_ddl(&self) -> bool {
+        let is_temporary = matches!(self.tmp, 4 | 5 | 6 | 8 | 9 | 10);
+        
+        !is_temporary && matches!(self.ddl_type, DDL_TYPE_CREATE_TABLE | DDL_TYPE_DROP_TABLE | DDL_TYPE_ALTER_TABLE | 
+                                                 DDL_TYPE_TRUNCATE_TABLE | DDL_TYPE_TRUNCATE_PARTITION)
+    }
+
+    pub fn is_truncate(&self) -> bool {
+        matches!(self.ddl_type, DDL_TYPE_TRUNCATE_TABLE | DDL_TYPE_TRUNCATE_PARTITION)
+    }
+
+    pub fn ddl_type_name(&self) -> &'static str {
+        match self.ddl_type {
+            DDL_TYPE_CREATE_TABLE => "create table",
+            DDL_TYPE_DROP_TABLE => "drop table",
+            DDL_TYPE_ALTER_TABLE => "alter table",
+            DDL_TYPE_TRUNCATE_TABLE => "truncate table",
+            DDL_TYPE_TRUNCATE_PARTITION => "truncate partition",
+            _ => "unknown",
+        }
+    }
+
+    fn init(&mut self, parser : &mut Parser) -> Result<()> {
+        match self.reader.next() {
+            Some(mut field_reader) => self.ddl_header(parser, &mut field_reader, 0),
+            None => olr_perr!("Expect ddl header field"),
+        }?;
+
+        // Fields 1 .. 6: session and object info, only owner is needed
+        for field_num in 1 .. 7 {
+            match self.reader.next() {
+                Some(mut field_reader) if field_num == 3 => self.ddl_owner(parser, &mut field_reader, field_num)?,
+                Some(_) => (),
+                None => return Ok(()),
+            }
+        }
+
+        if let Some(mut field_reader) = self.reader.next() {
+            self.ddl_sql_text(parser, &mut field_reader, 7)?;
+        }
+
+        // Fields 8 .. 10 are skipped, object number is in field 11
+        for field_num in 8 .. 12 {
+            match self.reader.next() {
+                Some(mut field_reader) if field_num == 11 => self.ddl_obj(parser, &mut field_reader, field_num)?,
+                Some(_) => (),
+                None => return Ok(()),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn ddl_header(&mut self, parser : &mut Parser, reader : &mut ByteReader, field_num : usize) -> Result<()> {
+        if reader.data().len() < 18 {
+            return olr_perr!("Size of ddl header field {} < 18. Dump: {}", reader.data().len(), reader.to_hex_dump());
+        }
+
+        reader.skip_bytes(4);
+        let usn = reader.read_u16()?;
+        let slt = reader.read_u16()?;
+        let seq = reader.read_u32()?;
+        self.xid = TypeXid::new(usn, slt, seq);
+        self.ddl_type = reader.read_u16()?;
+        reader.skip_bytes(2);
+        self.tmp = reader.read_u16()?;
+
+        if parser.can_dump(1) {
+            parser.write_dump(format_args!("\n[Change {}; DDL] XID: {} Type: {} ({}) Tmp: {}\n", field_num, self.xid, self.ddl_type, self.ddl_type_name(), self.tmp))?;
+        }
+
+        Ok(())
+    }
+
+    fn ddl_owner(&mut self, parser : &mut Parser, reader : &mut ByteReader, field_num : usize) -> Result<()> {
+        self.owner = String::from_utf8(reader.read_bytes(reader.data().len())?).unwrap_or_default();
+
+        if parser.can_dump(1) {
+            parser.write_dump(format_args!("\n[Change {}; DDL] Owner: {}\n", field_num, self.owner))?;
+        }
+
+        Ok(())
+    }
+
+    fn ddl_sql_text(&mut self, parser : &mut Parser, reader : &mut ByteReader, field_num : usize) -> Result<()> {
+        let data = reader.read_bytes(reader.data().len())?;
+        // Sql text is null terminated
+        let length = data.iter().position(|x| *x == 0).unwrap_or(data.len());
+        self.sql_text = String::from_utf8_lossy(&data[.. length]).into_owned();
+
+        if parser.can_dump(1) {
+            parser.write_dump(format_args!("\n[Change {}; DDL] Sql: {}\n", field_num, self.sql_text))?;
+        }
+
+        Ok(())
+    }
+
+    fn ddl_obj(&mut self, parser : &mut Parser, reader : &mut ByteReader, field_num : usize) -> Result<()> {
+        if reader.data().len() < 4 {
+            return olr_perr!("Size of ddl object field {} < 4", reader.data().len());
+        }
+
+        self.obj = reader.read_u32()?;
+
+        if parser.can_dump(1) {
+            parser.write_dump(format_args!("\n[Change {}; DDL] OBJ: {}\n", field_num, self.obj))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> VectorParser<'a> for OpCode1801<'a> {
+    fn parse(parser : &mut Parser, reader : VectorReader<'a>) -> Result<VectorData<'a>> {
+        Ok(
+            VectorData::OpCode1801(
+                OpCode1801::new(parser, reader)?
+            )
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{builder::formats, common::{testing, types::TypeXid}, parser::opcodes::VectorData};
+
+    #[test]
+    fn test_truncate_ddl() {
+        let context_ptr = testing::context();
+        let metadata_ptr = testing::metadata(context_ptr.clone());
+        let (builder_ptr, _) = testing::builder(context_ptr.clone(), metadata_ptr.clone(), formats::MESSAGE_FORMAT_DEFAULT);
+        let mut parser = testing::parser(context_ptr, builder_ptr, metadata_ptr);
+
+        let data = testing::truncate_vector_bytes(0x1126B);
+        let ddl = match testing::parse_vector(&mut parser, &data).into_data() {
+            VectorData::OpCode1801(ddl) => ddl,
+            data => panic!("Unexpected vector: {}", data),
+        };
+
+        assert_eq!(ddl.xid, TypeXid::new(10, 27, 12109));
+        assert!(ddl.is_truncate());
+        assert_eq!(ddl.owner, "SCOTT");
+        assert_eq!(ddl.sql_text, "truncate table T1");
+        assert_eq!(ddl.obj, 0x1126B);
+    }
+}
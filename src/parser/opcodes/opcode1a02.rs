@@ -0,0 +1,51 @@
+use super::{fields::{kdli::{Kdli, LobPage}, ktbredo::Ktbredo, VectorField}, VectorData, VectorParser};
+use crate::{common::{errors::Result, types::TypeXid}, olr_perr, parser::{byte_reader::ByteReader, parser_impl::Parser, record_reader::VectorReader}};
+
+/// Generic LOB redo. Carries LOB id and data of LOB page written in transaction.
+#[derive(Debug)]
+pub struct OpCode1A02<'a> {
+    pub xid : Option<TypeXid>, // None when KTB redo has no XID
+    pub page : LobPage<'a>,
+    
+    reader : VectorReader<'a>,
+}
+
+impl<'a> OpCode1A02<'a> {
+    pub fn new(parser : &mut Parser, reader : VectorReader<'a>) -> Result<Self> {
+        let mut res = Self {
+            xid : Default::default(),
+            page : Default::default(),
+            reader,
+        };
+        res.init(parser)?;
+        Ok(res)
+    }
+
+    fn init(&mut self, parser : &mut Parser) -> Result<()> {
+
+        match self.reader.next() {
+            Some(mut field_reader) => self.ktbredo(parser, &mut field_reader, 0),
+            None => olr_perr!("Expect ktb_redo field")
+        }?;
+
+        self.page = Kdli::parse_fields(parser, &mut self.reader, 1)?;
+
+        Ok(())
+    }
+
+    fn ktbredo(&mut self, parser : &mut Parser, reader : &mut ByteReader, field_num : usize) -> Result<()> {
+        let ktbredo = Ktbredo::parse_from_reader(parser, &mut self.reader, reader, field_num)?;
+        self.xid = ktbredo.xid;
+        Ok(())
+    }
+}
+
+impl<'a> VectorParser<'a> for OpCode1A02<'a> {
+    fn parse(parser : &mut Parser, reader : VectorReader<'a>) -> Result<VectorData<'a>> {
+        Ok(
+            VectorData::OpCode1A02(
+                OpCode1A02::new(parser, reader)?
+            )
+        )
+    }
+}
@@ -0,0 +1,36 @@
+use super::{fields::kdli::{Kdli, LobPage}, VectorData, VectorParser};
+use crate::{common::errors::Result, parser::{parser_impl::Parser, record_reader::VectorReader}};
+
+/// Direct-load LOB redo. It has no transaction id, LOB id links it with transaction.
+#[derive(Debug)]
+pub struct OpCode1A06<'a> {
+    pub page : LobPage<'a>,
+    
+    reader : VectorReader<'a>,
+}
+
+impl<'a> OpCode1A06<'a> {
+    pub fn new(parser : &mut Parser, reader : VectorReader<'a>) -> Result<Self> {
+        let mut res = Self {
+            page : Default::default(),
+            reader,
+        };
+        res.init(parser)?;
+        Ok(res)
+    }
+
+    fn init(&mut self, parser : &mut Parser) -> Result<()> {
+        self.page = Kdli::parse_fields(parser, &mut self.reader, 0)?;
+        Ok(())
+    }
+}
+
+impl<'a> VectorParser<'a> for OpCode1A06<'a> {
+    fn parse(parser : &mut Parser, reader : VectorReader<'a>) -> Result<VectorData<'a>> {
+        Ok(
+            VectorData::OpCode1A06(
+                OpCode1A06::new(parser, reader)?
+            )
+        )
+    }
+}
@@ -10,16 +10,19 @@ use std::path::PathBuf;
 use crossbeam::channel::Receiver;
 use log::{info, trace, warn};
 
-use crate::builder::JsonBuilder;
+use crate::builder::{lobs::LobContext, JsonBuilder};
+use crate::common::constants;
 use crate::common::thread::spawn;
-use crate::common::types::TypeTimestamp;
+use crate::common::types::{TypeRedoPosition, TypeScn, TypeTimestamp};
 use crate::ctx::Ctx;
+use crate::metadata;
 use crate::olr_perr;
 use crate::parser::archive_structs::record_header::RecordHeader;
 use crate::parser::fs_reader::{Reader, ReaderMessage};
-use crate::parser::opcodes::{Vector, VectorKind};
+use crate::parser::opcodes::{Vector, VectorData, VectorKind};
 use crate::parser::record_analizer::RecordAnalizer;
 use crate::parser::records_manager::Record;
+use crate::transactions::transaction::{SessionAttributes, Transaction};
 use crate::transactions::transaction_buffer::TransactionBuffer;
 use crate::{common::types::TypeSeq, olr_err};
 use crate::common::errors::OLRErrorCode::*;
@@ -33,15 +36,19 @@ use super::records_manager::RecordsManager;
 pub struct Parser {
     context_ptr : Arc<Ctx>,
     builder_ptr : Arc<JsonBuilder>,
+    metadata_ptr : Arc<metadata::Metadata>,
     transaction_buffer : Arc<Mutex<TransactionBuffer>>,
     file_path : PathBuf,
     sequence : TypeSeq,
 
     block_size      : Option<usize>,
     version         : Option<u32>,
+    next_scn        : Option<TypeScn>,
     endian          : Option<byte_reader::Endian>,
     metadata        : Option<Metadata>,
     dump_file       : Option<File>,
+    is_replaying    : bool,
+    parsed_size     : u64,
 
     records_manager : RecordsManager,
 }
@@ -70,6 +77,8 @@ impl Ord for Parser {
     }
 }
 
+const RECORD_HEADER_SIZE : usize = 24;
+
 struct ParserState<'a> {
     pub to_read : usize,
     pub start_block : usize,
@@ -81,22 +90,26 @@ struct ParserState<'a> {
 }
 
 impl Parser {
-    pub fn new(context_ptr : Arc<Ctx>, builder_ptr : Arc<JsonBuilder>, transaction_buffer : Arc<Mutex<TransactionBuffer>>, file_path : PathBuf, sequence : TypeSeq) -> Result<Self> {
+    pub fn new(context_ptr : Arc<Ctx>, builder_ptr : Arc<JsonBuilder>, metadata_ptr : Arc<metadata::Metadata>, transaction_buffer : Arc<Mutex<TransactionBuffer>>, file_path : PathBuf, sequence : TypeSeq) -> Result<Self> {
         let mut result = Self {
             context_ptr: context_ptr.clone(), 
             builder_ptr,
+            metadata_ptr,
             transaction_buffer,
             file_path, 
             sequence,
             block_size : None,
             version : None,
+            next_scn : None,
             endian : None, 
             metadata : None,
             dump_file : None,
+            is_replaying : false,
+            parsed_size : 0,
             records_manager : RecordsManager::new(context_ptr.clone()),
         };
 
-        if context_ptr.dump.level > 0 {
+        if context_ptr.dump.level > 0 || result.metadata_ptr.has_dump_xids() {
             let directory_path: PathBuf = PathBuf::new().join(context_ptr.dump.path.as_str());
             let dump_file_path: PathBuf = directory_path.join(format!("dump-{}.ansi", sequence));
             if let Err(err) = std::fs::create_dir_all(&directory_path) {
@@ -112,7 +125,8 @@ impl Parser {
     }
 
     pub fn can_dump(&self, level : u64) -> bool {
-        level <= self.context_ptr.dump.level
+        // Vectors replayed on commit were dumped when they were read
+        level <= self.context_ptr.dump.level && !self.is_replaying
     }
 
     pub fn write_dump(&mut self, fmt: fmt::Arguments<'_>) -> Result<()> {
@@ -141,6 +155,15 @@ impl Parser {
         self.version
     }
 
+    /// Bytes of redo file which were read by parser
+    pub fn parsed_size(&self) -> u64 {
+        self.parsed_size
+    }
+
+    pub fn next_scn(&self) -> Option<TypeScn> {
+        self.next_scn
+    }
+
     fn start_reader(&self) -> Result<(Receiver<ReaderMessage>, JoinHandle<Result<()>>)> {
         let (sx, rx) = self.context_ptr.get_reader_channel();
         let fs_reader = Reader::new(self.context_ptr.clone(), self.file_path.clone(), sx);
@@ -181,6 +204,7 @@ impl Parser {
             let (chunk, blocks_count) = match message {
                 ReaderMessage::Read(chunk, size) => {
                     assert!(size >= 512);
+                    self.parsed_size += size as u64;
                     (chunk, size / self.block_size.unwrap())
                 },
                 ReaderMessage::Eof => break,
@@ -216,16 +240,38 @@ impl Parser {
             if self.can_dump(1) {
                 self.write_dump(format_args!("{:#?}", state.redo_log_header))?;
             }
+            let release = match state.redo_log_header.release() {
+                Some(release) => release,
+                None => return olr_perr!("Redo version: 0x{:08X} is not supported, expected release: 11.2, 12.1, 12.2, 18, 19, 21 or 23", 
+                    state.redo_log_header.oracle_version),
+            };
+            info!("Redo version: 0x{:08X} release: {}", state.redo_log_header.oracle_version, release);
             self.version = Some(state.redo_log_header.oracle_version);
+            self.next_scn = Some(state.redo_log_header.next_scn);
             state.start_block += 1;
             state.end_block += 1;
             return Ok(());
         }
 
+        if self.context_ptr.disable_checks & constants::DISABLE_CHECKS_BLOCK_SUM == 0 {
+            self.validate_block(phisical_block)?;
+        }
+
         let mut reader = ByteReader::from_bytes(&phisical_block);
         reader.set_endian(self.endian.unwrap());
 
-        reader.skip_bytes(16); // Skip block header
+        let block_header = reader.read_block_header()?;
+        if self.context_ptr.disable_checks & constants::DISABLE_CHECKS_BLOCK_NUMBER == 0 {
+            if block_header.rba.block_number as usize != state.start_block {
+                return olr_err!(BadBlockNumber, "Block: {} has number: {} in header. Set flag {} in 'disable-checks' to skip check of block numbers. {}", 
+                    state.start_block, block_header.rba.block_number, constants::DISABLE_CHECKS_BLOCK_NUMBER, reader.to_error_hex_dump(4, 4));
+            }
+
+            if block_header.rba.sequence != state.redo_log_header.block_header.rba.sequence {
+                return olr_err!(BadBlockSequence, "Block: {} has sequence: {}, expected: {}. Set flag {} in 'disable-checks' to skip check of block numbers. {}", 
+                    state.start_block, block_header.rba.sequence, state.redo_log_header.block_header.rba.sequence, constants::DISABLE_CHECKS_BLOCK_NUMBER, reader.to_error_hex_dump(8, 4));
+            }
+        }
         
         if state.start_block == state.end_block {
             let record_header = match reader.read_record_header(state.redo_log_header.oracle_version) {
@@ -233,8 +279,10 @@ impl Parser {
                 Err(err) => return olr_perr!("Parse record header error: {}. {}", err, reader.to_error_hex_dump(16, 68))
             };
 
-            assert!(record_header.expansion.is_some(), "Dump: {}", reader.to_error_hex_dump(16, 68));
-            let record_expansion = record_header.expansion.as_ref().unwrap();
+            let record_expansion = match record_header.expansion.as_ref() {
+                Some(x) => x,
+                None => return olr_perr!("First record of block: {} has no LWN header. {}", state.start_block, reader.to_error_hex_dump(16, 24)),
+            };
             state.end_block = state.start_block + record_expansion.records_count as usize;
             state.timestamp = record_expansion.records_timestamp.clone();
 
@@ -257,6 +305,19 @@ impl Parser {
                     break;
                 }
 
+                if (redo_record_header.record_size as usize) < RECORD_HEADER_SIZE {
+                    return olr_err!(BadRecordSize, "Record at block: {} offset: {} has size: {}, expected at least: {}. {}", 
+                        state.start_block, prev_offset, redo_record_header.record_size, RECORD_HEADER_SIZE, reader.to_error_hex_dump(prev_offset, 4));
+                }
+
+                // Record can not cross the end of LWN
+                let block_size = self.block_size.unwrap();
+                let lwn_space = block_size - prev_offset + state.end_block.saturating_sub(state.start_block + 1) * (block_size - 16);
+                if self.context_ptr.disable_checks & constants::DISABLE_CHECKS_RECORD_SIZE == 0 && redo_record_header.record_size as usize > lwn_space {
+                    return olr_err!(BadRecordSize, "Record at block: {} offset: {} has size: {}, expected at most: {}. Set flag {} in 'disable-checks' to skip check of record sizes. {}", 
+                        state.start_block, prev_offset, redo_record_header.record_size, lwn_space, constants::DISABLE_CHECKS_RECORD_SIZE, reader.to_error_hex_dump(prev_offset, 4));
+                }
+
                 state.to_read = redo_record_header.record_size as usize;
                 state.record_position = 0;
 
@@ -307,31 +368,35 @@ impl Parser {
         let mut reader = ByteReader::from_bytes(&buffer);
         reader.set_endian(self.endian.unwrap());
 
-        let block_flag = reader.read_u8().unwrap();
-        let file_type = reader.read_u8().unwrap();
+        let block_flag = reader.read_u8()?;
+        let file_type = reader.read_u8()?;
         reader.skip_bytes(18);
-        let block_size = reader.read_u32().unwrap();
-        let number_of_blocks = reader.read_u32().unwrap();
-        let magic_number = reader.read_u32().unwrap();
+        let block_size = reader.read_u32()?;
+        let number_of_blocks = reader.read_u32()?;
+        let magic_number = reader.read_u32()?;
 
         if block_flag != 0 {
-            return olr_perr!("Invalid block flag: {}, expected 0x00. {}", block_flag, reader.to_error_hex_dump(0, 1));
+            return olr_err!(BadFileHeader, "Invalid block flag: {}, expected 0x00. {}", block_flag, reader.to_error_hex_dump(0, 1));
         }
 
         match (file_type, block_size) {
             (0x22, 512) | (0x22, 1024) | (0x82, 4096) => {
-                assert_eq!(self.block_size.unwrap(), block_size as usize);
+                if self.block_size.unwrap() != block_size as usize {
+                    return olr_err!(BadFileHeader, "Block size: {} differs from size: {} found by reader. {}", block_size, self.block_size.unwrap(), reader.to_error_hex_dump(20, 4));
+                }
             },
             _ => {
-                return olr_perr!("Invalid block size: {}, expected one of {{512, 1024, 4096}}. {}", block_size, reader.to_error_hex_dump(20, 4));
+                return olr_err!(BadFileHeader, "Invalid block size: {}, expected one of {{512, 1024, 4096}}. {}", block_size, reader.to_error_hex_dump(20, 4));
             }
         }
 
-        assert_eq!(magic_number, 0x7A7B7C7D);
+        if magic_number != 0x7A7B7C7D {
+            return olr_err!(BadFileHeader, "Invalid magic number: 0x{:08X}, expected 0x7A7B7C7D. {}", magic_number, reader.to_error_hex_dump(28, 4));
+        }
 
         let metadata = self.metadata.as_ref().unwrap();
         if metadata.len() != ((number_of_blocks + 1) * block_size) as u64 {
-            return olr_perr!("Invalid file size. ({} + 1) * {} != {} bytes. {}", number_of_blocks, block_size, metadata.len(), reader.to_error_hex_dump(24, 4));
+            return olr_err!(BadFileHeader, "Invalid file size. ({} + 1) * {} != {} bytes. {}", number_of_blocks, block_size, metadata.len(), reader.to_error_hex_dump(24, 4));
         }
         
         Ok(())
@@ -409,9 +474,11 @@ impl Parser {
         let checksum = self.block_checksum(read_buffer);
         if checksum != 0 {
             let mut reader = ByteReader::from_bytes(read_buffer);
+            reader.set_endian(self.endian.unwrap());
             reader.skip_bytes(14);
             let block_checksum = reader.read_u16().unwrap();
-            return olr_perr!("Bad block. Checksums are not equal: {} != {}. {}", checksum ^ block_checksum, block_checksum, reader.to_error_hex_dump(14, 2));
+            return olr_err!(BadBlockChecksum, "Bad block. Checksums are not equal: {} != {}. Set flag {} in 'disable-checks' to skip check of data blocks. {}", 
+                checksum ^ block_checksum, block_checksum, constants::DISABLE_CHECKS_BLOCK_SUM, reader.to_error_hex_dump(14, 2));
         }
         Ok(())
     }
@@ -448,22 +515,136 @@ impl Parser {
         Ok(())
     }
 
-    fn push_to_transaction_double(&mut self, vector1 : Vector, vector2 : Vector) -> Result<()> {
+    fn push_to_transaction_double(&mut self, record : &Record, vector1 : Vector, vector2 : Vector) -> Result<()> {
+        if let (Some(obj), Some(data_obj)) = (vector1.obj(), vector1.data_obj()) {
+            if !self.metadata_ptr.is_object_tracked(obj, Some(data_obj)) {
+                return Ok(());
+            }
+        }
+        if self.metadata_ptr.is_file_encrypted(vector2.afn() as u32) {
+            return Ok(());
+        }
+
         let mut guard: std::sync::MutexGuard<'_, TransactionBuffer> = self.transaction_buffer.lock().unwrap();
         let xid = vector1.xid().expect("vector1 must be an opcode with xid");
         
-        guard.add_double_in_transaction(xid, vector1, vector2)?;
+        let position = TypeRedoPosition::new(self.sequence, record.block, record.offset);
+        guard.add_double_in_transaction(xid, record.scn, record.timestamp, position, vector1, vector2)?;
         Ok(())
     }
 
-    fn push_to_transaction_commit(&mut self, commit : Vector) -> Result<()> {
+    fn push_session_attributes(&mut self, undo : Vector, session : Vector) -> Result<()> {
+        let xid = undo.xid().expect("vector1 must be an opcode with xid");
+
+        let attributes = match session.into_data() {
+            VectorData::OpCode0520(session) => SessionAttributes {
+                login_username : session.login_username,
+                session_number : session.session_number,
+                serial_number : session.serial_number,
+                audit_session_id : session.audit_session_id,
+            },
+            data => return olr_perr!("Expected session attributes vector, but got: {}", data),
+        };
+
+        let mut guard = self.transaction_buffer.lock().unwrap();
+        guard.set_session_attributes(xid, attributes)
+    }
+
+    /// Direct-load LOB data goes to transaction which writes the LOB
+    fn push_lob(&mut self, record : &Record, lob : Vector) -> Result<()> {
+        let mut guard = self.transaction_buffer.lock().unwrap();
+        let position = TypeRedoPosition::new(self.sequence, record.block, record.offset);
+        guard.add_lob_in_transaction(record.scn, record.timestamp, position, lob)
+    }
+
+    /// Tables and partitions are tracked at once, DDL message is sent at commit of transaction
+    fn push_ddl(&mut self, record : &Record, ddl : Vector) -> Result<()> {
+        match ddl.data() {
+            VectorData::OpCode1801(ddl) => self.metadata_ptr.track_ddl(ddl)?,
+            data => return olr_perr!("Expected ddl vector, but got: {}", data),
+        }
+
         let mut guard = self.transaction_buffer.lock().unwrap();
-        // guard.close_transaction(commit.xid)?;
+        let position = TypeRedoPosition::new(self.sequence, record.block, record.offset);
+        guard.add_ddl_in_transaction(ddl.xid().unwrap(), record.scn, record.timestamp, position, ddl)
+    }
+
+    fn push_to_transaction_commit(&mut self, record : &Record, commit : Vector) -> Result<()> {
+        let (xid, is_rollback) = match commit.into_data() {
+            VectorData::OpCode0504(commit) => (commit.xid, commit.flg & constants::FLAG_KTUCF_ROLLBACK != 0),
+            data => return olr_perr!("Expected commit vector, but got: {}", data),
+        };
 
-        // std::unimplemented!("Commit");
+        let transaction_buffer = self.transaction_buffer.clone();
+        let mut guard = transaction_buffer.lock().unwrap();
+
+        if let Some(transaction) = guard.close_transaction(xid, record.scn, record.timestamp, is_rollback)? {
+            let result = self.replay_transaction(&transaction, record);
+            guard.release_transaction(transaction)?;
+            result?;
+        }
 
         Ok(())
     }
+
+    /// LOB pages are written after the row with locator, so they are gathered before rows are built
+    fn collect_lobs(&mut self, transaction : &Transaction) -> Result<LobContext> {
+        let version = self.version.unwrap();
+        let mut result = LobContext::default();
+
+        for entry in transaction.entries() {
+            let data = if entry.vector2.is_empty() { entry.vector1 } else { entry.vector2 };
+
+            let mut reader = ByteReader::from_bytes(data);
+            reader.set_endian(self.endian.unwrap());
+            let vector = Vector::parse(self, &mut reader, version)?;
+
+            if let Some(page) = vector.lob_page() {
+                if let (Some(lob_id), Some(data)) = (page.lob_id, page.data) {
+                    result.add_page(lob_id, page.page_no, data);
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn replay_transaction(&mut self, transaction : &Transaction, commit_record : &Record) -> Result<()> {
+        let version = self.version.unwrap();
+        let xid = transaction.xid();
+
+        self.builder_ptr.set_commit_scn(xid, commit_record.scn);
+        self.builder_ptr.process_begin(
+            transaction.scn().unwrap_or(commit_record.scn), 
+            transaction.timestamp().unwrap_or(commit_record.timestamp), 
+            xid, transaction.attributes())?;
+
+        self.is_replaying = true;
+        let result = self.collect_lobs(transaction).and_then(|lobs| transaction.entries().try_for_each(|entry| -> Result<()> {
+            let mut reader1 = ByteReader::from_bytes(entry.vector1);
+            reader1.set_endian(self.endian.unwrap());
+
+            // Single vector is DDL or LOB page, which is already collected
+            if entry.vector2.is_empty() {
+                return match Vector::parse(self, &mut reader1, version)?.into_data() {
+                    VectorData::OpCode1801(ddl) => self.builder_ptr.process_ddl(entry.scn, entry.timestamp, ddl),
+                    _ => Ok(()),
+                };
+            }
+
+            let mut reader2 = ByteReader::from_bytes(entry.vector2);
+            reader2.set_endian(self.endian.unwrap());
+
+            let vector1 = Vector::parse(self, &mut reader1, version)?;
+            let vector2 = Vector::parse(self, &mut reader2, version)?;
+
+            self.builder_ptr.process_dml(entry.scn, entry.timestamp, entry.position, vector1, vector2, transaction.attributes(), &lobs)
+        }));
+        self.is_replaying = false;
+        result?;
+
+        self.builder_ptr.process_commit(commit_record.scn, commit_record.timestamp, xid, false, transaction.attributes())
+    }
 }
 
 
@@ -487,9 +668,22 @@ impl RecordAnalizer for Parser {
             self.write_dump(format_args!("\nHeader: {}\n", record_header))?;
         }
 
+        if !self.metadata_ptr.is_container_tracked(record_header.container_id) {
+            return Ok(());
+        }
+
         let mut vector_pull : VecDeque<Vector> = VecDeque::with_capacity(2);
         while !reader.eof() {
             let vector = Vector::parse(self, &mut reader, self.version.unwrap())?;
+
+            // Undo vector carries xid for its redo pair
+            if let Some(xid) = vector.xid().or_else(|| vector_pull.front().and_then(|x| x.xid())) {
+                if self.metadata_ptr.is_xid_dumped(xid) {
+                    self.write_dump(format_args!("\nXID: {} scn: {} vector: {}.{} size: {}{}\n", 
+                        xid, record.scn, vector.opcode() >> 8, vector.opcode() & 0xFF, vector.size(), 
+                        ByteReader::from_bytes(vector.raw_data()).to_colorless_hex_dump()))?;
+                }
+            }
             
             vector_pull.push_back(vector);
             
@@ -499,10 +693,24 @@ impl RecordAnalizer for Parser {
                 let second = vector_pull.pop_front().unwrap();
 
                 match (first.kind(), second.kind()) {
-                    (VectorKind::OpCode0501, VectorKind::OpCode1102) => {
+                    (VectorKind::OpCode0501, VectorKind::OpCode1002) |
+                    (VectorKind::OpCode0501, VectorKind::OpCode1004) |
+                    (VectorKind::OpCode0501, VectorKind::OpCode1018) |
+                    (VectorKind::OpCode0501, VectorKind::OpCode1102) |
+                    (VectorKind::OpCode0501, VectorKind::OpCode1103) |
+                    (VectorKind::OpCode0501, VectorKind::OpCode1105) |
+                    (VectorKind::OpCode0501, VectorKind::OpCode1106) |
+                    (VectorKind::OpCode0501, VectorKind::OpCode1111) |
+                    (VectorKind::OpCode0501, VectorKind::OpCode1112) |
+                    (VectorKind::OpCode0501, VectorKind::OpCode1A02) => {
+                        self.push_to_transaction_double(record, first, second)?;
+                    },
+                    (VectorKind::OpCode0501, VectorKind::OpCode1108) |
+                    (VectorKind::OpCode0501, VectorKind::OpCode1122) => {
+                        // Forwarding address of migrated row is changed or block is compressed, row data stays the same
                     },
                     (VectorKind::OpCode0501, VectorKind::OpCode0520) => {
-                        self.push_to_transaction_double(first, second)?;
+                        self.push_session_attributes(first, second)?;
                     },
                     (_, _) => {
                         info!("Unknown pair: {:?} {:?}", first.kind(), second.kind());
@@ -522,7 +730,13 @@ impl RecordAnalizer for Parser {
                         self.push_to_transaction_begin(record, vector_pull.pop_front().unwrap())?;
                     },
                     VectorKind::OpCode0504 => {
-                        self.push_to_transaction_commit(vector_pull.pop_front().unwrap())?;
+                        self.push_to_transaction_commit(record, vector_pull.pop_front().unwrap())?;
+                    },
+                    VectorKind::OpCode1801 => {
+                        self.push_ddl(record, vector_pull.pop_front().unwrap())?;
+                    },
+                    VectorKind::OpCode1301 | VectorKind::OpCode1A06 => {
+                        self.push_lob(record, vector_pull.pop_front().unwrap())?;
                     },
                     _ => (),
                 }
@@ -534,4 +748,145 @@ impl RecordAnalizer for Parser {
         vector_pull.clear();
         Ok(())
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use crate::{builder::formats, common::{constants, errors::Result, testing, types::TypeXid}, ctx::{Ctx, Dump}, locales::Locales, metadata::Metadata, oradefs::oracle_table::OracleTable};
+
+    use super::{byte_reader::Endian, Parser, ParserState, RecordAnalizer, RecordsManager};
+
+    const BLOCK_SIZE : usize = 512;
+    const SEQUENCE : u32 = 7;
+
+    /// Data block with one LWN record of `record_size` bytes with one vector, checksum of block is valid
+    fn lwn_block(number : u32, sequence : u32, record_size : u32) -> Vec<u8> {
+        let mut block = vec![0u8; BLOCK_SIZE];
+        block[0] = 0x01;
+        block[1] = 0x22;
+        block[4 .. 8].copy_from_slice(&number.to_le_bytes());
+        block[8 .. 12].copy_from_slice(&sequence.to_le_bytes());
+        block[16 .. 20].copy_from_slice(&record_size.to_le_bytes());
+        block[20] = 0x04;                                       // vld: LWN header
+        block[44 .. 48].copy_from_slice(&1u32.to_le_bytes());  // LWN blocks
+        let vector = testing::vector_bytes((4, 1), &[]);
+        block[84 .. 84 + vector.len()].copy_from_slice(&vector);
+
+        let checksum = block.chunks(2).fold(0u16, |acc, x| acc ^ u16::from_le_bytes([x[0], x[1]]));
+        block[14 .. 16].copy_from_slice(&checksum.to_le_bytes());
+        block
+    }
+
+    /// Processes `block` as the first data block of redo log
+    fn process_block(disable_checks : u64, block : &[u8]) -> Result<()> {
+        let context_ptr = Arc::new(Ctx::new(Dump::default(), 0, 0, 0, disable_checks, "checkpoint".to_string(), 600, 500, 100, 20, 16, 64, 4)?);
+        let metadata_ptr = testing::metadata(context_ptr.clone());
+        let (builder_ptr, _) = testing::builder(context_ptr.clone(), metadata_ptr.clone(), formats::MESSAGE_FORMAT_DEFAULT);
+        let mut parser : Parser = testing::parser(context_ptr, builder_ptr, metadata_ptr);
+        parser.version = Some(constants::REDO_VERSION_19_0);
+        parser.endian = Some(Endian::LittleEndian);
+        parser.block_size = Some(BLOCK_SIZE);
+
+        let mut state = ParserState { to_read : 0, start_block : 2, end_block : 2, redo_log_header : Default::default(), 
+                                      record_position : 0, timestamp : Default::default(), record : None };
+        state.redo_log_header.oracle_version = constants::REDO_VERSION_19_0;
+        state.redo_log_header.block_header.rba.sequence = SEQUENCE;
+        parser.process_block(&mut state, block)
+    }
+
+    #[test]
+    fn test_ddl_at_commit() -> Result<()> {
+        let context_ptr = testing::context();
+        let metadata_ptr = testing::metadata(context_ptr.clone());
+        let (builder_ptr, output) = testing::builder(context_ptr.clone(), metadata_ptr.clone(), formats::MESSAGE_FORMAT_DEFAULT);
+        let mut parser = testing::parser(context_ptr.clone(), builder_ptr, metadata_ptr.clone());
+        parser.version = Some(constants::REDO_VERSION_19_0);
+        parser.endian = Some(Endian::LittleEndian);
+
+        let table = || OracleTable::new("SCOTT".to_string(), "T1".to_string(), vec![testing::column(1, "ID", constants::SYS_COL_TYPE_NUMBER)], vec![]);
+        testing::set_tables(&metadata_ptr, vec![(100, 101, table())]);
+        testing::set_dictionary(&metadata_ptr, vec![(100, 102, table())]);
+
+        let mut records = RecordsManager::new(context_ptr);
+        let record = records.reserve_record(0)?;
+        record.scn = 20.into();
+
+        // Truncate is kept in its transaction until commit
+        let ddl = testing::truncate_vector_bytes(100);
+        let vector = testing::parse_vector(&mut parser, &ddl);
+        parser.push_ddl(record, vector)?;
+        assert!(output.take().is_empty());
+
+        let commit = testing::commit_vector_bytes(TypeXid::new(10, 27, 12109), false);
+        let vector = testing::parse_vector(&mut parser, &commit);
+        parser.push_to_transaction_commit(record, vector)?;
+
+        let messages = output.take();
+        assert_eq!(messages.iter().map(|x| x["OP"].as_str().unwrap()).collect::<Vec<_>>(), ["start", "truncate", "commit"]);
+        assert_eq!(messages[1]["XID"], "0x000a.01b.00002f4d");
+        Ok(())
+    }
+
+    #[test]
+    fn test_container_filter() -> Result<()> {
+        let context_ptr = testing::context();
+        let metadata_ptr = Arc::new(Metadata::new(context_ptr.clone(), Arc::new(Locales::new()), "TEST".to_string(), 3, 0.into(), 0, String::new(), 0));
+        let (builder_ptr, output) = testing::builder(context_ptr.clone(), metadata_ptr.clone(), formats::MESSAGE_FORMAT_DEFAULT);
+        let mut parser = testing::parser(context_ptr.clone(), builder_ptr, metadata_ptr.clone());
+        parser.version = Some(constants::REDO_VERSION_19_0);
+        parser.endian = Some(Endian::LittleEndian);
+
+        let table = OracleTable::new("SCOTT".to_string(), "T1".to_string(), vec![testing::column(1, "ID", constants::SYS_COL_TYPE_NUMBER)], vec![]);
+        testing::set_tables(&metadata_ptr, vec![(100, 101, table)]);
+
+        let mut records = RecordsManager::new(context_ptr);
+        let mut analize = |con_id : u32, xid : TypeXid| -> Result<Vec<String>> {
+            let (undo, redo) = testing::insert_vectors_bytes(100, 101, xid, &[&[0xC1, 0x02]]);
+            let commit = testing::commit_vector_bytes(xid, false);
+
+            // Record header of 12.1+ layout: size, vld, scn, sub scn, con_id
+            let mut data = vec![0u8; 24];
+            data.extend([undo, redo, commit].concat());
+            let size = data.len() as u32;
+            data[0 .. 4].copy_from_slice(&size.to_le_bytes());
+            data[8 .. 12].copy_from_slice(&20u32.to_le_bytes());
+            data[16 .. 20].copy_from_slice(&con_id.to_le_bytes());
+
+            let record = records.reserve_record(data.len())?;
+            record.scn = 20.into();
+            record.size = size;
+            record.data_mut().copy_from_slice(&data);
+            parser.analize_record(record)?;
+            records.free_chunks();
+
+            Ok(output.take().iter().map(|x| x["OP"].as_str().unwrap().to_string()).collect())
+        };
+
+        // Records of other pluggable databases are skipped, container id 0 is not bound to any container
+        assert!(analize(4, TypeXid::new(10, 1, 100))?.is_empty());
+        assert_eq!(analize(3, TypeXid::new(10, 2, 100))?, ["start", "insert", "commit"]);
+        assert_eq!(analize(0, TypeXid::new(10, 3, 100))?, ["start", "insert", "commit"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_block_checks() -> Result<()> {
+        process_block(0, &lwn_block(2, SEQUENCE, 104))?;
+
+        let mut corrupted = lwn_block(2, SEQUENCE, 104);
+        corrupted[100] ^= 0x10;
+        assert!(process_block(0, &corrupted).is_err());
+        process_block(constants::DISABLE_CHECKS_BLOCK_SUM, &corrupted)?;
+
+        // Block of other position or other redo log
+        assert!(process_block(0, &lwn_block(3, SEQUENCE, 104)).is_err());
+        assert!(process_block(0, &lwn_block(2, SEQUENCE + 1, 104)).is_err());
+        process_block(constants::DISABLE_CHECKS_BLOCK_NUMBER, &lwn_block(3, SEQUENCE + 1, 104))?;
+
+        // Record is bigger than its LWN of one block or smaller than record header
+        assert!(process_block(0, &lwn_block(2, SEQUENCE, 497)).is_err());
+        assert!(process_block(constants::DISABLE_CHECKS_RECORD_SIZE, &lwn_block(2, SEQUENCE, 20)).is_err());
+        Ok(())
+    }
+}
@@ -1,18 +1,21 @@
-use super::{archive_structs::vector_header::VectorHeader, byte_reader::ByteReader};
+use super::{archive_structs::vector_header::VectorHeader, byte_reader::{ByteReader, Endian}};
 
 #[derive(Debug)]
 pub struct VectorReader<'a> {
     pub header : VectorHeader,
     data : &'a [u8],
+    endian : Endian,
     current_pos : usize,
     current_field : usize,
 }
 
 impl<'a> VectorReader<'a> {
-    pub fn new(vector_header : VectorHeader, vector_data : &'a [u8]) -> Self {
+    /// Fields are read with byte order of redo log
+    pub fn new(vector_header : VectorHeader, vector_data : &'a [u8], endian : Endian) -> Self {
         Self {
             header : vector_header,
             data : vector_data,
+            endian,
             current_pos : 0,
             current_field : 0,
         }
@@ -35,7 +38,9 @@ impl<'a> VectorReader<'a> {
             pos += (fsize  + 3) & !3;
             fsize = self.header.fields_sizes[i + 1] as usize;
         }
-        ByteReader::from_bytes(&self.data[pos .. pos + fsize])
+        let mut reader = ByteReader::from_bytes(&self.data[pos .. pos + fsize]);
+        reader.set_endian(self.endian);
+        reader
     }
 
     pub fn skip_empty(&mut self) -> usize {
@@ -59,7 +64,8 @@ impl<'a> Iterator for VectorReader<'a> {
             None
         } else {
             let field_size = self.header.fields_sizes[self.current_field] as usize;
-            let reader = ByteReader::from_bytes(&self.data[self.current_pos .. self.current_pos + field_size]);
+            let mut reader = ByteReader::from_bytes(&self.data[self.current_pos .. self.current_pos + field_size]);
+            reader.set_endian(self.endian);
             self.current_pos += (field_size  + 3) & !3;
             self.current_field += 1;
             Some(reader)
@@ -71,3 +77,60 @@ impl<'a> Iterator for VectorReader<'a> {
         (tail_size, Some(tail_size))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{common::{constants, errors::Result}, parser::{byte_reader::{ByteReader, Endian}, byte_writer::ByteWriter}};
+
+    use super::VectorReader;
+
+    /// Vector 11.2 with fields of 4 and 2 bytes written in byte order `endian`
+    fn vector(endian : Endian) -> [u8; 48] {
+        let mut buffer = [0u8; 48];
+        let mut writer = ByteWriter::from_bytes(&mut buffer);
+        writer.set_endian(endian);
+
+        writer.write_u8(11).unwrap();
+        writer.write_u8(2).unwrap();
+        writer.write_u16(1).unwrap();       // class
+        writer.write_u16(7).unwrap();       // afn
+        writer.skip_bytes(2);
+        writer.write_u32(0x01000123).unwrap(); // dba
+        writer.skip_bytes(12);              // scn, seq, typ
+        writer.write_u16(3).unwrap();       // con_id
+        writer.skip_bytes(2);
+        writer.write_u16(0x10).unwrap();    // flag
+        writer.skip_bytes(2);
+        writer.write_u16(6).unwrap();       // size of fields sizes
+        writer.write_u16(4).unwrap();
+        writer.write_u16(2).unwrap();
+        writer.skip_bytes(2);
+        writer.write_u32(0x12345678).unwrap();
+        writer.write_u16(0xABCD).unwrap();
+        buffer
+    }
+
+    fn parse(buffer : &[u8], endian : Endian) -> Result<(u16, u32, u16, u16, u32, u16)> {
+        let mut reader = ByteReader::from_bytes(buffer);
+        reader.set_endian(endian);
+        let header = reader.read_redo_vector_header(constants::REDO_VERSION_19_0)?;
+        reader.align_up(4);
+
+        let ext = header.expansion.unwrap();
+        let (afn, dba) = (header.afn, header.dba);
+        let mut vector_reader = VectorReader::new(header, &buffer[reader.cursor() ..], endian);
+        let first = vector_reader.next().unwrap().read_u32()?;
+        let second = vector_reader.next().unwrap().read_u16()?;
+        Ok((afn, dba, ext.container_id, ext.flag, first, second))
+    }
+
+    #[test]
+    fn test_big_endian_vector() -> Result<()> {
+        let little = parse(&vector(Endian::LittleEndian), Endian::LittleEndian)?;
+        let big = parse(&vector(Endian::BigEndian), Endian::BigEndian)?;
+
+        assert_eq!(little, (7, 0x01000123, 3, 0x10, 0x12345678, 0xABCD));
+        assert_eq!(little, big);
+        Ok(())
+    }
+}
@@ -8,6 +8,7 @@ use log::{info, trace, warn};
 use crate::builder::JsonBuilder;
 use crate::common::types::TypeSeq;
 use crate::ctx::Ctx;
+use crate::metadata::Metadata;
 use crate::transactions::transaction_buffer::TransactionBuffer;
 use crate::{common::errors::Result, olr_err, parser::parser_impl::Parser};
 use crate::common::OLRErrorCode::*;
@@ -20,6 +21,7 @@ pub trait ArchiveDigger where Self: Send + Sync + Debug {
 pub struct ArchiveDiggerOffline {
     context_ptr : Arc<Ctx>,
     builder_ptr : Arc<JsonBuilder>,
+    metadata_ptr : Arc<Metadata>,
     archive_log_format : String, 
     db_recovery_file_destination : String,
     db_name : String,
@@ -37,11 +39,12 @@ unsafe impl Send for ArchiveDiggerOffline {}
 unsafe impl Sync for ArchiveDiggerOffline {}
 
 impl ArchiveDiggerOffline {
-    pub fn new(context : Arc<Ctx>, builder : Arc<JsonBuilder>, archive_log_format : String, db_recovery_file_destination : String,
+    pub fn new(context : Arc<Ctx>, builder : Arc<JsonBuilder>, metadata : Arc<Metadata>, archive_log_format : String, db_recovery_file_destination : String,
         db_name : String, min_sequence : Option<TypeSeq>, mapping_fn : Box<dyn Fn(PathBuf) -> PathBuf>) -> Self {
         Self {
             context_ptr: context,
             builder_ptr : builder,
+            metadata_ptr : metadata,
             archive_log_format, 
             db_recovery_file_destination,
             db_name,
@@ -125,9 +128,18 @@ impl ArchiveDigger for ArchiveDiggerOffline {
                     continue;
                 }
 
+                // Sequence which was parsed to its end before restart is not parsed again
+                if let Some(position) = self.metadata_ptr.checkpoint_position() {
+                    let size = archive_file.metadata().map(|x| x.len()).unwrap_or(u64::MAX);
+                    if sequence < position.sequence || (sequence == position.sequence && position.offset >= size) {
+                        info!("Skip sequence {} of checkpoint", sequence);
+                        continue;
+                    }
+                }
+
                 info!("Found sequence: {:?}", sequence);
 
-                let parser = Parser::new(self.context_ptr.clone(), self.builder_ptr.clone(), transaction_buffer.clone(), archive_file, sequence)?;
+                let parser = Parser::new(self.context_ptr.clone(), self.builder_ptr.clone(), self.metadata_ptr.clone(), transaction_buffer.clone(), archive_file, sequence)?;
                 parser_queue.push(Reverse(parser));
             }
         }
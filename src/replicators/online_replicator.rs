@@ -28,11 +28,11 @@ pub struct OnlineReplicator {
 
 impl OnlineReplicator {
     pub fn new(context_ptr : Arc<Ctx>, builder_ptr : Arc<JsonBuilder>, metadata_ptr : Arc<Metadata>, archive_digger  : Box<dyn ArchiveDigger>,
-         alias : String, database_name : String, user : String, password : String, server : String) -> Self {
+         alias : String, database_name : String, user : String, password : String, server : String, stream_uncommitted : bool) -> Self {
         debug!("Initialize OnlineReplicator");
         Self {
-            context_ptr : context_ptr.clone(), builder_ptr, metadata_ptr, archive_digger, 
-            transaction_buffer : Arc::new(Mutex::new(TransactionBuffer::new(context_ptr))),
+            context_ptr : context_ptr.clone(), builder_ptr : builder_ptr.clone(), metadata_ptr : metadata_ptr.clone(), archive_digger, 
+            transaction_buffer : Arc::new(Mutex::new(TransactionBuffer::new(context_ptr, builder_ptr, metadata_ptr, stream_uncommitted))),
             alias, database_name, user, password, server
         }
     }
@@ -45,7 +45,13 @@ impl Thread for OnlineReplicator {
         let conn = oracle::Connection::connect(&self.user, &self.password, &self.server)
             .map_err(|err| olr_err!(OracleConnection, "Problems with connection: {}", err))?;
         
-        self.metadata_ptr.set_schema_resource(OracleSchemaResource::FromConnection(conn))?;
+        self.metadata_ptr.set_schema_resource(OracleSchemaResource::FromConnection(Arc::new(conn)))?;
+        self.metadata_ptr.switch_container()?;
+        self.metadata_ptr.load_charsets()?;
+        self.metadata_ptr.load_checkpoint()?;
+        self.metadata_ptr.resolve_filter()?;
+        self.metadata_ptr.load_encryption()?;
+        self.metadata_ptr.check_supplemental_log()?;
 
         let mut parsers_queue = self.archive_digger.get_parsers_queue(self.transaction_buffer.clone())?;
         
@@ -58,6 +64,10 @@ impl Thread for OnlineReplicator {
                 warn!("Can not parse sequence: {}. Stop replication", parser.sequence());
                 return res;
             }
+
+            if let Some(scn) = parser.next_scn() {
+                self.metadata_ptr.checkpoint(parser.sequence(), parser.parsed_size(), scn, parser.parsed_size())?;
+            }
         }
 
         info!("Stop replicator. Thread id: {} alias: {}", self.thread_id(), self.alias());
@@ -1,20 +1,33 @@
-
-
 use std::collections::VecDeque;
 
-use crate::{common::types::{TypeRecordScn, TypeTimestamp, TypeXid}, olr_err, parser::opcodes::Vector};
+use serde::Serialize;
+
+use crate::{common::types::{TypeRecordScn, TypeRedoPosition, TypeTimestamp, TypeXid}, olr_err, parser::opcodes::Vector};
 
-use super::transaction_chunk::{TransactionChunk, TRANSACTION_CHUNK_BUFFER_SIZE};
+use super::transaction_chunk::{TransactionChunk, TransactionChunkEntry, TRANSACTION_CHUNK_BUFFER_SIZE};
 use crate::common::errors::Result;
 use crate::common::OLRErrorCode::TransactionMemory;
 
+/// Session info from 5.20 vector of the first change in transaction
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub struct SessionAttributes {
+    pub login_username : String,
+    pub session_number : u32,
+    pub serial_number : u16,
+    pub audit_session_id : u32,
+}
+
 #[derive(Debug)]
 pub struct Transaction {
     xid : TypeXid,
 
     is_begined : bool,
+    is_skipped : bool,
+    is_streamed : bool,
     scn : Option<TypeRecordScn>,
     timestamp : Option<TypeTimestamp>,
+    attributes : Option<SessionAttributes>,
 
     chunks : VecDeque<&'static mut TransactionChunk>,
 }
@@ -26,10 +39,49 @@ impl Transaction {
             scn : None,
             timestamp : None,
             is_begined : false,
+            is_skipped : false,
+            is_streamed : false,
+            attributes : None,
             chunks : VecDeque::new(),
         }
     }
 
+    pub fn xid(&self) -> TypeXid {
+        self.xid
+    }
+
+    pub fn scn(&self) -> Option<TypeRecordScn> {
+        self.scn
+    }
+
+    pub fn timestamp(&self) -> Option<TypeTimestamp> {
+        self.timestamp
+    }
+
+    pub fn attributes(&self) -> Option<&SessionAttributes> {
+        self.attributes.as_ref()
+    }
+
+    pub fn set_attributes(&mut self, attributes : SessionAttributes) {
+        self.attributes = Some(attributes);
+    }
+
+    pub fn is_skipped(&self) -> bool {
+        self.is_skipped
+    }
+
+    pub fn set_skipped(&mut self) {
+        self.is_skipped = true;
+    }
+
+    pub fn is_streamed(&self) -> bool {
+        self.is_streamed
+    }
+
+    pub fn set_streamed(&mut self) {
+        self.is_streamed = true;
+    }
+
     pub fn set_start_info(&mut self, scn : TypeRecordScn, timestamp : TypeTimestamp) {
         self.is_begined = true;
         self.scn = Some(scn);
@@ -44,24 +96,36 @@ impl Transaction {
     }
 
     pub fn append_transaction_chunk(&mut self, chunk : &'static mut TransactionChunk) {
-        match self.chunks.back_mut() {
-            Some(last) => {
-                last.set_next(chunk);
-                chunk.set_prev(*last);
-            },
-            None => (),
+        if let Some(last) = self.chunks.back_mut() {
+            last.set_next(chunk);
+            chunk.set_prev(*last);
         }
 
         self.chunks.push_back(chunk);
     }
 
-    pub fn append_double(&mut self, v1 : Vector, v2 : Vector) -> Result<()> {
+    pub fn append_double(&mut self, scn : TypeRecordScn, timestamp : TypeTimestamp, position : TypeRedoPosition, v1 : Vector, v2 : Vector) -> Result<()> {
         match self.chunks.back_mut() {
             Some(last) => {
-                last.append_double(v1, v2)?;
+                last.append_double(scn, timestamp, position, v1, v2)?;
                 Ok(())
             },
             None => olr_err!(TransactionMemory, "No chunk for pushing vectors"),
         }
     }
+
+    pub fn append_single(&mut self, scn : TypeRecordScn, timestamp : TypeTimestamp, position : TypeRedoPosition, v1 : Vector) -> Result<()> {
+        match self.chunks.back_mut() {
+            Some(last) => last.append_single(scn, timestamp, position, v1),
+            None => olr_err!(TransactionMemory, "No chunk for pushing vector"),
+        }
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = TransactionChunkEntry<'_>> {
+        self.chunks.iter().flat_map(|chunk| chunk.entries())
+    }
+
+    pub fn take_chunks(&mut self) -> VecDeque<&'static mut TransactionChunk> {
+        std::mem::take(&mut self.chunks)
+    }
 }
@@ -1,12 +1,12 @@
-
-
-use crate::{common::{memory_pool::MemoryChunk, types::{TypeRecordScn, TypeTimestamp}}, olr_perr};
+use crate::{builder::{lobs::LobContext, JsonBuilder}, common::{memory_pool::MemoryChunk, types::{TypeLobId, TypeRecordScn, TypeRedoPosition, TypeTimestamp}}, metadata::Metadata, olr_perr};
 
 use std::{collections::{hash_map::Entry, HashMap}, sync::Arc};
 
-use crate::{common::{errors::Result, types::TypeXid}, ctx::Ctx, parser::opcodes::Vector};
+use log::info;
 
-use super::{transaction::Transaction, transaction_chunk::{TransactionChunk, HEADER_TOTAL_SIZE, TRANSACTION_CHUNK_BUFFER_SIZE}};
+use crate::{common::{errors::Result, types::TypeXid}, ctx::Ctx, parser::opcodes::{Vector, VectorData}};
+
+use super::{transaction::{SessionAttributes, Transaction}, transaction_chunk::{TransactionChunk, HEADER_TOTAL_SIZE, TRANSACTION_CHUNK_BUFFER_SIZE}};
 
 #[derive(Debug)]
 struct FullnessMarker(usize);
@@ -146,26 +146,43 @@ impl TransactionManager {
 
         Ok(result)
     }
+
+    pub(crate) fn remove_transaction(&mut self, xid : TypeXid) -> Option<Transaction> {
+        self.transactions.remove(&xid)
+    }
 }
 
 #[derive(Debug)]
 pub struct TransactionBuffer {
+    builder_ptr             : Arc<JsonBuilder>,
+    metadata_ptr            : Arc<Metadata>,
+    stream_uncommitted      : bool,
     transactions_manager    : TransactionManager,
     memory_manager          : TransactionMemoryManager,
+    lob_owners              : HashMap<TypeLobId, TypeXid>, // Transactions which write LOBs, to link direct-load LOB vectors
 }
 
 unsafe impl Send for TransactionBuffer {}
 unsafe impl Sync for TransactionBuffer {}
 
 impl TransactionBuffer {
-    pub fn new(context_ptr : Arc<Ctx>) -> Self {
+    pub fn new(context_ptr : Arc<Ctx>, builder_ptr : Arc<JsonBuilder>, metadata_ptr : Arc<Metadata>, stream_uncommitted : bool) -> Self {
         Self {
+            builder_ptr,
+            metadata_ptr,
+            stream_uncommitted,
             transactions_manager : TransactionManager::default(),
             memory_manager : TransactionMemoryManager::new(context_ptr),
+            lob_owners : HashMap::new(),
         }
     }
 
     pub fn init_transaction(&mut self, xid : TypeXid, scn : TypeRecordScn, timestamp : TypeTimestamp) -> Result<()> {
+        if self.metadata_ptr.is_xid_skipped(xid) {
+            info!("Skip transaction: {}", xid);
+            return Ok(());
+        }
+
         let transaction = self.transactions_manager.find_transaction(xid, true)?.unwrap();
 
         transaction.set_start_info(scn, timestamp);
@@ -173,22 +190,304 @@ impl TransactionBuffer {
         Ok(())
     }
 
-    pub fn add_double_in_transaction(&mut self, xid : TypeXid, v1 : Vector, v2 : Vector) -> Result<()> {
+    /// Stores session info of transaction and marks it skipped if it was issued by filtered user
+    pub fn set_session_attributes(&mut self, xid : TypeXid, attributes : SessionAttributes) -> Result<()> {
+        if self.metadata_ptr.is_xid_skipped(xid) {
+            return Ok(());
+        }
+
+        let is_skipped = self.metadata_ptr.is_user_skipped(&attributes.login_username);
         let transaction = self.transactions_manager.find_transaction(xid, true)?.unwrap();
 
-        let added_size = v1.size() + v2.size() + HEADER_TOTAL_SIZE;
+        if is_skipped && !transaction.is_skipped() {
+            info!("Skip transaction: {} of user: {}", xid, attributes.login_username);
+            transaction.set_skipped();
+            let chunks = transaction.take_chunks();
+            for tr_chunk in chunks {
+                self.memory_manager.delete_transaction_chunk(tr_chunk)?;
+            }
+        }
+
+        transaction.set_attributes(attributes);
+        Ok(())
+    }
+
+    pub fn add_double_in_transaction(&mut self, xid : TypeXid, scn : TypeRecordScn, timestamp : TypeTimestamp, position : TypeRedoPosition, v1 : Vector, v2 : Vector) -> Result<()> {
+        if self.metadata_ptr.is_xid_skipped(xid) {
+            return Ok(());
+        }
+
+        let transaction = self.transactions_manager.find_transaction(xid, true)?.unwrap();
+
+        if transaction.is_skipped() {
+            return Ok(());
+        }
+
+        if self.stream_uncommitted {
+            Self::stream_begin(&self.builder_ptr, transaction, scn, timestamp)?;
+
+            // Provisional data: the commit or rollback marker follows when 5.4 is parsed.
+            // LOB pages follow the row, so out-of-row LOB values are not known here.
+            return self.builder_ptr.process_dml(scn, timestamp, position, v1, v2, transaction.attributes(), &LobContext::default());
+        }
+
+        if let Some(lob_id) = v2.lob_page().and_then(|x| x.lob_id) {
+            self.lob_owners.insert(lob_id, xid);
+        }
+
+        Self::reserve(&mut self.memory_manager, transaction, v1.size() + v2.size() + HEADER_TOTAL_SIZE)?;
+        transaction.append_double(scn, timestamp, position, v1, v2)?;
+
+        Ok(())
+    }
+
+    /// DDL is sent at commit of its transaction like DML
+    pub fn add_ddl_in_transaction(&mut self, xid : TypeXid, scn : TypeRecordScn, timestamp : TypeTimestamp, position : TypeRedoPosition, ddl : Vector) -> Result<()> {
+        if self.metadata_ptr.is_xid_skipped(xid) {
+            return Ok(());
+        }
+
+        let transaction = self.transactions_manager.find_transaction(xid, true)?.unwrap();
+
+        if transaction.is_skipped() {
+            return Ok(());
+        }
+
+        if self.stream_uncommitted {
+            Self::stream_begin(&self.builder_ptr, transaction, scn, timestamp)?;
+
+            return match ddl.into_data() {
+                VectorData::OpCode1801(ddl) => self.builder_ptr.process_ddl(scn, timestamp, ddl),
+                data => olr_perr!("Expected ddl vector, but got: {}", data),
+            };
+        }
+
+        Self::reserve(&mut self.memory_manager, transaction, ddl.size() + HEADER_TOTAL_SIZE)?;
+        transaction.append_single(scn, timestamp, position, ddl)
+    }
+
+    /// Begin of streamed transaction is delayed until the first change, when session attributes are known
+    fn stream_begin(builder_ptr : &JsonBuilder, transaction : &mut Transaction, scn : TypeRecordScn, timestamp : TypeTimestamp) -> Result<()> {
+        if transaction.is_streamed() {
+            return Ok(());
+        }
+
+        transaction.set_streamed();
+        builder_ptr.process_begin(transaction.scn().unwrap_or(scn), transaction.timestamp().unwrap_or(timestamp), transaction.xid(), transaction.attributes())
+    }
+
+    /// Adds LOB vector without transaction id to transaction which writes the same LOB
+    pub fn add_lob_in_transaction(&mut self, scn : TypeRecordScn, timestamp : TypeTimestamp, position : TypeRedoPosition, v1 : Vector) -> Result<()> {
+        if self.stream_uncommitted {
+            return Ok(());
+        }
+
+        let xid = match v1.lob_page().and_then(|x| x.lob_id).and_then(|x| self.lob_owners.get(&x)) {
+            Some(xid) => *xid,
+            None => return Ok(()),
+        };
+
+        let transaction = match self.transactions_manager.find_transaction(xid, false)? {
+            Some(transaction) if !transaction.is_skipped() => transaction,
+            _ => return Ok(()),
+        };
+
+        Self::reserve(&mut self.memory_manager, transaction, v1.size() + HEADER_TOTAL_SIZE)?;
+        transaction.append_single(scn, timestamp, position, v1)
+    }
 
+    fn reserve(memory_manager : &mut TransactionMemoryManager, transaction : &mut Transaction, added_size : usize) -> Result<()> {
         if added_size > TRANSACTION_CHUNK_BUFFER_SIZE {
             return olr_perr!("There is no capacity in data buffer for vectors with summary size: {}", added_size);
         }
 
         if !transaction.has_cappacity_for(added_size) {
-            let tr_chunk = self.memory_manager.get_transaction_chunk()?;
+            let tr_chunk = memory_manager.get_transaction_chunk()?;
             transaction.append_transaction_chunk(tr_chunk);
         }
 
-        transaction.append_double(v1, v2)?;
+        Ok(())
+    }
+
+    /// Removes transaction from the buffer. Returns it only when its changes
+    /// have to be replayed by the caller, i.e. committed and not streamed before.
+    pub fn close_transaction(&mut self, xid : TypeXid, scn : TypeRecordScn, timestamp : TypeTimestamp, is_rollback : bool) -> Result<Option<Transaction>> {
+        let transaction = self.transactions_manager.remove_transaction(xid);
+        self.lob_owners.retain(|_, owner| *owner != xid);
+
+        if self.metadata_ptr.is_xid_skipped(xid) {
+            if let Some(transaction) = transaction {
+                self.release_transaction(transaction)?;
+            }
+            return Ok(None);
+        }
+
+        if self.stream_uncommitted {
+            if let Some(transaction) = transaction {
+                if transaction.is_streamed() {
+                    self.builder_ptr.process_commit(scn, timestamp, xid, is_rollback, transaction.attributes())?;
+                }
+                self.release_transaction(transaction)?;
+            }
+            return Ok(None);
+        }
+
+        match transaction {
+            Some(transaction) if is_rollback || transaction.is_skipped() => {
+                self.release_transaction(transaction)?;
+                Ok(None)
+            },
+            transaction => Ok(transaction),
+        }
+    }
+
+    pub fn release_transaction(&mut self, mut transaction : Transaction) -> Result<()> {
+        for tr_chunk in transaction.take_chunks() {
+            self.memory_manager.delete_transaction_chunk(tr_chunk)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{builder::formats, common::{constants, errors::Result, testing, types::{TypeRedoPosition, TypeXid}}, oradefs::oracle_table::OracleTable, transactions::transaction::SessionAttributes};
+
+    use super::TransactionBuffer;
+
+    #[test]
+    fn test_close_transaction() -> Result<()> {
+        let context_ptr = testing::context();
+        let metadata_ptr = testing::metadata(context_ptr.clone());
+        let (builder_ptr, output) = testing::builder(context_ptr.clone(), metadata_ptr.clone(), formats::MESSAGE_FORMAT_DEFAULT);
+        let mut parser = testing::parser(context_ptr.clone(), builder_ptr.clone(), metadata_ptr.clone());
+        let mut buffer = TransactionBuffer::new(context_ptr, builder_ptr.clone(), metadata_ptr, false);
+
+        // Vectors of 20 changes do not fit in one chunk
+        let undo = testing::vector_bytes((99, 1), &[&[1u8; 4000]]);
+        let redo = testing::vector_bytes((99, 2), &[&[2u8; 3000], &[3u8; 5]]);
+        let (commited, rolled_back) = (TypeXid::new(1, 2, 3), TypeXid::new(1, 3, 4));
+
+        for xid in [commited, rolled_back] {
+            buffer.init_transaction(xid, 100.into(), 1.into())?;
+            for i in 0 .. 20 {
+                let position = TypeRedoPosition::new(7, i, 16);
+                buffer.add_double_in_transaction(xid, (100 + i as u64).into(), 1.into(), position, 
+                    testing::parse_vector(&mut parser, &undo), testing::parse_vector(&mut parser, &redo))?;
+            }
+        }
+
+        assert!(buffer.close_transaction(rolled_back, 200.into(), 2.into(), true)?.is_none());
+
+        let transaction = buffer.close_transaction(commited, 200.into(), 2.into(), false)?.unwrap();
+        assert_eq!(transaction.xid(), commited);
+
+        let entries : Vec<_> = transaction.entries().collect();
+        assert_eq!(entries.len(), 20);
+        for (i, entry) in entries.iter().enumerate() {
+            assert_eq!(entry.scn, (100 + i as u64).into());
+            assert_eq!(entry.position, TypeRedoPosition::new(7, i as u32, 16));
+            assert_eq!(entry.vector1, undo.as_slice());
+            assert_eq!(entry.vector2, redo.as_slice());
+
+            let vector = testing::parse_vector(&mut parser, entry.vector2);
+            assert_eq!(vector.opcode(), 0x6302);
+        }
+
+        buffer.release_transaction(transaction)?;
+        assert!(buffer.memory_manager.allocated_chunks.is_empty());
+        assert!(buffer.close_transaction(commited, 200.into(), 2.into(), false)?.is_none());
+
+        // Transaction is replayed by parser, builder has no messages yet
+        assert!(output.take().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_stream_uncommitted() -> Result<()> {
+        let context_ptr = testing::context();
+        let metadata_ptr = testing::metadata(context_ptr.clone());
+        let (builder_ptr, output) = testing::builder(context_ptr.clone(), metadata_ptr.clone(), formats::MESSAGE_FORMAT_DEFAULT);
+        let mut parser = testing::parser(context_ptr.clone(), builder_ptr.clone(), metadata_ptr.clone());
+        let mut buffer = TransactionBuffer::new(context_ptr, builder_ptr.clone(), metadata_ptr.clone(), true);
+
+        let table = OracleTable::new("SCOTT".to_string(), "T1".to_string(), vec![testing::column(1, "ID", constants::SYS_COL_TYPE_NUMBER)], vec![]);
+        testing::set_tables(&metadata_ptr, vec![(100, 101, table)]);
+
+        let (commited, rolled_back, empty) = (TypeXid::new(1, 2, 3), TypeXid::new(1, 3, 4), TypeXid::new(1, 4, 5));
+
+        for xid in [commited, rolled_back, empty] {
+            buffer.init_transaction(xid, 100.into(), 1.into())?;
+        }
+
+        for xid in [commited, rolled_back] {
+            let (undo, redo) = testing::insert_vectors_bytes(100, 101, xid, &[&[0xC1, 0x02]]);
+            for _ in 0 .. 2 {
+                buffer.add_double_in_transaction(xid, 101.into(), 1.into(), TypeRedoPosition::default(), 
+                    testing::parse_vector(&mut parser, &undo), testing::parse_vector(&mut parser, &redo))?;
+            }
+        }
+
+        // Changes are sent before commit and not kept, begin is sent once before the first change
+        let messages = output.take();
+        assert_eq!(messages.iter().map(|x| x["OP"].as_str().unwrap()).collect::<Vec<_>>(), ["start", "insert", "insert", "start", "insert", "insert"]);
+        assert_eq!(messages[1]["XID"], "0x0001.002.00000003");
+        assert_eq!(messages[4]["XID"], "0x0001.003.00000004");
+        assert!(buffer.memory_manager.allocated_chunks.is_empty());
+
+        assert!(buffer.close_transaction(rolled_back, 200.into(), 2.into(), true)?.is_none());
+        assert!(buffer.close_transaction(commited, 201.into(), 2.into(), false)?.is_none());
+        assert!(buffer.close_transaction(empty, 202.into(), 2.into(), false)?.is_none());
+
+        let messages = output.take();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0]["OP"], "rollback");
+        assert_eq!(messages[0]["SCN"], 200);
+        assert_eq!(messages[0]["XID"], "0x0001.003.00000004");
+        assert_eq!(messages[1]["OP"], "commit");
+        assert_eq!(messages[1]["SCN"], 201);
+        assert_eq!(messages[1]["XID"], "0x0001.002.00000003");
+        Ok(())
+    }
+
+    #[test]
+    fn test_skip_user() -> Result<()> {
+        let context_ptr = testing::context();
+        let metadata_ptr = testing::metadata(context_ptr.clone());
+        let (builder_ptr, _) = testing::builder(context_ptr.clone(), metadata_ptr.clone(), formats::MESSAGE_FORMAT_DEFAULT);
+        let mut parser = testing::parser(context_ptr.clone(), builder_ptr.clone(), metadata_ptr.clone());
+        let mut buffer = TransactionBuffer::new(context_ptr, builder_ptr.clone(), metadata_ptr.clone(), false);
+
+        let table = OracleTable::new("SCOTT".to_string(), "T1".to_string(), vec![testing::column(1, "ID", constants::SYS_COL_TYPE_NUMBER)], vec![]);
+        testing::set_tables(&metadata_ptr, vec![(100, 101, table)]);
+        metadata_ptr.add_skip_user("APPLY".to_string());
+
+        let (applied, replicated) = (TypeXid::new(1, 2, 3), TypeXid::new(1, 3, 4));
+
+        for (xid, user) in [(applied, "APPLY"), (replicated, "SCOTT")] {
+            buffer.init_transaction(xid, 100.into(), 1.into())?;
+
+            let (undo, redo) = testing::insert_vectors_bytes(100, 101, xid, &[&[0xC1, 0x02]]);
+            buffer.add_double_in_transaction(xid, 101.into(), 1.into(), TypeRedoPosition::default(), 
+                testing::parse_vector(&mut parser, &undo), testing::parse_vector(&mut parser, &redo))?;
+
+            // Session attributes may come after the first change, buffered changes are dropped then
+            let attributes = SessionAttributes { login_username : user.to_string(), session_number : 10, ..Default::default() };
+            buffer.set_session_attributes(xid, attributes)?;
+
+            buffer.add_double_in_transaction(xid, 102.into(), 1.into(), TypeRedoPosition::default(), 
+                testing::parse_vector(&mut parser, &undo), testing::parse_vector(&mut parser, &redo))?;
+        }
+
+        assert!(buffer.close_transaction(applied, 200.into(), 2.into(), false)?.is_none());
+
+        let transaction = buffer.close_transaction(replicated, 201.into(), 2.into(), false)?.unwrap();
+        assert_eq!(transaction.entries().count(), 2);
+        assert_eq!(transaction.attributes().unwrap().login_username, "SCOTT");
+        assert_eq!(transaction.attributes().unwrap().session_number, 10);
 
+        buffer.release_transaction(transaction)?;
+        assert!(buffer.memory_manager.allocated_chunks.is_empty());
         Ok(())
     }
 }
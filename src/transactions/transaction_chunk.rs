@@ -1,5 +1,4 @@
-use log::info;
-
+use crate::common::types::{TypeRecordScn, TypeRedoPosition, TypeTimestamp};
 use crate::parser::opcodes::Vector;
 use crate::common::errors::Result;
 
@@ -17,11 +16,26 @@ pub const TRANSACTION_CHUNK_SIZE : usize = 64 * 1024;
 pub const TRANSACTION_CHUNK_HEADER_SIZE : usize = size_of::<TransactionChunkHeader>();
 pub const TRANSACTION_CHUNK_BUFFER_SIZE : usize = TRANSACTION_CHUNK_SIZE - TRANSACTION_CHUNK_HEADER_SIZE;
 pub const HEADER_OFFSET_OP : usize = 0;
-pub const HEADER_OFFSET_VECTOR1 : usize = size_of::<u32>();
-pub const HEADER_OFFSET_VECTOR2 : usize = size_of::<u32>() + size_of::<Vector>();
-pub const HEADER_OFFSET_DATA : usize = size_of::<u32>() + size_of::<Vector>() + size_of::<Vector>();
-pub const HEADER_OFFSET_DATA_SIZE : usize = size_of::<u32>() + size_of::<Vector>() + size_of::<Vector>();
-pub const HEADER_TOTAL_SIZE : usize = size_of::<u32>() + size_of::<Vector>() + size_of::<Vector>() + size_of::<usize>();
+pub const HEADER_OFFSET_TIMESTAMP : usize = size_of::<u32>();
+pub const HEADER_OFFSET_SCN : usize = size_of::<u32>() + size_of::<u32>();
+pub const HEADER_OFFSET_SEQUENCE : usize = HEADER_OFFSET_SCN + size_of::<u64>();
+pub const HEADER_OFFSET_BLOCK : usize = HEADER_OFFSET_SEQUENCE + size_of::<u32>();
+pub const HEADER_OFFSET_OFFSET : usize = HEADER_OFFSET_BLOCK + size_of::<u32>();
+pub const HEADER_OFFSET_VECTOR1_SIZE : usize = HEADER_OFFSET_OFFSET + size_of::<u32>();
+pub const HEADER_OFFSET_VECTOR2_SIZE : usize = HEADER_OFFSET_VECTOR1_SIZE + size_of::<usize>();
+pub const HEADER_OFFSET_DATA_SIZE : usize = HEADER_OFFSET_VECTOR2_SIZE + size_of::<usize>();
+pub const HEADER_OFFSET_DATA : usize = HEADER_OFFSET_DATA_SIZE + size_of::<usize>();
+pub const HEADER_TOTAL_SIZE : usize = HEADER_OFFSET_DATA + size_of::<u64>(); // with alignment reserve
+
+/// One buffered change: a pair of raw redo vectors and the position of their record
+#[derive(Debug)]
+pub struct TransactionChunkEntry<'a> {
+    pub scn : TypeRecordScn,
+    pub timestamp : TypeTimestamp,
+    pub position : TypeRedoPosition,
+    pub vector1 : &'a [u8],
+    pub vector2 : &'a [u8],
+}
 
 #[derive(Debug)]
 pub struct TransactionChunk {
@@ -54,6 +68,10 @@ impl TransactionChunk {
         self.header.size
     }
 
+    pub(crate) fn elements(&self) -> usize {
+        self.header.elements
+    }
+
     pub(crate) fn set_prev(&mut self, chunk : *mut TransactionChunk) {
         self.header.prev = Some(chunk);
     }
@@ -61,7 +79,7 @@ impl TransactionChunk {
     pub(crate) fn set_next(&mut self, chunk : *mut TransactionChunk) {
         self.header.next = Some(chunk);
     }
-    
+
     pub(crate) fn begin(&mut self) -> *mut u8 {
         self.buffer.as_mut_ptr()
     }
@@ -70,21 +88,34 @@ impl TransactionChunk {
         unsafe { self.buffer.as_mut_ptr().add(self.size()) }
     }
 
-    pub(crate) fn append_double(&mut self, v1 : Vector, v2 : Vector) -> Result<()> {
+    pub(crate) fn append_double(&mut self, scn : TypeRecordScn, timestamp : TypeTimestamp, position : TypeRedoPosition, v1 : Vector, v2 : Vector) -> Result<()> {
         let opcodes: u32 = ((v1.opcode() as u32) << 16) | (v2.opcode() as u32);
-        let v1_data = v1.data();
-        let v2_data = v2.data();
-        let v1_size = v1.size();
-        let v2_size = v2.size();
-        let size = v1_size + v2_size + HEADER_TOTAL_SIZE;
-        
+        self.append(opcodes, scn, timestamp, position, v1.raw_data(), v2.raw_data())
+    }
+
+    /// Vector without pair is kept with empty second vector
+    pub(crate) fn append_single(&mut self, scn : TypeRecordScn, timestamp : TypeTimestamp, position : TypeRedoPosition, v1 : Vector) -> Result<()> {
+        let opcodes: u32 = (v1.opcode() as u32) << 16;
+        self.append(opcodes, scn, timestamp, position, v1.raw_data(), &[])
+    }
+
+    fn append(&mut self, opcodes : u32, scn : TypeRecordScn, timestamp : TypeTimestamp, position : TypeRedoPosition, v1 : &[u8], v2 : &[u8]) -> Result<()> {
+        let v1_size = v1.len();
+        let v2_size = v2.len();
+        let size = (v1_size + v2_size + HEADER_OFFSET_DATA + 7) & !7;
+
         unsafe {
-            std::ptr::write((self.end().add(HEADER_OFFSET_OP)) as *mut u32, opcodes);
-            std::ptr::write((self.end().add(HEADER_OFFSET_VECTOR1)) as *mut Vector, v1);
-            std::ptr::write((self.end().add(HEADER_OFFSET_VECTOR2)) as *mut Vector, v2);
-            std::ptr::copy_nonoverlapping(v1_data, self.end().add(HEADER_OFFSET_DATA), v1_size);
-            std::ptr::copy_nonoverlapping(v2_data, self.end().add(HEADER_OFFSET_DATA + v1_size), v2_size);
-            std::ptr::write((self.end().add(HEADER_OFFSET_DATA_SIZE)) as *mut usize, size);
+            std::ptr::write_unaligned((self.end().add(HEADER_OFFSET_OP)) as *mut u32, opcodes);
+            std::ptr::write_unaligned((self.end().add(HEADER_OFFSET_TIMESTAMP)) as *mut u32, timestamp.into());
+            std::ptr::write_unaligned((self.end().add(HEADER_OFFSET_SCN)) as *mut u64, scn.into());
+            std::ptr::write_unaligned((self.end().add(HEADER_OFFSET_SEQUENCE)) as *mut u32, position.sequence);
+            std::ptr::write_unaligned((self.end().add(HEADER_OFFSET_BLOCK)) as *mut u32, position.block);
+            std::ptr::write_unaligned((self.end().add(HEADER_OFFSET_OFFSET)) as *mut u32, position.offset as u32);
+            std::ptr::write_unaligned((self.end().add(HEADER_OFFSET_VECTOR1_SIZE)) as *mut usize, v1_size);
+            std::ptr::write_unaligned((self.end().add(HEADER_OFFSET_VECTOR2_SIZE)) as *mut usize, v2_size);
+            std::ptr::write_unaligned((self.end().add(HEADER_OFFSET_DATA_SIZE)) as *mut usize, size);
+            std::ptr::copy_nonoverlapping(v1.as_ptr(), self.end().add(HEADER_OFFSET_DATA), v1_size);
+            std::ptr::copy_nonoverlapping(v2.as_ptr(), self.end().add(HEADER_OFFSET_DATA + v1_size), v2_size);
         }
 
         self.header.elements += 1;
@@ -92,4 +123,31 @@ impl TransactionChunk {
 
         Ok(())
     }
+
+    pub(crate) fn entries(&self) -> impl Iterator<Item = TransactionChunkEntry<'_>> {
+        let mut offset = 0;
+
+        (0 .. self.elements()).map(move |_| {
+            unsafe {
+                let ptr = self.buffer.as_ptr().add(offset);
+                let timestamp = std::ptr::read_unaligned(ptr.add(HEADER_OFFSET_TIMESTAMP) as *const u32);
+                let scn = std::ptr::read_unaligned(ptr.add(HEADER_OFFSET_SCN) as *const u64);
+                let sequence = std::ptr::read_unaligned(ptr.add(HEADER_OFFSET_SEQUENCE) as *const u32);
+                let block = std::ptr::read_unaligned(ptr.add(HEADER_OFFSET_BLOCK) as *const u32);
+                let record_offset = std::ptr::read_unaligned(ptr.add(HEADER_OFFSET_OFFSET) as *const u32);
+                let v1_size = std::ptr::read_unaligned(ptr.add(HEADER_OFFSET_VECTOR1_SIZE) as *const usize);
+                let v2_size = std::ptr::read_unaligned(ptr.add(HEADER_OFFSET_VECTOR2_SIZE) as *const usize);
+                let size = std::ptr::read_unaligned(ptr.add(HEADER_OFFSET_DATA_SIZE) as *const usize);
+                offset += size;
+
+                TransactionChunkEntry {
+                    scn : scn.into(),
+                    timestamp : timestamp.into(),
+                    position : TypeRedoPosition::new(sequence, block, record_offset as u16),
+                    vector1 : std::slice::from_raw_parts(ptr.add(HEADER_OFFSET_DATA), v1_size),
+                    vector2 : std::slice::from_raw_parts(ptr.add(HEADER_OFFSET_DATA + v1_size), v2_size),
+                }
+            }
+        })
+    }
 }